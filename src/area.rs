@@ -0,0 +1,140 @@
+// one Area trait for every type that has an area, so Rect::area and
+// calc_area can be used by the same generic code
+
+use crate::rect::Rect;
+use crate::shape::{calc_area_unchecked, Shape};
+
+pub trait Area {
+    fn area(&self) -> f64;
+
+    fn unit_label(&self) -> &'static str {
+        "units²"
+    }
+}
+
+// a u32 times a u32 fits in a u64, and every u64 below 2^53 is exact as an f64
+impl Area for Rect {
+    fn area(&self) -> f64 {
+        (self.len as u64 * self.breadth as u64) as f64
+    }
+}
+
+// like area_stats this uses calc_area_unchecked, validate_shape catches bad shapes first
+impl Area for Shape {
+    fn area(&self) -> f64 {
+        calc_area_unchecked(self)
+    }
+}
+
+impl<T: Area + ?Sized> Area for &T {
+    fn area(&self) -> f64 {
+        (**self).area()
+    }
+
+    fn unit_label(&self) -> &'static str {
+        (**self).unit_label()
+    }
+}
+
+// an empty slice has a total of 0.0. this and every other f64 total in the
+// crate is a fold from 0.0, because Iterator::sum on floats starts from -0.0
+// and an empty sum would print as "-0"
+pub fn total_area<T: Area>(items: &[T]) -> f64 {
+    items.iter().fold(0.0, |total, item| total + item.area())
+}
+
+// the first of equal areas wins a tie
+pub fn largest_by_area<T: Area>(items: &[T]) -> Option<&T> {
+    let mut largest = items.first()?;
+    for item in &items[1..] {
+        if item.area().total_cmp(&largest.area()).is_gt() {
+            largest = item;
+        }
+    }
+    Some(largest)
+}
+
+// a slice of trait objects can mix every type that has an area, 0.0 when empty
+pub fn total_area_dyn(items: &[&dyn Area]) -> f64 {
+    items.iter().fold(0.0, |total, item| total + item.area())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // its own unit, to check the default label can be replaced
+    struct Acre(f64);
+
+    impl Area for Acre {
+        fn area(&self) -> f64 {
+            self.0
+        }
+
+        fn unit_label(&self) -> &'static str {
+            "acres"
+        }
+    }
+
+    #[test]
+    fn totals_for_slices_of_one_type() {
+        let rects = [Rect::new(2, 3), Rect::new(4, 5), Rect::new(1, 1)];
+        assert_eq!(total_area(&rects), 27.0);
+        let shapes = [Shape::Rect(2.0, 3.0), Shape::Scaled(2.0, Box::new(Shape::Rect(1.0, 1.0)))];
+        assert_eq!(total_area(&shapes), 10.0);
+        let circles = [Shape::Circle(1.0), Shape::Circle(2.0)];
+        assert!((total_area(&circles) - 5.0 * std::f64::consts::PI).abs() < 1e-12);
+        // references to the items work the same through the &T impl
+        let refs: Vec<&Rect> = rects.iter().collect();
+        assert_eq!(total_area(&refs), 27.0);
+    }
+
+    #[test]
+    fn a_mixed_dyn_slice_adds_up_every_type() {
+        let rect = Rect::new(3, 3);
+        let shape = Shape::Group(vec![Shape::Rect(1.0, 2.0), Shape::Rect(0.5, 2.0)]);
+        let field = Acre(0.5);
+        let items: [&dyn Area; 3] = [&rect, &shape, &field];
+        assert_eq!(total_area_dyn(&items), 12.5);
+        let labels: Vec<&str> = items.iter().map(|item| item.unit_label()).collect();
+        assert_eq!(labels, ["units²", "units²", "acres"]);
+    }
+
+    #[test]
+    fn empty_slices() {
+        let none: [Rect; 0] = [];
+        let total = total_area(&none);
+        // +0.0, not the -0.0 that Iterator::sum gives for floats
+        assert_eq!(total, 0.0);
+        assert!(total.is_sign_positive());
+        assert!(total_area_dyn(&[]).is_sign_positive());
+        assert_eq!(largest_by_area(&none), None);
+    }
+
+    #[test]
+    fn largest_by_area_takes_the_first_of_a_tie() {
+        let rects = [Rect::new(1, 6), Rect::new(2, 3), Rect::new(3, 2), Rect::new(1, 1)];
+        assert!(std::ptr::eq(largest_by_area(&rects).unwrap(), &rects[0]));
+        let shapes = [Shape::Rect(1.0, 1.0), Shape::Circle(1.0), Shape::Rect(3.0, 1.0)];
+        assert_eq!(largest_by_area(&shapes), Some(&shapes[1]));
+        assert_eq!(largest_by_area(&[Rect::new(9, 9)]), Some(&Rect::new(9, 9)));
+    }
+
+    #[test]
+    fn integer_rect_areas_are_exact_below_two_to_the_53() {
+        let side = (1u32 << 26) + 1;
+        let cases = [
+            Rect::new(side, side),
+            Rect::new(u32::MAX, 1 << 21),
+            Rect::new(u32::MAX - 2, 3),
+            Rect::new(0, u32::MAX),
+        ];
+        for rect in cases {
+            let exact = rect.len as u64 * rect.breadth as u64;
+            assert!(exact < 1 << 53);
+            assert_eq!(Area::area(&rect) as u64, exact, "{:?}", rect);
+        }
+        // far above a u32 without overflowing
+        assert_eq!(Area::area(&Rect::new(u32::MAX, u32::MAX)), (u32::MAX as f64).powi(2));
+    }
+}
@@ -0,0 +1,63 @@
+// a day's agenda built on rust_tut::datetime::events, clashes are flagged
+// and the free time between meetings is listed at the end
+
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+use rust_tut::datetime::events::{find_conflicts, free_slots, Event};
+use rust_tut::style;
+
+fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 3, 14, hour, minute, 0).unwrap()
+}
+
+fn main(){
+    style::init_from_env();
+
+    let plan = [
+        ("standup", (9, 30), (9, 45)),
+        ("design review", (10, 0), (11, 0)),
+        ("1:1 with Priya", (10, 30), (11, 0)),
+        ("lunch", (12, 30), (13, 30)),
+        ("deploy window", (15, 0), (17, 0)),
+        ("interview", (16, 0), (16, 45)),
+        ("broken invite", (14, 0), (13, 0)),
+    ];
+
+    let mut events = Vec::new();
+    for (title, (h1, m1), (h2, m2)) in plan {
+        match Event::new(title, at(h1, m1), at(h2, m2)) {
+            Ok(event) => events.push(event),
+            Err(err) => eprintln!("{} {}", style::red("skipped:"), err),
+        }
+    }
+    events.sort();
+
+    let conflicts = find_conflicts(&events);
+    println!("{}", style::heading("agenda for 2025-03-14"));
+    for (i, event) in events.iter().enumerate() {
+        let clashes: Vec<&str> = conflicts
+            .iter()
+            .filter_map(|&(a, b)| match (a == i, b == i) {
+                (true, _) => Some(events[b].title.as_str()),
+                (_, true) => Some(events[a].title.as_str()),
+                _ => None,
+            })
+            .collect();
+        let line = format!(
+            "{}-{}  {}",
+            event.start.format("%H:%M"),
+            event.end.format("%H:%M"),
+            event.title
+        );
+        if clashes.is_empty() {
+            println!("{}", line);
+        } else {
+            println!("{}  {}", line, style::red(&format!("clashes with {}", clashes.join(", "))));
+        }
+    }
+
+    println!();
+    println!("{}", style::heading("free for 30 minutes or more (09:00 to 18:00)"));
+    for (from, to) in free_slots(&events, at(9, 0), at(18, 0), TimeDelta::minutes(30)) {
+        println!("{}-{}", from.format("%H:%M"), to.format("%H:%M"));
+    }
+}
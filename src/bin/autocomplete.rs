@@ -0,0 +1,55 @@
+// prefix completion with rust_tut::trie::Trie
+//
+// cargo run --bin autocomplete -- ca
+// cargo run --bin autocomplete -- ca my_words.txt
+// without a file it uses the crate's sample word list
+
+use std::fs::read_to_string;
+
+use rust_tut::report::args_without_report_flags;
+use rust_tut::error::{run_main, AppError, Contextual};
+use rust_tut::sample_data::word_list;
+use rust_tut::trie::Trie;
+
+fn main(){
+    run_main(run)
+}
+
+fn run() -> Result<(), AppError> {
+    let args = args_without_report_flags();
+    let prefix = args.first().map(String::as_str).unwrap_or("c");
+
+    let mut trie = Trie::new();
+    match args.get(1) {
+        Some(path) => {
+            let text = read_to_string(path).context(format!("reading {}", path))?;
+            for word in text.split_whitespace() {
+                trie.insert(word);
+            }
+        }
+        None => {
+            for word in word_list() {
+                trie.insert(word);
+            }
+        }
+    }
+    println!("{} words loaded", trie.len());
+    println!("completions for {:?}: {:?}", prefix, trie.words_with_prefix(prefix));
+
+    // usernames are not just ascii
+    let mut users = Trie::new();
+    for name in ["car", "cart", "carton", "zoë", "zoey", "日本語"] {
+        users.insert(name);
+    }
+    println!("zo -> {:?}", users.words_with_prefix("zo"));
+    println!("日本 is a prefix? {}", users.starts_with("日本"));
+    users.remove("carton");
+    println!(
+        "after removing carton: car? {} cart? {} ca -> {:?}",
+        users.contains("car"),
+        users.contains("cart"),
+        users.words_with_prefix("ca")
+    );
+    println!("inserting \"\" adds a word? {}", users.insert(""));
+    Ok(())
+}
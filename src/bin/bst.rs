@@ -0,0 +1,45 @@
+// rust_tut::bst, a binary search tree built out of Box and Option
+
+use rust_tut::bst::Bst;
+use rust_tut::rng::Rng;
+
+fn shuffled(count: i64, seed: u64) -> Vec<i64> {
+    let mut values: Vec<i64> = (1..=count).collect();
+    let mut rng = Rng::new(seed);
+    // Fisher-Yates
+    for i in (1..values.len()).rev() {
+        let j = rng.range(0, i as i64 + 1) as usize;
+        values.swap(i, j);
+    }
+    values
+}
+
+fn main(){
+    let mut tree = Bst::new();
+    for value in shuffled(100, 42) {
+        tree.insert(value);
+    }
+    println!("inserting 50 again adds it? {}", tree.insert(50));
+    let sorted: Vec<i64> = tree.iter().copied().collect();
+    println!("{} values, sorted? {}", tree.len(), sorted.windows(2).all(|w| w[0] < w[1]));
+    println!("min {:?} max {:?} height {}", tree.min(), tree.max(), tree.height());
+
+    let mut small = Bst::new();
+    for value in [8, 3, 10, 1, 6, 14, 4, 7, 13] {
+        small.insert(value);
+    }
+    println!("{:?}", small.iter().collect::<Vec<_>>());
+    small.remove(&13); // a leaf
+    small.remove(&14); // one child left after 13 went
+    small.remove(&3); // two children
+    println!("without 13, 14 and 3: {:?} (len {})", small.iter().collect::<Vec<_>>(), small.len());
+    println!("removing 99 finds it? {}", small.remove(&99));
+    println!("contains 6? {} contains 3? {}", small.contains(&6), small.contains(&3));
+
+    // inserting in sorted order makes every node a right child
+    let mut line = Bst::new();
+    for value in 0..1000 {
+        line.insert(value);
+    }
+    println!("sorted insert of 1000 values has height {}", line.height());
+}
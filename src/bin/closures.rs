@@ -0,0 +1,160 @@
+// closures and the Fn / FnMut / FnOnce traits
+
+use std::thread;
+use std::time::Duration;
+
+use rust_tut::cache::{Cache, Metered, UnboundedCache};
+use rust_tut::lru::LruCache;
+
+// remembers results in whatever cache it is given, which decides what to forget
+// (the book's version kept one value, so a second argument got the first answer)
+struct Cacher<F: Fn(u32) -> u32, C: Cache<u32, u32>> {
+    calculation: F,
+    values: C,
+}
+
+impl<F: Fn(u32) -> u32, C: Cache<u32, u32>> Cacher<F, C> {
+    fn new(calculation: F, values: C) -> Cacher<F, C> {
+        Cacher { calculation, values }
+    }
+
+    fn value(&mut self, arg: u32) -> u32 {
+        match self.values.get(&arg) {
+            Some(&v) => v,
+            None => {
+                let v = (self.calculation)(arg);
+                self.values.put(arg, v);
+                v
+            }
+        }
+    }
+}
+
+// returning a closure: each call builds a new one that captures x
+fn make_adder(x: i32) -> impl Fn(i32) -> i32 {
+    move |y| x + y
+}
+
+// FnMut can change what it captured, so it can be called many times
+fn call_n_times<F: FnMut()>(n: usize, mut f: F) {
+    for _ in 0..n {
+        f();
+    }
+}
+
+// FnOnce may move its captures out, so it can only be called once
+fn consume<F: FnOnce() -> String>(f: F) -> String {
+    f()
+}
+
+fn main(){
+    let mut slow_square = Cacher::new(
+        |n| {
+            println!("  calculating {}...", n);
+            thread::sleep(Duration::from_millis(100));
+            n * n
+        },
+        LruCache::new(2).unwrap(),
+    );
+    println!("square of 4 is {}", slow_square.value(4));
+    println!("square of 4 again is {}", slow_square.value(4));
+    println!("square of 7 is {}", slow_square.value(7));
+    // room for two, so 9 pushes out 4, the one used longest ago
+    println!("square of 9 is {}", slow_square.value(9));
+    println!("square of 4 is calculated again: {}", slow_square.value(4));
+
+    // an unbounded cache never forgets, Metered counts how often it helped
+    let mut cube = Cacher::new(|n| n * n * n, Metered::new(UnboundedCache::new()));
+    for n in [2, 3, 2, 2, 3, 4] {
+        cube.value(n);
+    }
+    let metrics = cube.values.metrics();
+    println!(
+        "cubes: {} hits, {} misses, {} cached",
+        metrics.hits,
+        metrics.misses,
+        cube.values.len()
+    );
+
+    let add_two = make_adder(2);
+    let add_ten = make_adder(10);
+    println!("add_ten(add_two(5)) = {}", add_ten(add_two(5)));
+
+    let mut counter = 0;
+    call_n_times(3, || counter += 1);
+    println!("counter after 3 calls is {}", counter);
+
+    let name = String::from("Pawan");
+    let greeting = consume(move || name + " says hi");
+    println!("{}", greeting);
+    // println!("{}", name); // error: name was moved into the closure
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn cacher_remembers_each_argument_separately() {
+        let calls = Cell::new(0);
+        let mut double = Cacher::new(
+            |n| {
+                calls.set(calls.get() + 1);
+                n * 2
+            },
+            UnboundedCache::new(),
+        );
+        assert_eq!(double.value(1), 2);
+        // the book's bug would give 2 here
+        assert_eq!(double.value(5), 10);
+        assert_eq!(double.value(1), 2);
+        assert_eq!(double.value(5), 10);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn cacher_with_an_lru_recalculates_what_it_forgot() {
+        let calls = Cell::new(0);
+        let mut square = Cacher::new(
+            |n| {
+                calls.set(calls.get() + 1);
+                n * n
+            },
+            LruCache::new(2).unwrap(),
+        );
+        for n in [4, 7, 9, 4] {
+            square.value(n);
+        }
+        assert_eq!(square.value(4), 16);
+        assert_eq!(calls.get(), 4);
+    }
+
+    #[test]
+    fn adders_compose() {
+        let add_two = make_adder(2);
+        let add_ten = make_adder(10);
+        assert_eq!(add_ten(add_two(5)), 17);
+        let compose = |f: &dyn Fn(i32) -> i32, g: &dyn Fn(i32) -> i32, x| g(f(x));
+        assert_eq!(compose(&add_two, &make_adder(-2), 0), 0);
+    }
+
+    #[test]
+    fn fn_mut_sees_every_call() {
+        let mut seen = Vec::new();
+        let mut counter = 0;
+        call_n_times(4, || {
+            counter += 1;
+            seen.push(counter);
+        });
+        assert_eq!(counter, 4);
+        assert_eq!(seen, [1, 2, 3, 4]);
+        call_n_times(0, || panic!("never called"));
+    }
+
+    #[test]
+    fn fn_once_can_move_its_capture_out() {
+        let name = String::from("Pawan");
+        assert_eq!(consume(move || name + " says hi"), "Pawan says hi");
+    }
+}
@@ -0,0 +1,44 @@
+// compares the words used in two text files with rust_tut::set_ops
+//
+// cargo run --bin compare_words -- rust.txt src/words.txt
+
+use std::collections::{BTreeSet, HashSet};
+use std::fs::read_to_string;
+
+use rust_tut::set_ops::{
+    is_subset_chain, jaccard_similarity, multi_intersection, symmetric_diff_sorted,
+};
+use rust_tut::text::word_frequencies;
+
+fn word_set(path: &str) -> HashSet<String> {
+    match read_to_string(path) {
+        Ok(text) => word_frequencies(&text).iter().map(|(word, _)| word.clone()).collect(),
+        Err(err) => {
+            eprintln!("error: could not read {}: {}", path, err);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn main(){
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [first, second] = args.as_slice() else {
+        eprintln!("usage: compare_words <file> <file>");
+        std::process::exit(2);
+    };
+
+    let sets = [word_set(first), word_set(second)];
+    let (a, b) = (&sets[0], &sets[1]);
+    println!("{} has {} different words, {} has {}", first, a.len(), second, b.len());
+    println!("jaccard similarity: {:.3}", jaccard_similarity(a, b));
+
+    let common: BTreeSet<String> = multi_intersection(&sets).into_iter().collect();
+    println!("in both: {:?}", common);
+
+    let sorted_a: BTreeSet<String> = a.iter().cloned().collect();
+    let sorted_b: BTreeSet<String> = b.iter().cloned().collect();
+    let only_one = symmetric_diff_sorted(&sorted_a, &sorted_b);
+    let first_few = &only_one[..only_one.len().min(8)];
+    println!("in just one of them: {} words, the first few {:?}", only_one.len(), first_few);
+    println!("every word of {} is in {}? {}", first, second, is_subset_chain(&sets));
+}
@@ -0,0 +1,142 @@
+// const generics: the size of the matrix is part of its type
+// so multiplying matrices with the wrong shapes is a compile error
+
+use std::ops::{Index, IndexMut};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Matrix<const R: usize, const C: usize> {
+    data: [[f64; C]; R],
+}
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    fn new(data: [[f64; C]; R]) -> Matrix<R, C> {
+        Matrix { data }
+    }
+
+    fn zero() -> Matrix<R, C> {
+        Matrix { data: [[0.0; C]; R] }
+    }
+
+    fn transpose(&self) -> Matrix<C, R> {
+        let mut out = Matrix::<C, R>::zero();
+        for i in 0..R {
+            for j in 0..C {
+                out[(j, i)] = self[(i, j)];
+            }
+        }
+        out
+    }
+
+    // (R x C) * (C x K) = (R x K), the shared C is checked by the compiler
+    fn mul<const K: usize>(&self, other: &Matrix<C, K>) -> Matrix<R, K> {
+        let mut out = Matrix::<R, K>::zero();
+        for i in 0..R {
+            for j in 0..K {
+                for k in 0..C {
+                    out[(i, j)] += self[(i, k)] * other[(k, j)];
+                }
+            }
+        }
+        out
+    }
+}
+
+// identity only makes sense for square matrices, so it gets its own impl
+impl<const N: usize> Matrix<N, N> {
+    fn identity() -> Matrix<N, N> {
+        let mut out = Matrix::zero();
+        for i in 0..N {
+            out[(i, i)] = 1.0;
+        }
+        out
+    }
+}
+
+impl<const R: usize, const C: usize> Index<(usize, usize)> for Matrix<R, C> {
+    type Output = f64;
+
+    fn index(&self, (row, col): (usize, usize)) -> &f64 {
+        &self.data[row][col]
+    }
+}
+
+impl<const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<R, C> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut f64 {
+        &mut self.data[row][col]
+    }
+}
+
+// works for an array of any length, N is inferred at the call site
+fn min_array<const N: usize>(arr: [i32; N]) -> i32 {
+    let mut min = i32::MAX;
+    for value in arr {
+        if value < min {
+            min = value;
+        }
+    }
+    min
+}
+
+fn main(){
+    let a = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let b = Matrix::new([[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]]);
+
+    let product = a.mul(&b);
+    println!("2x3 * 3x2 = {:?}", product.data);
+    println!("element (1, 0) is {}", product[(1, 0)]);
+
+    println!("transpose of a: {:?}", a.transpose().data);
+    println!("transposed twice equals a? {}", a.transpose().transpose() == a);
+
+    let id = Matrix::<3, 3>::identity();
+    println!("a * I == a? {}", a.mul(&id) == a);
+
+    // a.mul(&a); // error: expected `Matrix<3, _>`, found `Matrix<2, 3>`
+    // Matrix::<2, 3>::identity(); // error: no function `identity` for Matrix<2, 3>
+
+    println!("min of [4, -2, 9] is {}", min_array([4, -2, 9]));
+    println!("min of [] is {} (i32::MAX)", min_array([]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_by_three_times_three_by_two() {
+        let a = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let b = Matrix::new([[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]]);
+        assert_eq!(a.mul(&b), Matrix::new([[58.0, 64.0], [139.0, 154.0]]));
+        // and the other way round gives a 3x3
+        assert_eq!(b.mul(&a)[(2, 2)], 11.0 * 3.0 + 12.0 * 6.0);
+    }
+
+    #[test]
+    fn transpose_twice_is_the_original() {
+        let a = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let t = a.transpose();
+        assert_eq!(t, Matrix::new([[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]));
+        assert_eq!(t.transpose(), a);
+    }
+
+    #[test]
+    fn multiplying_by_the_identity_changes_nothing() {
+        let a = Matrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        assert_eq!(a.mul(&Matrix::<3, 3>::identity()), a);
+        assert_eq!(Matrix::<2, 2>::identity().mul(&a), a);
+    }
+
+    #[test]
+    fn index_mut_writes_one_element() {
+        let mut m = Matrix::<2, 2>::zero();
+        m[(0, 1)] = 5.0;
+        assert_eq!(m.data, [[0.0, 5.0], [0.0, 0.0]]);
+    }
+
+    #[test]
+    fn min_array_of_any_length() {
+        assert_eq!(min_array([4, -2, 9]), -2);
+        assert_eq!(min_array([7]), 7);
+        assert_eq!(min_array([]), i32::MAX);
+    }
+}
@@ -0,0 +1,30 @@
+// rust_tut::contention: timing a Mutex, an atomic and sharded atomics
+// under the same load
+//
+// cargo run --release --bin contention
+
+use std::time::Instant;
+
+use rust_tut::contention::{
+    concurrent_count_atomic, concurrent_count_mutex, concurrent_count_sharded,
+};
+
+const INCREMENTS: u64 = 200_000;
+
+fn time(label: &str, run: impl Fn() -> u64) {
+    let start = Instant::now();
+    let count = run();
+    println!("  {:<8} {:>9} in {:?}", label, count, start.elapsed());
+}
+
+fn main(){
+    for threads in [1, 4, 16] {
+        println!("{} threads x {} increments", threads, INCREMENTS);
+        time("mutex", || concurrent_count_mutex(threads, INCREMENTS));
+        time("atomic", || concurrent_count_atomic(threads, INCREMENTS));
+        time("sharded", || concurrent_count_sharded(threads, INCREMENTS, threads));
+    }
+
+    // more shards than threads: the extra shards just stay at zero
+    println!("2 threads, 8 shards: {}", concurrent_count_sharded(2, 10, 8));
+}
@@ -0,0 +1,36 @@
+// rust_tut::counter::Counter, counting things with set-like operators
+
+use rust_tut::counter::Counter;
+use rust_tut::text::{is_anagram, word_frequencies};
+
+fn main(){
+    let a: Counter<char> = "hello world".chars().filter(|c| c.is_alphabetic()).collect();
+    let b: Counter<char> = "lemon".chars().collect();
+    println!("a: {:?}", a.most_common(10));
+    println!("b: {:?}", b.most_common(10));
+    println!("count of 'z' in a: {}", a.count(&'z'));
+    println!("a + b: {:?}", (&a + &b).most_common(3));
+    println!("a - b: {:?}", (&a - &b).most_common(10));
+    println!("a & b: {:?}", (&a & &b).most_common(10));
+    println!("a | b has {} letters, {} in total", (&a | &b).len(), (&a | &b).total());
+
+    let mut seen = Counter::new();
+    for word in ["rust", "go", "rust"] {
+        seen.add_one(word);
+    }
+    println!("rust was seen {} times", seen.count(&"rust"));
+
+    let words = word_frequencies("the cat and the hat and the bat");
+    println!("most common words: {:?}", words.most_common(2));
+
+    let pairs = [
+        ("listen", "silent"),
+        ("Dormitory", "dirty room"),
+        ("schoolmaster", "the classroom"),
+        ("straße", "ßtraes"),
+        ("rust", "trusty"),
+    ];
+    for (x, y) in pairs {
+        println!("{:?} and {:?} anagrams? {}", x, y, is_anagram(x, y));
+    }
+}
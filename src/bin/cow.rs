@@ -0,0 +1,122 @@
+// Cow (clone on write): borrow when we can, allocate only when we must
+
+use std::borrow::Cow;
+use std::time::Instant;
+
+// collapses runs of whitespace into one space and trims the ends
+// clean input comes back borrowed, so nothing is allocated
+fn normalize_whitespace(input: &str) -> Cow<'_, str> {
+    let mut prev_space = true;
+    let mut clean = !input.ends_with(char::is_whitespace);
+    for c in input.chars() {
+        if c.is_whitespace() && (prev_space || c != ' ') {
+            clean = false;
+            break;
+        }
+        prev_space = c.is_whitespace();
+    }
+    if clean {
+        return Cow::Borrowed(input);
+    }
+    Cow::Owned(input.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+// the naive version always builds a new String
+fn normalize_whitespace_alloc(input: &str) -> String {
+    input.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// a slice of the input is still a borrow, so no allocation either way
+fn strip_prefix_or_keep<'a>(input: &'a str, prefix: &str) -> Cow<'a, str> {
+    match input.strip_prefix(prefix) {
+        Some(rest) => Cow::Borrowed(rest),
+        None => Cow::Borrowed(input),
+    }
+}
+
+// takes both &'static str and String without forcing callers to convert
+fn label(text: impl Into<Cow<'static, str>>) -> String {
+    let text = text.into();
+    match text {
+        Cow::Borrowed(s) => format!("borrowed: {}", s),
+        Cow::Owned(s) => format!("owned: {}", s),
+    }
+}
+
+fn describe(value: Cow<'_, str>) -> String {
+    let kind = if matches!(value, Cow::Borrowed(_)) { "Borrowed" } else { "Owned" };
+    format!("{:?} ({})", value, kind)
+}
+
+fn main(){
+    for input in ["already clean", "  needs\t\tcleaning  ", "two  spaces"] {
+        println!("{:?} -> {}", input, describe(normalize_whitespace(input)));
+    }
+
+    println!("{}", describe(strip_prefix_or_keep("src/bin/cow.rs", "src/")));
+    println!("{}", describe(strip_prefix_or_keep("Cargo.toml", "src/")));
+
+    println!("{}", label("a literal"));
+    println!("{}", label(String::from("a String")));
+
+    // most inputs in real text are clean, which is where Cow wins
+    let input = "the quick brown fox jumps over the lazy dog";
+    let start = Instant::now();
+    for _ in 0..100_000 {
+        std::hint::black_box(normalize_whitespace(std::hint::black_box(input)));
+    }
+    println!("Cow version:    {:?}", start.elapsed());
+
+    let start = Instant::now();
+    for _ in 0..100_000 {
+        std::hint::black_box(normalize_whitespace_alloc(std::hint::black_box(input)));
+    }
+    println!("always-alloc:   {:?}", start.elapsed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_input_is_borrowed() {
+        for input in ["already clean", "", "one", "a b c"] {
+            let result = normalize_whitespace(input);
+            assert!(matches!(result, Cow::Borrowed(_)), "{:?}", input);
+            assert_eq!(result, input);
+        }
+    }
+
+    #[test]
+    fn dirty_input_is_owned_and_cleaned() {
+        let cases = [
+            ("  needs\t\tcleaning  ", "needs cleaning"),
+            ("two  spaces", "two spaces"),
+            ("tab\there", "tab here"),
+            ("trailing ", "trailing"),
+            (" leading", "leading"),
+            ("line\nbreak", "line break"),
+            ("   ", ""),
+        ];
+        for (input, expected) in cases {
+            let result = normalize_whitespace(input);
+            assert!(matches!(result, Cow::Owned(_)), "{:?}", input);
+            assert_eq!(result, expected);
+            assert_eq!(normalize_whitespace_alloc(input), expected);
+        }
+    }
+
+    #[test]
+    fn stripping_a_prefix_never_allocates() {
+        let stripped = strip_prefix_or_keep("src/bin/cow.rs", "src/");
+        assert!(matches!(stripped, Cow::Borrowed("bin/cow.rs")));
+        let kept = strip_prefix_or_keep("Cargo.toml", "src/");
+        assert!(matches!(kept, Cow::Borrowed("Cargo.toml")));
+    }
+
+    #[test]
+    fn label_takes_literals_and_strings() {
+        assert_eq!(label("a literal"), "borrowed: a literal");
+        assert_eq!(label(String::from("a String")), "owned: a String");
+    }
+}
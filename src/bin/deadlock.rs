@@ -0,0 +1,32 @@
+// rust_tut::locking: two threads, two locks, opposite order
+//
+// cargo run --bin deadlock
+
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rust_tut::locking::{deadlock_demo, try_lock_timeout};
+
+fn main(){
+    let m = Mutex::new(5);
+    let free = try_lock_timeout(&m, Duration::from_millis(10), Duration::from_millis(1));
+    println!("uncontended lock: {:?}", free.as_deref());
+    drop(free);
+
+    thread::scope(|s| {
+        let guard = m.lock().unwrap();
+        s.spawn(|| {
+            let start = Instant::now();
+            let got = try_lock_timeout(&m, Duration::from_millis(30), Duration::from_millis(5));
+            println!("lock held elsewhere: got it? {} after {:?}", got.is_some(), start.elapsed());
+        });
+        thread::sleep(Duration::from_millis(100));
+        drop(guard);
+    });
+
+    println!("without backing off: {}", deadlock_demo(false));
+    let start = Instant::now();
+    let outcome = deadlock_demo(true);
+    println!("with back off and jitter: {} in {:?}", outcome, start.elapsed());
+}
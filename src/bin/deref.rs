@@ -0,0 +1,170 @@
+// Deref and DerefMut: making our own smart pointer behave like a reference
+
+use std::cell::Cell;
+use std::ops::{Deref, DerefMut};
+
+struct MyBox<T>(T);
+
+impl<T> MyBox<T> {
+    fn new(value: T) -> MyBox<T> {
+        MyBox(value)
+    }
+}
+
+impl<T> Deref for MyBox<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for MyBox<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+// counts every deref so we can watch the compiler insert them for us
+struct TrackedBox<T> {
+    value: T,
+    derefs: Cell<usize>,
+}
+
+impl<T> TrackedBox<T> {
+    fn new(value: T) -> TrackedBox<T> {
+        TrackedBox { value, derefs: Cell::new(0) }
+    }
+
+    fn deref_count(&self) -> usize {
+        self.derefs.get()
+    }
+}
+
+impl<T> Deref for TrackedBox<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // deref only gets &self, so the counter needs interior mutability
+        self.derefs.set(self.derefs.get() + 1);
+        &self.value
+    }
+}
+
+fn hello(name: &str) -> String {
+    format!("Hello, {}!", name)
+}
+
+// don't do this: using Deref to fake inheritance
+struct Animal {
+    name: String,
+}
+
+impl Animal {
+    fn speak(&self) -> String {
+        format!("{} makes a sound", self.name)
+    }
+}
+
+struct Dog {
+    animal: Animal,
+}
+
+impl Deref for Dog {
+    type Target = Animal;
+
+    fn deref(&self) -> &Animal {
+        &self.animal
+    }
+}
+
+trait Named {
+    fn name(&self) -> &str;
+}
+
+impl Named for Animal {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+fn introduce(animal: &Animal) -> String {
+    format!("this is {}", animal.name)
+}
+
+fn name_of<T: Named>(item: &T) -> &str {
+    item.name()
+}
+
+fn main(){
+    let x = MyBox::new(5);
+    println!("*x = {}", *x);
+
+    // &MyBox<String> -> &String -> &str, all done by deref coercion
+    let name = MyBox::new(String::from("Pawan"));
+    println!("{}", hello(&name));
+
+    let mut list = MyBox::new(vec![1, 2, 3]);
+    list.push(4);
+    *list = vec![10, 20];
+    println!("mutated through DerefMut: {:?}", *list);
+
+    let tracked = TrackedBox::new(String::from("rust"));
+    println!("len is {}", tracked.len());
+    println!("uppercase is {}", tracked.to_uppercase());
+    println!("{}", hello(&tracked));
+    println!("derefs so far: {}", tracked.deref_count());
+
+    // it "works": dog.speak() finds Animal's method and deref coercion passes
+    // &dog where &Animal is wanted. but Dog is still not an Animal to a trait
+    // bound, name_of(&dog) doesn't compile because only Animal is Named, and
+    // Dog can't override speak: code that has the &Animal always calls Animal's
+    let dog = Dog { animal: Animal { name: String::from("Rex") } };
+    println!("{}", dog.speak());
+    println!("{}", introduce(&dog));
+    // println!("{}", name_of(&dog)); // error: `Dog: Named` is not satisfied
+    println!("the animal inside is named {}", name_of(&dog.animal));
+    println!("prefer a trait both types implement, or a plain field (dog.animal.speak()) instead");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coercion_turns_mybox_string_into_str() {
+        let name = MyBox::new(String::from("Pawan"));
+        assert_eq!(hello(&name), "Hello, Pawan!");
+        // two levels: &MyBox<MyBox<String>> -> &MyBox<String> -> &String -> &str
+        let nested = MyBox::new(MyBox::new(String::from("Asha")));
+        assert_eq!(hello(&nested), "Hello, Asha!");
+    }
+
+    #[test]
+    fn deref_count_goes_up_once_per_deref() {
+        let tracked = TrackedBox::new(String::from("rust"));
+        assert_eq!(tracked.deref_count(), 0);
+        assert_eq!(tracked.len(), 4);
+        assert_eq!(tracked.deref_count(), 1);
+        assert_eq!(hello(&tracked), "Hello, rust!");
+        assert_eq!(*tracked, "rust");
+        assert_eq!(tracked.deref_count(), 3);
+    }
+
+    #[test]
+    fn deref_mut_allows_mutation() {
+        let mut list = MyBox::new(vec![1, 2, 3]);
+        list.push(4);
+        assert_eq!(*list, [1, 2, 3, 4]);
+        *list = vec![10];
+        list[0] += 1;
+        assert_eq!(list.0, [11]);
+    }
+
+    #[test]
+    fn dog_coerces_to_animal_but_keeps_its_speak() {
+        let dog = Dog { animal: Animal { name: String::from("Rex") } };
+        assert_eq!(introduce(&dog), "this is Rex");
+        assert_eq!(dog.speak(), "Rex makes a sound");
+    }
+}
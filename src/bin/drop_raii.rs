@@ -0,0 +1,169 @@
+// the Drop trait and RAII guards
+// drop runs automatically when a value goes out of scope
+
+use std::cell::RefCell;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Instant;
+
+// creates a real file when built and deletes it when dropped
+struct TempFileGuard {
+    path: PathBuf,
+}
+
+impl TempFileGuard {
+    fn new(name: &str, contents: &str) -> std::io::Result<TempFileGuard> {
+        let path = std::env::temp_dir().join(format!("{}-{}", std::process::id(), name));
+        fs::write(&path, contents)?;
+        Ok(TempFileGuard { path })
+    }
+}
+
+impl Drop for TempFileGuard {
+    fn drop(&mut self) {
+        // drop can't return an error, so a failed delete is only reported
+        if let Err(err) = fs::remove_file(&self.path) {
+            println!("could not delete {}: {}", self.path.display(), err);
+        }
+    }
+}
+
+// prints how long it lived when dropped
+struct ScopedTimer {
+    label: &'static str,
+    start: Instant,
+}
+
+impl ScopedTimer {
+    fn new(label: &'static str) -> ScopedTimer {
+        ScopedTimer { label, start: Instant::now() }
+    }
+}
+
+impl Drop for ScopedTimer {
+    fn drop(&mut self) {
+        println!("{} took {:?}", self.label, self.start.elapsed());
+    }
+}
+
+// pushes its name into a shared log so we can see the drop order
+struct Noisy {
+    name: &'static str,
+    log: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        self.log.borrow_mut().push(self.name);
+    }
+}
+
+// fields are dropped in declaration order, after the struct's own drop
+struct Pair {
+    _first: Noisy,
+    _second: Noisy,
+}
+
+fn noisy(name: &'static str, log: &Rc<RefCell<Vec<&'static str>>>) -> Noisy {
+    Noisy { name, log: Rc::clone(log) }
+}
+
+fn main(){
+    let path;
+    {
+        let guard = TempFileGuard::new("drop_raii.txt", "temporary").unwrap();
+        path = guard.path.clone();
+        println!("inside the scope, file exists? {}", path.exists());
+    }
+    println!("after the scope, file exists? {}", path.exists());
+
+    {
+        let _timer = ScopedTimer::new("summing a million numbers");
+        let total: u64 = (1..=1_000_000u64).sum();
+        println!("total is {}", total);
+    }
+
+    // std::mem::drop releases a value early instead of at the end of scope
+    let guard = TempFileGuard::new("early.txt", "released early").unwrap();
+    let early_path = guard.path.clone();
+    drop(guard);
+    println!("after drop(guard), file exists? {}", early_path.exists());
+
+    // locals are dropped in reverse order of declaration
+    let log = Rc::new(RefCell::new(Vec::new()));
+    {
+        let _a = noisy("a", &log);
+        let _b = noisy("b", &log);
+        let _c = noisy("c", &log);
+    }
+    println!("locals dropped in order {:?}", log.borrow());
+
+    // struct fields are dropped in the order they are declared
+    log.borrow_mut().clear();
+    {
+        let _pair = Pair { _first: noisy("first", &log), _second: noisy("second", &log) };
+    }
+    println!("fields dropped in order {:?}", log.borrow());
+
+    // mem::forget skips drop completely, so the file leaks
+    let guard = TempFileGuard::new("forgotten.txt", "leaked").unwrap();
+    let leaked_path = guard.path.clone();
+    std::mem::forget(guard);
+    println!("after mem::forget, file exists? {}", leaked_path.exists());
+    fs::remove_file(&leaked_path).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // each test uses its own file name, they all run in one process
+    #[test]
+    fn the_file_is_gone_after_the_guard_scope() {
+        let path;
+        {
+            let guard = TempFileGuard::new("test-scope.txt", "hello").unwrap();
+            path = guard.path.clone();
+            assert_eq!(fs::read_to_string(&path).unwrap(), "hello");
+        }
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn drop_releases_early() {
+        let guard = TempFileGuard::new("test-early.txt", "").unwrap();
+        let path = guard.path.clone();
+        drop(guard);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn locals_drop_in_reverse_declaration_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        {
+            let _a = noisy("a", &log);
+            let _b = noisy("b", &log);
+            let _c = noisy("c", &log);
+        }
+        assert_eq!(*log.borrow(), ["c", "b", "a"]);
+    }
+
+    #[test]
+    fn fields_drop_in_declaration_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        {
+            let _pair = Pair { _first: noisy("first", &log), _second: noisy("second", &log) };
+        }
+        assert_eq!(*log.borrow(), ["first", "second"]);
+    }
+
+    #[test]
+    fn forget_leaks_the_file() {
+        let guard = TempFileGuard::new("test-forget.txt", "leaked").unwrap();
+        let path = guard.path.clone();
+        std::mem::forget(guard);
+        assert!(path.exists());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,59 @@
+// rust_tut::matrix::DynMatrix, a matrix sized at runtime with flat storage
+// (const_generics.rs has the version whose size is part of the type)
+//
+// cargo run --release --bin dyn_matrix
+// the timing at the end compares it with a plain Vec<Vec<f64>> multiply
+
+use std::time::Instant;
+
+use rust_tut::matrix::DynMatrix;
+
+// the textbook triple loop over nested Vecs, only here to compare against
+fn nested_mul(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let mut out = vec![vec![0.0; b[0].len()]; a.len()];
+    for i in 0..a.len() {
+        for j in 0..b[0].len() {
+            for k in 0..b.len() {
+                out[i][j] += a[i][k] * b[k][j];
+            }
+        }
+    }
+    out
+}
+
+fn main(){
+    let a = DynMatrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]).unwrap();
+    let b = DynMatrix::from_rows(vec![vec![7.0, 8.0], vec![9.0, 10.0], vec![11.0, 12.0]]).unwrap();
+    print!("a =\n{}", a);
+    print!("a * b =\n{}", a.mul(&b).unwrap());
+    print!("transpose of a =\n{}", a.transpose());
+    println!("a * I == a? {}", a.mul(&DynMatrix::identity(3)).unwrap() == a);
+
+    match a.mul(&a) {
+        Ok(m) => print!("{}", m),
+        Err(err) => println!("error: {}", err),
+    }
+    match a.add(&b) {
+        Ok(m) => print!("{}", m),
+        Err(err) => println!("error: {}", err),
+    }
+    match DynMatrix::from_rows(vec![vec![1.0, 2.0], vec![3.0]]) {
+        Ok(m) => print!("{}", m),
+        Err(err) => println!("error: {}", err),
+    }
+    println!("a.get(5, 0) is {:?}", a.get(5, 0));
+
+    let n = 200;
+    let rows: Vec<Vec<f64>> =
+        (0..n).map(|i| (0..n).map(|j| ((i * j) % 7) as f64).collect()).collect();
+    let flat = DynMatrix::from_rows(rows.clone()).unwrap();
+
+    let start = Instant::now();
+    let nested = nested_mul(&rows, &rows);
+    let nested_time = start.elapsed();
+    let start = Instant::now();
+    let product = flat.mul(&flat).unwrap();
+    let flat_time = start.elapsed();
+    println!("{}x{} multiply: nested Vec {:?}, flat {:?}", n, n, nested_time, flat_time);
+    println!("same result? {}", DynMatrix::from_rows(nested).unwrap() == product);
+}
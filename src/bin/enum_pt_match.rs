@@ -1,19 +1,292 @@
-enum Shape{
-    Rect(f64, f64),
-    Circle(f64),
+// cargo run --bin enum_pt_match
+// cargo run --bin enum_pt_match -- shapes.txt
+// the file has one shape per line, like "rect 2 4", "circle 1.5" or
+// "scaled 2 circle 1", blank lines and lines starting with # are skipped
+
+use std::fs::read_to_string;
+
+use rust_tut::shape::{
+    area_or_default, area_stats, calc_area, first_circle, largest_rect_dims, normalize, pair_areas,
+    parse_shape, perimeter, running_mean, sort_shapes_lenient, validate_shape, AreaError, ByArea,
+    Shape, ShapeKind, ShapeVisitor,
+};
+use rust_tut::rect::Rect;
+use rust_tut::shape_visitors::{to_svg, total_area_visited};
+
+// ? hands the first bad shape's error back to the caller
+fn total_area(shapes: &[Shape]) -> Result<f64, AreaError> {
+    let mut total = 0.0;
+    for shape in shapes {
+        total += calc_area(shape)?;
+    }
+    Ok(total)
+}
+
+// Shape is #[non_exhaustive], so outside the lib a match needs a `_` arm
+fn describe(shape: &Shape) -> String {
+    match shape {
+        Shape::Rect(a, b) => format!("a {} by {} rectangle", a, b),
+        Shape::Circle(r) => format!("a circle of radius {}", r),
+        Shape::Scaled(factor, inner) => format!("{} scaled by {}", describe(inner), factor),
+        _ => String::from("a shape this bin doesn't know yet"),
+    }
+}
+
+// counts the bare shapes, looking through every Scaled
+#[derive(Default)]
+struct Counter {
+    rects: u32,
+    circles: u32,
+    others: u32,
+}
+
+impl ShapeVisitor for Counter {
+    fn visit_rect(&mut self, _length: f64, _breadth: f64) {
+        self.rects += 1;
+    }
+
+    fn visit_circle(&mut self, _radius: f64) {
+        self.circles += 1;
+    }
+
+    fn visit_scaled(&mut self, _factor: f64, inner: &Shape) {
+        inner.visit(self);
+    }
+
+    fn visit_unknown(&mut self, _shape: &Shape) {
+        self.others += 1;
+    }
+}
+
+struct Row {
+    line: usize,
+    shape: Shape,
+    area: f64,
+}
+
+#[derive(Default)]
+struct Report {
+    rows: Vec<Row>,
+    // line number and what went wrong
+    errors: Vec<(usize, String)>,
+}
+
+impl Report {
+    // 0.0 for a report with no rows
+    fn total(&self) -> f64 {
+        self.rows.iter().fold(0.0, |total, row| total + row.area)
+    }
+
+    // the first one wins a tie
+    fn largest(&self) -> Option<&Row> {
+        self.rows.iter().fold(None, |best: Option<&Row>, row| match best {
+            Some(best) if best.area >= row.area => Some(best),
+            _ => Some(row),
+        })
+    }
+}
+
+fn build_report(lines: impl Iterator<Item = (usize, String)>) -> Report {
+    let mut report = Report::default();
+    for (line, text) in lines {
+        let text = text.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        let result = parse_shape(text)
+            .map_err(|err| err.to_string())
+            .and_then(|shape| match calc_area(&shape) {
+                Ok(area) => Ok((shape, area)),
+                Err(err) => Err(err.to_string()),
+            });
+        match result {
+            Ok((shape, area)) => report.rows.push(Row { line, shape, area }),
+            Err(err) => report.errors.push((line, err)),
+        }
+    }
+    report
+}
+
+fn print_report(report: &Report) {
+    for row in &report.rows {
+        println!("line {:>3}: {:?} area {:.2}", row.line, row.shape, row.area);
+    }
+    for (line, err) in &report.errors {
+        eprintln!("line {:>3}: error: {}", line, err);
+    }
+    println!("{} shape(s), total area {:.2}", report.rows.len(), report.total());
+    if let Some(row) = report.largest() {
+        println!("largest is {:?} on line {} ({:.2})", row.shape, row.line, row.area);
+    }
 }
 
-fn main(){ 
+// an Err from main is printed with Debug and the process exits with 1
+fn main() -> Result<(), AreaError> {
+    if let Some(path) = std::env::args().nth(1) {
+        let text = match read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) => {
+                eprintln!("error: could not read {}: {}", path, err);
+                std::process::exit(1);
+            }
+        };
+        let lines = text.lines().enumerate().map(|(i, line)| (i + 1, line.to_string()));
+        let report = build_report(lines);
+        print_report(&report);
+        if !report.errors.is_empty() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let rect = Shape::Rect(2.0,4.0);
-    println!("{}",calc_area(rect));
+    println!("{}",calc_area(&rect)?);
     let circ = Shape::Circle(7.0);
-    println!("{}",calc_area(circ));
+    println!("{}",calc_area(&circ)?);
 
+    // impl_struct's Rect (u32 sides) turns into a Shape and back again
+    let phone = Rect::new(20, 40);
+    let as_shape = Shape::from(phone);
+    println!("{:?} is {:?}, area {}", phone, as_shape, calc_area(&as_shape)?);
+    println!("and back: {:?}", Rect::try_from(&as_shape));
+    let not_rects =
+        [Shape::Rect(2.5, 1.0), Shape::Rect(-3.0, 1.0), Shape::Rect(1e10, 1.0), circ.clone()];
+    for shape in not_rects {
+        if let Err(err) = Rect::try_from(&shape) {
+            println!("{:?} is not a Rect: {}", shape, err);
+        }
+    }
+
+    // Scaled wraps another shape, and can wrap a Scaled again
+    let twice = Shape::scaled(3.0, Shape::scaled(2.0, rect.clone()).unwrap()).unwrap();
+    println!("rect x2 x3: area {} perimeter {}", calc_area(&twice)?, perimeter(&twice));
+    println!("normalized: {:?}", normalize(twice));
+
+    match Shape::scaled(-1.0, circ.clone()) {
+        Ok(shape) => println!("{:?}", shape),
+        Err(err) => println!("error: {}", err),
+    }
+
+    let good = [rect.clone(), circ.clone()];
+    println!("total of {:?} is {}", good, total_area(&good)?);
+    let bad = [rect, Shape::Circle(-2.0), circ];
+    match total_area(&bad) {
+        Ok(total) => println!("total is {}", total),
+        Err(err) => println!("error: {}", err),
+    }
+    // calc_area stops at the first bad number, validate_shape lists them all
+    let very_bad = Shape::Scaled(-1.0, Box::new(Shape::Rect(-2.0, -3.0)));
+    if let Err(issues) = validate_shape(&very_bad) {
+        for issue in issues {
+            println!("  {}", issue);
+        }
+    }
+
+    // Option<Shape> helpers
+    let shapes =
+        [Shape::Rect(1.0, 6.0), Shape::Circle(2.0), Shape::Rect(2.0, 3.0), Shape::Circle(1.0)];
+    println!("area of the first or 0: {}", area_or_default(shapes.first(), 0.0));
+    println!("area of the tenth or 0: {}", area_or_default(shapes.get(9), 0.0));
+    println!("first circle: {:?}", first_circle(&shapes));
+    println!("first circle of []: {:?}", first_circle(&[]));
+    println!("largest rect (a tie, the last wins): {:?}", largest_rect_dims(&shapes));
+    println!("pair areas: {:?}", pair_areas(Some(Shape::Rect(1.0, 2.0)), Some(Shape::Circle(1.0))));
+    println!("pair with None: {:?}", pair_areas(Some(Shape::Rect(1.0, 2.0)), None));
+    let with_bad = pair_areas(Some(Shape::Circle(-1.0)), Some(Shape::Circle(1.0)));
+    println!("pair with a bad shape: {:?}", with_bad);
+
+    // kind() for a cheap classification, visit() to walk into Scaled
+    let mixed = [
+        Shape::Rect(1.0, 2.0),
+        Shape::scaled(2.0, Shape::Circle(1.0)).unwrap(),
+        Shape::Circle(3.0),
+    ];
+    let mut counter = Counter::default();
+    for shape in &mixed {
+        let kind = shape.kind();
+        let note = if kind == ShapeKind::Scaled { " (wrapped)" } else { "" };
+        println!("{:?}: {}{}", kind, describe(shape), note);
+        shape.visit(&mut counter);
+    }
+    println!("{} rect(s), {} circle(s), {} other", counter.rects, counter.circles, counter.others);
+
+    // Counter never heard of Group, the default visit_group walks into it anyway
+    let group = Shape::Group(vec![
+        Shape::Circle(1.0),
+        Shape::scaled(2.0, Shape::Group(vec![Shape::Rect(1.0, 1.0), Shape::Circle(0.5)])).unwrap(),
+    ]);
+    let mut counter = Counter::default();
+    group.visit(&mut counter);
+    println!("the group has {} rect(s) and {} circle(s)", counter.rects, counter.circles);
+    println!("visited area {:.4}, calc_area {:?}", total_area_visited(&mixed), total_area(&mixed));
+    print!("{}", to_svg(&[group], 100, 100));
+
+    // ordered by area through ByArea: equal areas compare equal,
+    // a shape without an area doesn't compare at all
+    let (wide, tall) = (Shape::Rect(2.0, 3.0), Shape::Rect(3.0, 2.0));
+    let bad_shape = Shape::Circle(f64::NAN);
+    println!("same shape? {}, same area? {}", wide == tall, ByArea(&wide) == ByArea(&tall));
+    let pi_rect = Shape::Rect(std::f64::consts::PI, 1.0);
+    let unit_circle = Shape::Circle(1.0);
+    let same_area = ByArea(&unit_circle) == ByArea(&pi_rect);
+    println!("unit circle same area as a pi x 1 rect? {}", same_area);
+    println!("circle 1 < rect 2x3? {}", ByArea(&unit_circle) < ByArea(&wide));
+    println!("rect vs NaN circle: {:?}", ByArea(&wide).partial_cmp(&ByArea(&bad_shape)));
+    let mut unsorted = vec![wide, Shape::Circle(-1.0), Shape::Circle(1.0), bad_shape, tall];
+    let moved = sort_shapes_lenient(&mut unsorted);
+    println!("sorted with {} at the end: {:?}", moved, unsorted);
+
+    // statistics straight off an iterator, no Vec in between
+    let squares = (1..=4).map(|side| Shape::Rect(side as f64, side as f64));
+    println!("stats of 1x1 .. 4x4 squares: {:?}", area_stats(squares));
+    println!("stats of nothing: {:?}", area_stats(std::iter::empty()));
+    let squares = (1..=4).map(|side| Shape::Rect(side as f64, side as f64));
+    let means: Vec<f64> = running_mean(squares).collect();
+    println!("running mean: {:?}", means);
+    Ok(())
 }
 
-fn calc_area(shape:Shape) -> f64 {
-    match shape{
-        Shape::Rect(a,b) => a * b,
-        Shape::Circle(r) => 3.14 * r * r,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> impl Iterator<Item = (usize, String)> + '_ {
+        text.lines().enumerate().map(|(i, line)| (i + 1, line.to_string()))
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn mixed_valid_and_invalid_lines() {
+        let text = "rect 2 4\n\n# a comment\ncircle -1\ntriangle 3\n  scaled 2 circle 1  \n\
+                    rect 1\n";
+        let report = build_report(lines(text));
+        let good: Vec<usize> = report.rows.iter().map(|row| row.line).collect();
+        assert_eq!(good, [1, 6]);
+        assert_eq!(report.rows[0].area, 8.0);
+        assert_eq!(report.rows[1].shape, Shape::scaled(2.0, Shape::Circle(1.0)).unwrap());
+        assert_eq!(
+            report.errors,
+            [
+                (4, String::from("radius can't be negative, got -1")),
+                (5, String::from("unknown shape `triangle`")),
+                (7, String::from("rect takes 2 number(s), got 1")),
+            ]
+        );
+        assert!((report.total() - (8.0 + 4.0 * std::f64::consts::PI)).abs() < 1e-9);
+        assert_eq!(report.largest().map(|row| row.line), Some(6));
+    }
+
+    #[test]
+    fn an_all_comments_file_is_empty_but_not_an_error() {
+        let report = build_report(lines("# shapes\n\n   # indented\n\t\n"));
+        assert!(report.rows.is_empty());
+        assert!(report.errors.is_empty());
+        assert_eq!(report.total().to_bits(), 0.0f64.to_bits());
+        assert!(report.largest().is_none());
+    }
+
+    #[test]
+    fn the_first_of_equal_areas_is_the_largest() {
+        let report = build_report(lines("rect 2 3\nrect 3 2\nrect 1 1\n"));
+        assert_eq!(report.largest().map(|row| row.line), Some(1));
+    }
+}
@@ -0,0 +1,261 @@
+// layered errors: every stage has its own error type, From impls glue
+// them into one AppError, and source() keeps the whole cause chain
+
+use std::error::Error;
+use std::fmt;
+use std::fs::read_to_string;
+use std::io;
+
+use rust_tut::config::{parse_config, ConfigError};
+use rust_tut::error::{ContextError, Contextual};
+use rust_tut::style;
+
+// stage 1: parsing a config string like "file=rust.txt\nmultiplier=3",
+// rust_tut::config has the parser and its ConfigError
+
+// stage 2: reading the file named in the config
+#[derive(Debug)]
+struct ReadError {
+    path: String,
+    source: io::Error,
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "could not read `{}`", self.path)
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+// stage 3: computing a value from the file contents
+#[derive(Debug)]
+enum ComputeError {
+    EmptyFile,
+    Overflow,
+}
+
+impl fmt::Display for ComputeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ComputeError::EmptyFile => write!(f, "the file has no words"),
+            ComputeError::Overflow => write!(f, "the result does not fit in a u32"),
+        }
+    }
+}
+
+impl Error for ComputeError {}
+
+// the top level error, one variant per stage
+#[derive(Debug)]
+enum AppError {
+    Config(ConfigError),
+    Read(ReadError),
+    Compute(ComputeError),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AppError::Config(_) => write!(f, "bad config"),
+            AppError::Read(_) => write!(f, "reading input failed"),
+            AppError::Compute(_) => write!(f, "computation failed"),
+        }
+    }
+}
+
+impl Error for AppError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AppError::Config(e) => Some(e),
+            AppError::Read(e) => Some(e),
+            AppError::Compute(e) => Some(e),
+        }
+    }
+}
+
+impl From<ConfigError> for AppError {
+    fn from(e: ConfigError) -> Self {
+        AppError::Config(e)
+    }
+}
+
+impl From<ReadError> for AppError {
+    fn from(e: ReadError) -> Self {
+        AppError::Read(e)
+    }
+}
+
+impl From<ComputeError> for AppError {
+    fn from(e: ComputeError) -> Self {
+        AppError::Compute(e)
+    }
+}
+
+fn read_input(path: &str) -> Result<String, ReadError> {
+    read_to_string(path).map_err(|source| ReadError { path: path.to_string(), source })
+}
+
+fn compute(text: &str, multiplier: u32) -> Result<u32, ComputeError> {
+    let words = text.split_whitespace().count() as u32;
+    if words == 0 {
+        return Err(ComputeError::EmptyFile);
+    }
+    words.checked_mul(multiplier).ok_or(ComputeError::Overflow)
+}
+
+// every ? converts the stage error into AppError through From
+fn run(config_text: &str) -> Result<u32, AppError> {
+    let config = parse_config(config_text)?;
+    let text = read_input(&config.file)?;
+    let value = compute(&text, config.multiplier)?;
+    Ok(value)
+}
+
+// the same pipeline with a boxed error, no AppError needed at all
+fn run_boxed(config_text: &str) -> Result<u32, Box<dyn Error + Send + Sync>> {
+    let config = parse_config(config_text)?;
+    let text = read_input(&config.file)?;
+    let value = compute(&text, config.multiplier)?;
+    Ok(value)
+}
+
+// no stage types at all: plain errors plus a note on what was going on
+fn run_with_context(config_text: &str) -> Result<u32, ContextError> {
+    let config = parse_config(config_text).context("reading config")?;
+    let text = read_to_string(&config.file)
+        .context(format!("opening {}", config.file))
+        .context("loading input")?;
+    let value = compute(&text, config.multiplier).context("computing the value")?;
+    Ok(value)
+}
+
+// prints the error and then every cause under it
+fn print_error_chain(err: &dyn Error) {
+    println!("{} {}", style::red("error:"), err);
+    let mut source = err.source();
+    while let Some(cause) = source {
+        println!("  {} {}", style::dim("caused by:"), cause);
+        source = cause.source();
+    }
+}
+
+fn main(){
+    style::init_from_env();
+
+    let configs = [
+        "file=rust.txt\nmultiplier=3",
+        "multiplier=3",
+        "file=rust.txt\nmultiplier=three",
+        "file=missing.txt\nmultiplier=3",
+        "file=rust.txt\nmultiplier=4000000000",
+    ];
+
+    for config in configs {
+        println!("{}", style::heading(&format!("config: {:?}", config)));
+        match run(config) {
+            Ok(value) => println!("value is {}", style::green(&value.to_string())),
+            Err(err) => print_error_chain(&err),
+        }
+        println!();
+    }
+
+    match run_boxed("file=missing.txt\nmultiplier=3") {
+        Ok(value) => println!("boxed value is {}", value),
+        Err(err) => print_error_chain(err.as_ref()),
+    }
+
+    println!();
+    for config in ["file=missing.txt\nmultiplier=3", "multiplier=3"] {
+        match run_with_context(config) {
+            Ok(value) => println!("value is {}", value),
+            // {} is just the outermost message, {:#} the whole chain on one line
+            Err(err) => println!("{} {}\n  {:#}", style::red("error:"), err, err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    // the message of err and of every cause under it, outermost first
+    fn chain(err: &dyn Error) -> Vec<String> {
+        let mut messages = vec![err.to_string()];
+        let mut source = err.source();
+        while let Some(cause) = source {
+            messages.push(cause.to_string());
+            source = cause.source();
+        }
+        messages
+    }
+
+    #[test]
+    fn the_happy_path_gives_a_value() {
+        // rust.txt is in the crate root, where cargo runs the tests
+        let words = read_to_string("rust.txt").unwrap().split_whitespace().count() as u32;
+        assert_eq!(run("file=rust.txt\nmultiplier=3").unwrap(), words * 3);
+        assert_eq!(run_boxed("file=rust.txt\nmultiplier=2").unwrap(), words * 2);
+    }
+
+    #[test]
+    fn a_config_failure_is_two_levels_deep() {
+        let err = run("multiplier=3").unwrap_err();
+        assert!(matches!(err, AppError::Config(_)));
+        assert_eq!(chain(&err), ["bad config", "missing key `file`"]);
+    }
+
+    #[test]
+    fn a_bad_number_keeps_the_parse_error_as_a_third_level() {
+        let err = run("file=rust.txt\nmultiplier=three").unwrap_err();
+        assert_eq!(
+            chain(&err),
+            ["bad config", "`multiplier` is not a number", "invalid digit found in string"]
+        );
+    }
+
+    #[test]
+    fn a_read_failure_ends_in_the_io_error() {
+        let err = run("file=no/such/file.txt\nmultiplier=3").unwrap_err();
+        assert!(matches!(err, AppError::Read(_)));
+        let messages = chain(&err);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[..2], ["reading input failed", "could not read `no/such/file.txt`"]);
+        let io = err.source().and_then(Error::source).unwrap();
+        assert_eq!(io.downcast_ref::<io::Error>().unwrap().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn compute_failures_are_two_levels_deep() {
+        let err = run("file=rust.txt\nmultiplier=4000000000").unwrap_err();
+        assert_eq!(chain(&err), ["computation failed", "the result does not fit in a u32"]);
+
+        let path = std::env::temp_dir().join(format!("error_chain_empty_{}", std::process::id()));
+        fs::write(&path, "  \n").unwrap();
+        let err = run(&format!("file={}\nmultiplier=1", path.display())).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(chain(&err), ["computation failed", "the file has no words"]);
+    }
+
+    #[test]
+    fn the_boxed_version_skips_the_app_level() {
+        let err = run_boxed("file=no/such/file.txt\nmultiplier=3").unwrap_err();
+        assert_eq!(chain(err.as_ref()).len(), 2);
+        assert!(err.downcast_ref::<ReadError>().is_some());
+        let err = run_boxed("multiplier=3").unwrap_err();
+        assert_eq!(chain(err.as_ref()), ["missing key `file`"]);
+    }
+
+    #[test]
+    fn context_notes_stack_up_in_the_chain() {
+        let err = run_with_context("file=no/such/file.txt\nmultiplier=3").unwrap_err();
+        let messages = chain(&err);
+        assert_eq!(messages[..2], ["loading input", "opening no/such/file.txt"]);
+        assert_eq!(messages.len(), 3);
+    }
+}
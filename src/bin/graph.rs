@@ -0,0 +1,42 @@
+// rust_tut::graph on a made up module tree: an edge goes from a module
+// to each module declared inside it, or to one it `use`s
+
+use rust_tut::graph::Graph;
+
+fn main(){
+    let mut modules: Graph<String> = Graph::new();
+    let edges = [
+        ("crate", "animals"),
+        ("animals", "mammals"),
+        ("animals", "birds"),
+        ("mammals", "dog"),
+        ("mammals", "cat"),
+        ("birds", "parrot"),
+        ("dog", "sounds"),
+        ("parrot", "sounds"),
+    ];
+    for (from, to) in edges {
+        modules.add_edge(from.to_string(), to.to_string(), true);
+    }
+
+    let root = String::from("crate");
+    println!("bfs from crate: {:?}", modules.bfs(&root));
+    // dog and parrot both lead to sounds in four steps, dog was added first
+    println!("crate to sounds: {:?}", modules.shortest_path(&root, &"sounds".to_string()));
+    let cat_to_sounds = modules.shortest_path(&"cat".to_string(), &"sounds".to_string());
+    println!("cat to sounds: {:?}", cat_to_sounds);
+    println!("bfs from a missing module: {:?}", modules.bfs(&"fish".to_string()));
+    println!("connected? {} cycle? {}", modules.is_connected(), modules.has_cycle());
+
+    // sounds uses animals again, which closes a loop
+    modules.add_edge("sounds".to_string(), "animals".to_string(), true);
+    println!("after sounds -> animals, cycle? {}", modules.has_cycle());
+
+    modules.add_node("orphan".to_string());
+    println!("with an orphan module, connected? {}", modules.is_connected());
+
+    let mut friends: Graph<&str> = Graph::new();
+    friends.add_edge("ann", "bob", false);
+    friends.add_edge("bob", "cid", false);
+    println!("undirected neighbours of bob: {:?}", friends.neighbors(&"bob"));
+}
@@ -0,0 +1,43 @@
+// grouping with rust_tut::collections_ext: group_by, group_by_ordered, counts_by
+
+use std::collections::BTreeMap;
+
+use rust_tut::collections_ext::{counts_by, group_by, group_by_ordered};
+use rust_tut::sample_data::{sample_users, sample_words};
+use rust_tut::shape::{Shape, ShapeKind};
+
+fn main(){
+    // grouping references keeps the users where they are,
+    // and the groups go into a BTreeMap so they print in a fixed order
+    let users = sample_users(7, 10);
+    let by_country: BTreeMap<&str, Vec<_>> =
+        group_by(&users, |user| if user.is_indian { "India" } else { "elsewhere" })
+            .into_iter()
+            .collect();
+    for (country, group) in &by_country {
+        let names: Vec<&str> = group.iter().map(|user| user.name.as_str()).collect();
+        println!("{}: {:?}", country, names);
+    }
+
+    // grouping owned values moves them into the groups
+    let shapes = vec![
+        Shape::Circle(1.0),
+        Shape::Rect(1.0, 2.0),
+        Shape::scaled(2.0, Shape::Circle(1.0)).unwrap(),
+        Shape::Circle(3.0),
+    ];
+    let by_kind: Vec<(ShapeKind, Vec<Shape>)> = group_by_ordered(shapes, Shape::kind);
+    for (kind, group) in &by_kind {
+        println!("{:?}: {:?}", kind, group);
+    }
+
+    // first-seen order, unlike the HashMap from group_by
+    let words = sample_words(3, 12);
+    for (letter, group) in group_by_ordered(&words, |word| word.chars().next().unwrap()) {
+        println!("{}: {:?}", letter, group);
+    }
+    let lengths: BTreeMap<usize, usize> =
+        counts_by(&words, |word| word.len()).into_iter().collect();
+    println!("words per length: {:?}", lengths);
+    println!("grouping nothing: {:?}", group_by(Vec::<i32>::new(), |n| n % 2));
+}
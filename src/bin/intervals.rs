@@ -0,0 +1,24 @@
+// rust_tut::intervals: merging [start, end] ranges and asking about them
+
+use rust_tut::intervals::{contains_point, gaps, merge, total_covered, Interval};
+
+fn main(){
+    let raw = [(8, 10), (1, 3), (2, 6), (15, 18), (10, 12), (20, 20)];
+    let intervals: Vec<Interval> = raw.iter().map(|&(s, e)| Interval::new(s, e).unwrap()).collect();
+
+    let merged = merge(intervals.clone());
+    let shown: Vec<(i64, i64)> = merged.iter().map(|i| (i.start(), i.end())).collect();
+    println!("{:?} merges to {:?}", raw, shown);
+    println!("total covered: {}", total_covered(&intervals));
+
+    for p in [0, 1, 6, 7, 12, 20] {
+        println!("contains {}? {}", p, contains_point(&intervals, p));
+    }
+
+    let window = Interval::new(2, 16).unwrap();
+    let free: Vec<(i64, i64)> =
+        gaps(&intervals, window).iter().map(|i| (i.start(), i.end())).collect();
+    println!("gaps inside (2, 16): {:?}", free);
+
+    println!("backwards interval: {:?}", Interval::new(5, 1));
+}
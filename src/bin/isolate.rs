@@ -0,0 +1,39 @@
+// rust_tut::isolate: a list of checks where one panicking doesn't stop the rest
+//
+// cargo run --bin isolate
+
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rust_tut::isolate::{run_all_isolated, run_isolated};
+
+static HOOK_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn main(){
+    let n = 7;
+    let jobs: Vec<Box<dyn FnOnce() -> i32>> = vec![
+        Box::new(|| 1 + 1),
+        Box::new(|| panic!("a plain message")),
+        Box::new(move || panic!("formatted: n is {}", n)),
+        Box::new(|| panic::panic_any(42)),
+        Box::new(|| i32::MAX.checked_add(1).expect("overflow")),
+        Box::new(|| 10),
+    ];
+    for (i, result) in run_all_isolated(jobs).into_iter().enumerate() {
+        match result {
+            Ok(value) => println!("job {} -> {}", i, value),
+            Err(info) => println!("job {} {}", i, info),
+        }
+    }
+
+    // a hook of our own, it must still be there after run_isolated
+    panic::set_hook(Box::new(|_| {
+        HOOK_CALLS.fetch_add(1, Ordering::SeqCst);
+    }));
+    let quiet = run_isolated(|| panic!("not seen by our hook"));
+    println!("{:?}, hook calls so far: {}", quiet, HOOK_CALLS.load(Ordering::SeqCst));
+    let _ = panic::catch_unwind(|| panic!("seen by our hook"));
+    println!("after a normal panic: {}", HOOK_CALLS.load(Ordering::SeqCst));
+    // back to the default hook
+    let _ = panic::take_hook();
+}
@@ -0,0 +1,36 @@
+// rust_tut::linked_list, a singly linked list made of boxes
+
+use rust_tut::linked_list::LinkedList;
+
+fn main(){
+    let mut list = LinkedList::new();
+    for value in [3, 2, 1] {
+        list.push_front(value);
+    }
+    println!("list: {:?} (len {})", list.iter().collect::<Vec<_>>(), list.len());
+
+    if let Some(first) = list.peek_mut() {
+        *first *= 10;
+    }
+    println!("after peek_mut, peek is {:?}", list.peek());
+
+    for value in list.iter_mut() {
+        *value += 1;
+    }
+    println!("after iter_mut: {:?}", list.iter().collect::<Vec<_>>());
+
+    list.reverse();
+    println!("reversed: {:?}", list.iter().collect::<Vec<_>>());
+    println!("pop_front gives {:?}", list.pop_front());
+    println!("into_iter: {:?}", list.into_iter().collect::<Vec<_>>());
+
+    // 100,000 nodes would overflow the stack with the default recursive drop
+    let mut long = LinkedList::new();
+    for value in 0..100_000 {
+        long.push_front(value);
+    }
+    long.reverse();
+    println!("long list starts at {:?}, has {} nodes", long.peek(), long.len());
+    drop(long);
+    println!("and was dropped fine");
+}
@@ -0,0 +1,73 @@
+// rust_tut::logger: four threads logging through one writer thread
+//
+// cargo run --bin logger
+
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use rust_tut::error::ResultExt;
+use rust_tut::logger::{Level, Logger};
+use rust_tut::shape::parse_shape;
+use rust_tut::{log_error, log_info};
+
+// a sink the demo can read back after the logger is gone
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn main(){
+    let buffer = SharedBuffer::default();
+    let logger = Logger::new(Box::new(buffer.clone()), Level::Info);
+    thread::scope(|s| {
+        for t in 0..4 {
+            let logger = &logger;
+            s.spawn(move || {
+                for i in 0..250 {
+                    log_info!(logger, "thread {} message {}", t, i);
+                    // below the Info threshold, never written
+                    logger.log(Level::Debug, "noise");
+                }
+            });
+        }
+    });
+    log_error!(logger, "one error after the threads are done");
+    logger.shutdown().unwrap();
+
+    let text = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    let well_formed = lines.iter().all(|line| {
+        let mut parts = line.splitn(3, ' ');
+        let level = parts.next().unwrap_or("");
+        let stamp = parts.next().unwrap_or("");
+        ["INFO", "ERROR"].contains(&level)
+            && chrono::DateTime::parse_from_rfc3339(stamp).is_ok()
+            && parts.next().is_some()
+    });
+    println!("{} lines written, all `LEVEL timestamp message`? {}", lines.len(), well_formed);
+    println!("last line: {}", lines.last().unwrap());
+
+    // straight to stderr, raising the level at runtime
+    let logger = Logger::stderr(Level::Debug);
+    logger.log(Level::Debug, "debug is on");
+    logger.set_level(Level::Warn);
+    logger.log(Level::Info, "this one is filtered out");
+    logger.log(Level::Warn, "warnings still get through");
+
+    // bad shapes are logged and skipped, the good ones carry on
+    let shapes: Vec<_> = ["rect 1 2", "hexagon 3", "circle 1", "circle"]
+        .iter()
+        .filter_map(|text| parse_shape(text).log_err(&logger))
+        .collect();
+    logger.shutdown().unwrap();
+    println!("{} of 4 shapes parsed", shapes.len());
+}
@@ -0,0 +1,106 @@
+// declarative macros with macro_rules!
+// a macro has to be defined before it is used in the same file
+
+use std::collections::HashMap;
+
+// my own version of vec!, both the list form and the [elem; count] form
+macro_rules! my_vec {
+    () => {
+        Vec::new()
+    };
+    ($elem:expr; $count:expr) => {
+        std::iter::repeat_n($elem, $count).collect::<Vec<_>>()
+    };
+    ($($x:expr),+ $(,)?) => {{
+        // pushing one by one is the point here, vec! would just hide it
+        #[allow(clippy::vec_init_then_push)]
+        let v = {
+            let mut v = Vec::new();
+            $(v.push($x);)+
+            v
+        };
+        v
+    }};
+}
+
+// hashmap!{ k => v, ... } with an optional trailing comma
+macro_rules! hashmap {
+    () => {
+        HashMap::new()
+    };
+    ($($key:expr => $value:expr),+ $(,)?) => {{
+        let mut map = HashMap::new();
+        $(map.insert($key, $value);)+
+        map
+    }};
+}
+
+// runs the expression, prints how long it took and gives the value back
+macro_rules! time_it {
+    ($e:expr) => {{
+        let start = std::time::Instant::now();
+        let value = $e;
+        println!("`{}` took {:?}", stringify!($e), start.elapsed());
+        value
+    }};
+}
+
+fn main(){
+    let v: Vec<i32> = my_vec![1, 2, 3];
+    println!("my_vec![1, 2, 3] = {:?}, same as vec!? {}", v, v == vec![1, 2, 3]);
+
+    let zeros = my_vec![0; 5];
+    println!("my_vec![0; 5] = {:?} (len {})", zeros, zeros.len());
+
+    let empty: Vec<i32> = my_vec![];
+    println!("my_vec![] is empty? {}", empty.is_empty());
+
+    let ages = hashmap! {
+        "pawan" => 21,
+        "rust" => 10,
+    };
+    println!("pawan is {} and rust is {}", ages["pawan"], ages["rust"]);
+
+    let sum = time_it!((1..=1_000_000u64).sum::<u64>());
+    println!("sum of 1..=1_000_000 is {}", sum);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    #[test]
+    fn my_vec_list_form_matches_vec() {
+        assert_eq!(my_vec![1, 2, 3], vec![1, 2, 3]);
+        assert_eq!(my_vec!["a", "b",], vec!["a", "b"]);
+        let empty: Vec<u8> = my_vec![];
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn my_vec_repeat_form_has_count_copies() {
+        let v = my_vec![String::from("x"); 4];
+        assert_eq!(v.len(), 4);
+        assert!(v.iter().all(|s| s == "x"));
+        assert_eq!(my_vec![7; 0], Vec::<i32>::new());
+    }
+
+    #[test]
+    fn hashmap_holds_every_pair() {
+        let map = hashmap! { "one" => 1, "two" => 2, "three" => 3, };
+        assert_eq!(map.len(), 3);
+        assert_eq!((map["one"], map["two"], map["three"]), (1, 2, 3));
+        // a repeated key keeps the last value, like insert does
+        let map = hashmap! { 'a' => 1, 'a' => 2 };
+        assert_eq!(map, HashMap::from([('a', 2)]));
+        let empty: HashMap<u8, u8> = hashmap! {};
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn time_it_gives_back_the_value() {
+        assert_eq!(time_it!(2 + 3), 5);
+        let text = time_it!(String::from("done"));
+        assert_eq!(text, "done");
+    }
+}
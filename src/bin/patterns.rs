@@ -0,0 +1,219 @@
+// every kind of pattern, one small function each
+
+#[derive(Debug, Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+enum Message {
+    Quit,
+    Move(Point),
+    Write(String),
+}
+
+// range patterns
+fn classify_age(age: u8) -> &'static str {
+    match age {
+        0..=12 => "child",
+        13..=19 => "teenager",
+        20..=64 => "adult",
+        _ => "senior",
+    }
+}
+
+// match guards
+fn describe_number(n: i32) -> &'static str {
+    match n {
+        x if x < 0 => "negative",
+        0 => "zero",
+        x if x % 2 == 0 => "positive even",
+        _ => "positive odd",
+    }
+}
+
+// @ bindings keep the value while also testing it against a pattern
+fn bucket(n: u32) -> String {
+    match n {
+        small @ 1..=9 => format!("single digit {}", small),
+        big @ 10..=99 => format!("double digit {}", big),
+        other => format!("something else {}", other),
+    }
+}
+
+// or-patterns
+fn letter_kind(c: char) -> &'static str {
+    match c.to_ascii_lowercase() {
+        'a' | 'e' | 'i' | 'o' | 'u' => "vowel",
+        'a'..='z' => "consonant",
+        _ => "not a letter",
+    }
+}
+
+// nested destructuring of a struct inside an Option
+fn extract_origin(point: Option<Point>) -> i32 {
+    match point {
+        Some(Point { x: 0, y: 0 }) => 0,
+        Some(Point { x, y: 0 }) => x,
+        Some(Point { x: 0, y }) => y,
+        Some(Point { x, y }) => x + y,
+        None => -1,
+    }
+}
+
+// nested destructuring of a struct inside an enum
+fn message_summary(msg: &Message) -> String {
+    match msg {
+        Message::Quit => String::from("quit"),
+        Message::Move(Point { x, y: 0 }) => format!("move along x to {}", x),
+        Message::Move(Point { x, y }) => format!("move to ({}, {})", x, y),
+        Message::Write(text) => format!("write {} chars", text.len()),
+    }
+}
+
+// ref and ref mut borrow from the matched value instead of moving out of it
+fn shout_name(name: &mut Option<String>) -> usize {
+    if let Some(ref mut inner) = *name {
+        inner.push('!');
+    }
+    match *name {
+        Some(ref inner) => inner.len(),
+        None => 0,
+    }
+}
+
+// binding modes: matching Option<&T> gives a &T without writing ref
+fn first_even(values: &[i32]) -> i32 {
+    match values.iter().find(|v| *v % 2 == 0) {
+        Some(&v) => v,
+        None => 0,
+    }
+}
+
+// matches! is a shortcut for a match that returns a bool
+fn is_move_right(msg: &Message) -> bool {
+    matches!(msg, Message::Move(Point { x, .. }) if *x > 0)
+}
+
+// tuple patterns with _ and ..
+fn fizzbuzz(n: u32) -> String {
+    match (n % 3, n % 5) {
+        (0, 0) => String::from("FizzBuzz"),
+        (0, _) => String::from("Fizz"),
+        (_, 0) => String::from("Buzz"),
+        _ => n.to_string(),
+    }
+}
+
+fn main(){
+    println!("15 is a {}", classify_age(15));
+    println!("-4 is {}", describe_number(-4));
+    println!("{}", bucket(42));
+    println!("'E' is a {}", letter_kind('E'));
+    println!("origin sum {}", extract_origin(Some(Point { x: 3, y: 4 })));
+
+    let messages = [
+        Message::Quit,
+        Message::Move(Point { x: 5, y: 0 }),
+        Message::Move(Point { x: -1, y: 2 }),
+        Message::Write(String::from("hello")),
+    ];
+    for msg in &messages {
+        println!("{} (moves right? {})", message_summary(msg), is_move_right(msg));
+    }
+
+    let mut name = Some(String::from("pawan"));
+    println!("shouted length {}, name is now {:?}", shout_name(&mut name), name);
+
+    println!("first even is {}", first_even(&[1, 3, 8, 10]));
+
+    let line: Vec<String> = (1..=15).map(fizzbuzz).collect();
+    println!("{}", line.join(" "));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_classify_ages_at_the_edges() {
+        assert_eq!(classify_age(0), "child");
+        assert_eq!(classify_age(12), "child");
+        assert_eq!(classify_age(13), "teenager");
+        assert_eq!(classify_age(19), "teenager");
+        assert_eq!(classify_age(20), "adult");
+        assert_eq!(classify_age(64), "adult");
+        assert_eq!(classify_age(65), "senior");
+        assert_eq!(classify_age(u8::MAX), "senior");
+    }
+
+    #[test]
+    fn guards_pick_the_first_true_arm() {
+        assert_eq!(describe_number(i32::MIN), "negative");
+        assert_eq!(describe_number(0), "zero");
+        assert_eq!(describe_number(8), "positive even");
+        assert_eq!(describe_number(7), "positive odd");
+    }
+
+    #[test]
+    fn at_bindings_keep_the_value() {
+        assert_eq!(bucket(7), "single digit 7");
+        assert_eq!(bucket(10), "double digit 10");
+        assert_eq!(bucket(0), "something else 0");
+        assert_eq!(bucket(100), "something else 100");
+    }
+
+    #[test]
+    fn or_patterns_match_any_alternative() {
+        assert_eq!(letter_kind('a'), "vowel");
+        assert_eq!(letter_kind('U'), "vowel");
+        assert_eq!(letter_kind('z'), "consonant");
+        assert_eq!(letter_kind('7'), "not a letter");
+    }
+
+    #[test]
+    fn nested_option_patterns() {
+        assert_eq!(extract_origin(Some(Point { x: 0, y: 0 })), 0);
+        assert_eq!(extract_origin(Some(Point { x: 5, y: 0 })), 5);
+        assert_eq!(extract_origin(Some(Point { x: 0, y: -3 })), -3);
+        assert_eq!(extract_origin(Some(Point { x: 3, y: 4 })), 7);
+        assert_eq!(extract_origin(None), -1);
+    }
+
+    #[test]
+    fn nested_enum_patterns() {
+        assert_eq!(message_summary(&Message::Quit), "quit");
+        assert_eq!(message_summary(&Message::Move(Point { x: 5, y: 0 })), "move along x to 5");
+        assert_eq!(message_summary(&Message::Move(Point { x: 1, y: 2 })), "move to (1, 2)");
+        assert_eq!(message_summary(&Message::Write(String::from("héllo"))), "write 6 chars");
+    }
+
+    #[test]
+    fn ref_mut_changes_the_value_in_place() {
+        let mut name = Some(String::from("pawan"));
+        assert_eq!(shout_name(&mut name), 6);
+        assert_eq!(name.as_deref(), Some("pawan!"));
+        let mut nobody = None;
+        assert_eq!(shout_name(&mut nobody), 0);
+    }
+
+    #[test]
+    fn binding_modes_through_option_ref() {
+        assert_eq!(first_even(&[1, 3, 8, 10]), 8);
+        assert_eq!(first_even(&[1, 3]), 0);
+        assert_eq!(first_even(&[]), 0);
+    }
+
+    #[test]
+    fn matches_macro_with_a_guard() {
+        assert!(is_move_right(&Message::Move(Point { x: 1, y: 9 })));
+        assert!(!is_move_right(&Message::Move(Point { x: 0, y: 9 })));
+        assert!(!is_move_right(&Message::Quit));
+    }
+
+    #[test]
+    fn tuple_patterns_with_wildcards() {
+        let line: Vec<String> = (1..=15).map(fizzbuzz).collect();
+        assert_eq!(line.join(" "), "1 2 Fizz 4 Buzz Fizz 7 8 Fizz Buzz 11 Fizz 13 14 FizzBuzz");
+    }
+}
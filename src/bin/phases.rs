@@ -0,0 +1,19 @@
+// rust_tut::phased: a Barrier keeping worker threads in lockstep
+//
+// cargo run --bin phases -- 4 6
+
+use rust_tut::phased::phased_compute;
+
+fn main(){
+    let args: Vec<usize> = std::env::args().skip(1).filter_map(|arg| arg.parse().ok()).collect();
+    let workers = args.first().copied().unwrap_or(4);
+    let phases = args.get(1).copied().unwrap_or(6);
+
+    let table = phased_compute(workers, phases);
+    for (w, row) in table.iter().enumerate() {
+        println!("worker {}: {:?}", w, row);
+    }
+    // the column sums are what the next phase started from
+    let sums: Vec<u64> = (0..phases).map(|k| table.iter().map(|row| row[k]).sum()).collect();
+    println!("phase sums: {:?}", sums);
+}
@@ -0,0 +1,26 @@
+// rust_tut::bounded_pipeline: backpressure with a bounded sync_channel
+//
+// cargo run --bin pipeline
+
+use std::time::Duration;
+
+use rust_tut::bounded_pipeline::{run_pipeline, run_pipeline_with_delay};
+
+fn main(){
+    let report = run_pipeline(4, 2, 250, 16);
+    println!(
+        "4 producers x 250 items, 2 consumers: produced {} consumed {}",
+        report.produced, report.consumed
+    );
+    let all_there = report.consumed_ids.iter().copied().eq(0..report.produced);
+    println!("every id from 0 to {} seen exactly once? {}", report.produced - 1, all_there);
+
+    // slow consumers: a small buffer makes the producers wait, a big one hides it
+    for capacity in [1, 64] {
+        let report = run_pipeline_with_delay(3, 1, 20, capacity, Duration::from_millis(2));
+        println!(
+            "capacity {:>2}: longest a producer was blocked in send: {:?}",
+            capacity, report.max_observed_queue_wait
+        );
+    }
+}
@@ -1,11 +1,240 @@
 // cargo add <package_name> will add the package to your current project
 //Using chrono for this code
+//
+// cargo run --bin pkt_manage -- --offset +05:30 --format "%d %b %Y %H:%M"
+// cargo run --bin pkt_manage -- --until 2025-12-31T00:00:00Z
+// cargo run --bin pkt_manage -- --from 2025-01-01 --to "2025-03-01 12:30:00"
+// cargo run --bin pkt_manage -- --cal 2025-03
+// cargo run --bin pkt_manage -- --timestamp 1735689600
+// cargo run --bin pkt_manage -- --now-unix
+// cargo run --bin pkt_manage -- --week --format-preset friendly
+// cargo run --bin pkt_manage -- --offset-report
+// cargo run --bin pkt_manage -- --add-months -13
+// cargo run --bin pkt_manage -- --zones "Asia/Kolkata,America/New_York,Europe/Berlin"
+// cargo run --release --bin pkt_manage -- --bench-rfc3339 (timings mean little in debug)
 
-use chrono::{Utc, Local};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, FixedOffset, Utc, Local, SecondsFormat};
+use rust_tut::datetime::args::{
+    months_from, parse_offset, parse_rfc3339, parse_year_month, validate_format, TimeArgError,
+};
+use rust_tut::datetime::calendar::render_month;
+use rust_tut::datetime::countdown::{countdown_to, format_countdown};
+use rust_tut::datetime::offsets::{minutes_to_hhmm, offset_nickname, offset_report};
+use rust_tut::datetime::parse::{parse_datetime_with_format, span_between, DateFormat};
+use rust_tut::datetime::presets::{iso_week_label, FormatPreset};
+use rust_tut::datetime::timestamp::{format_rfc3339_manual, from_unix, to_unix};
+use rust_tut::datetime::zones::{convert_to_zones, ZoneTime};
+use rust_tut::rng::Rng;
+use rust_tut::style;
+
+const DEFAULT_FORMAT: &str = "%Y-%m-%d %H:%M:%S %:z";
+
+#[derive(Default)]
+struct Options {
+    format: Option<String>,
+    offset: Option<FixedOffset>,
+    until: Option<DateTime<Utc>>,
+    from: Option<(DateTime<Utc>, DateFormat)>,
+    to: Option<(DateTime<Utc>, DateFormat)>,
+    cal: Option<(i32, u32)>,
+    timestamp: Option<DateTime<Utc>>,
+    now_unix: bool,
+    week: bool,
+    preset: Option<FormatPreset>,
+    offset_report: bool,
+    add_months: Option<i32>,
+    zones: Option<String>,
+    bench_rfc3339: bool,
+}
+
+fn next_value<'a>(
+    iter: &mut impl Iterator<Item = &'a String>,
+    flag: &str,
+) -> Result<&'a String, TimeArgError> {
+    iter.next().ok_or_else(|| TimeArgError::MissingValue(flag.to_string()))
+}
+
+fn parse_args(args: &[String]) -> Result<Options, TimeArgError> {
+    let mut options = Options::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--format" => {
+                let format = next_value(&mut iter, arg)?;
+                validate_format(format)?;
+                options.format = Some(format.clone());
+            }
+            "--offset" => options.offset = Some(parse_offset(next_value(&mut iter, arg)?)?),
+            "--until" => options.until = Some(parse_rfc3339(next_value(&mut iter, arg)?)?),
+            "--from" => {
+                options.from = Some(parse_datetime_with_format(next_value(&mut iter, arg)?)?)
+            }
+            "--to" => options.to = Some(parse_datetime_with_format(next_value(&mut iter, arg)?)?),
+            "--cal" => options.cal = Some(parse_year_month(next_value(&mut iter, arg)?)?),
+            "--timestamp" => {
+                let value = next_value(&mut iter, arg)?;
+                let secs = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| TimeArgError::BadTimestamp(value.clone()))?;
+                options.timestamp = Some(from_unix(secs)?);
+            }
+            "--now-unix" => options.now_unix = true,
+            "--week" => options.week = true,
+            "--offset-report" => options.offset_report = true,
+            "--bench-rfc3339" => options.bench_rfc3339 = true,
+            "--zones" => options.zones = Some(next_value(&mut iter, arg)?.clone()),
+            "--add-months" => {
+                let value = next_value(&mut iter, arg)?;
+                let months = value.trim().parse().map_err(|_| TimeArgError::BadNumber {
+                    flag: arg.clone(),
+                    value: value.clone(),
+                })?;
+                options.add_months = Some(months);
+            }
+            "--format-preset" => options.preset = Some(next_value(&mut iter, arg)?.parse()?),
+            _ => return Err(TimeArgError::UnknownFlag(arg.clone())),
+        }
+    }
+    Ok(options)
+}
+
+// formats every instant with `format` and returns how long it took and how
+// many bytes came out, so the work can't be optimised away
+fn time_formatting(
+    instants: &[DateTime<Utc>],
+    format: impl Fn(DateTime<Utc>) -> String,
+) -> (Duration, usize) {
+    let start = Instant::now();
+    let bytes = instants.iter().map(|&dt| format(dt).len()).sum();
+    (start.elapsed(), bytes)
+}
+
+fn bench_rfc3339() {
+    let mut rng = Rng::new(137);
+    // 1970 to 2100, the years a timestamp usually comes from
+    let instants: Vec<DateTime<Utc>> = (0..100_000)
+        .filter_map(|_| from_unix(rng.range(0, 4_102_444_800)).ok())
+        .collect();
+    let chrono_format = |dt: DateTime<Utc>| dt.to_rfc3339_opts(SecondsFormat::Secs, true);
+    let same = instants.iter().all(|&dt| format_rfc3339_manual(dt) == Some(chrono_format(dt)));
+    println!("hand-rolled output matches chrono for {} instants? {}", instants.len(), same);
+    let (chrono_time, chrono_bytes) = time_formatting(&instants, chrono_format);
+    let (manual_time, manual_bytes) =
+        time_formatting(&instants, |dt| format_rfc3339_manual(dt).unwrap_or_default());
+    println!("  {:<17} {:?} ({} bytes)", "to_rfc3339_opts", chrono_time, chrono_bytes);
+    println!("  {:<17} {:?} ({} bytes)", "hand-rolled", manual_time, manual_bytes);
+}
+
+fn print_zone_table(rows: &[ZoneTime]) {
+    let width = rows.iter().map(|row| row.zone.len()).max().unwrap_or(0);
+    for row in rows {
+        println!(
+            "{:<width$}  {}  {:<5} {}",
+            row.zone,
+            row.time.format("%Y-%m-%d %H:%M"),
+            row.abbreviation,
+            minutes_to_hhmm(row.offset_minutes()),
+            width = width
+        );
+    }
+}
 
 fn main(){
-    let utc = Utc::now();
-    let local = Local::now();
-    println!("The UTC time is {}", utc);
-    println!("Your local time is {}", local);
-}
\ No newline at end of file
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{} {}", style::red("error:"), err);
+            std::process::exit(2);
+        }
+    };
+
+    if args.is_empty() {
+        let utc = Utc::now();
+        let local = Local::now();
+        println!("The UTC time is {}", utc);
+        println!("Your local time is {}", local);
+        return;
+    }
+
+    let now = Utc::now();
+    if options.format.is_some() || (options.offset.is_some() && options.preset.is_none()) {
+        let offset = options.offset.unwrap_or(FixedOffset::east_opt(0).unwrap());
+        let format = options.format.as_deref().unwrap_or(DEFAULT_FORMAT);
+        println!("{}", now.with_timezone(&offset).format(format));
+    }
+    if let Some(target) = options.until {
+        println!("{}: {}", target.to_rfc3339(), format_countdown(&countdown_to(target, now)));
+    }
+    if options.from.is_some() || options.to.is_some() {
+        // a missing end of the span means now
+        let (from, from_format) = options.from.unwrap_or((now, DateFormat::Rfc3339));
+        let (to, to_format) = options.to.unwrap_or((now, DateFormat::Rfc3339));
+        println!("from {} ({})", from.to_rfc3339(), from_format.label());
+        println!("to   {} ({})", to.to_rfc3339(), to_format.label());
+        println!("span {}", span_between(from, to));
+    }
+    if let Some((year, month)) = options.cal {
+        match render_month(year, month, Some(Local::now().date_naive())) {
+            Ok(grid) => print!("{}", grid),
+            Err(err) => eprintln!("{} {}", style::red("error:"), err),
+        }
+    }
+    if let Some(dt) = options.timestamp {
+        println!("UTC:   {}", dt);
+        println!("local: {}", dt.with_timezone(&Local));
+    }
+    if options.now_unix {
+        println!("{}", to_unix(now));
+    }
+    if options.bench_rfc3339 {
+        bench_rfc3339();
+    }
+    if let Some(preset) = options.preset {
+        match options.offset {
+            Some(offset) => println!("{}", preset.format(now.with_timezone(&offset))),
+            None => println!("{}", preset.format(now.with_timezone(&Local))),
+        }
+    }
+    if options.week {
+        // the week can change with the offset near midnight on a Sunday
+        match options.offset {
+            Some(offset) => println!("ISO week {}", iso_week_label(&now.with_timezone(&offset))),
+            None => println!("ISO week {}", iso_week_label(&now.with_timezone(&Local))),
+        }
+    }
+    if options.offset_report {
+        let report = offset_report(now.with_timezone(&Local));
+        let hhmm = minutes_to_hhmm(report.offset_minutes);
+        match offset_nickname(report.offset_minutes) {
+            Some(name) => println!("local time is UTC{} ({}-like)", hhmm, name),
+            None => println!("local time is UTC{}", hhmm),
+        }
+        if report.is_dst_possible {
+            println!("this zone changes its offset during the year (daylight saving)");
+        }
+    }
+    if let Some(months) = options.add_months {
+        let today = Local::now().date_naive();
+        match months_from(today, months) {
+            Ok(later) => println!("{} {:+} months is {}", today, months, later),
+            Err(err) => {
+                eprintln!("{} {}", style::red("error:"), err);
+                std::process::exit(2);
+            }
+        }
+    }
+    if let Some(zones) = &options.zones {
+        let names: Vec<&str> = zones.split(',').collect();
+        match convert_to_zones(now, &names) {
+            Ok(rows) => print_zone_table(&rows),
+            Err(err) => {
+                print_zone_table(&err.converted);
+                eprintln!("{} {}", style::red("error:"), err);
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+// a pomodoro timer: work, short break, work, ... and a long break every few cycles
+//
+// cargo run --bin pomodoro -- --work 25 --break 5 --fast
+// --fast counts minutes as seconds so a whole cycle can be watched quickly
+
+use std::fmt;
+use std::thread;
+use std::time::Duration;
+
+use chrono::Local;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PomodoroConfig {
+    work: Duration,
+    short_break: Duration,
+    long_break: Duration,
+    cycles_before_long: u32,
+}
+
+impl PomodoroConfig {
+    fn duration_of(&self, phase: Phase) -> Duration {
+        match phase {
+            Phase::Work => self.work,
+            Phase::ShortBreak => self.short_break,
+            Phase::LongBreak => self.long_break,
+        }
+    }
+}
+
+// the state machine knows nothing about clocks, it only says what comes next
+struct PomodoroState {
+    config: PomodoroConfig,
+    phase: Phase,
+    completed_work: u32,
+}
+
+impl PomodoroState {
+    // starts in a work phase
+    fn new(config: PomodoroConfig) -> PomodoroState {
+        PomodoroState { config, phase: Phase::Work, completed_work: 0 }
+    }
+
+    fn advance(&mut self) -> Phase {
+        self.phase = match self.phase {
+            Phase::Work => {
+                self.completed_work += 1;
+                if self.completed_work.is_multiple_of(self.config.cycles_before_long) {
+                    Phase::LongBreak
+                } else {
+                    Phase::ShortBreak
+                }
+            }
+            Phase::ShortBreak | Phase::LongBreak => Phase::Work,
+        };
+        self.phase
+    }
+}
+
+#[derive(Debug)]
+enum ConfigError {
+    MissingValue(String),
+    BadNumber { flag: String, value: String },
+    Zero(String),
+    UnknownFlag(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::MissingValue(flag) => write!(f, "{} needs a value", flag),
+            ConfigError::BadNumber { flag, value } => {
+                write!(f, "{} expects a whole number, got `{}`", flag, value)
+            }
+            ConfigError::Zero(flag) => write!(f, "{} can't be zero", flag),
+            ConfigError::UnknownFlag(flag) => write!(f, "unknown flag {}", flag),
+        }
+    }
+}
+
+struct Options {
+    config: PomodoroConfig,
+    fast: bool,
+    // how many phases to run before stopping
+    phases: u32,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, ConfigError> {
+    let mut minutes = [25, 5, 15];
+    let mut cycles = 4;
+    let mut phases = None;
+    let mut fast = false;
+
+    let mut iter = args.iter();
+    while let Some(flag) = iter.next() {
+        let target = match flag.as_str() {
+            "--fast" => {
+                fast = true;
+                continue;
+            }
+            "--work" => &mut minutes[0],
+            "--break" => &mut minutes[1],
+            "--long-break" => &mut minutes[2],
+            "--cycles" => &mut cycles,
+            "--phases" => phases.insert(0),
+            _ => return Err(ConfigError::UnknownFlag(flag.clone())),
+        };
+        let value = iter.next().ok_or_else(|| ConfigError::MissingValue(flag.clone()))?;
+        *target = value
+            .parse()
+            .map_err(|_| ConfigError::BadNumber { flag: flag.clone(), value: value.clone() })?;
+        if *target == 0 {
+            return Err(ConfigError::Zero(flag.clone()));
+        }
+    }
+
+    // in fast mode one "minute" lasts a second
+    let unit = if fast { 1 } else { 60 };
+    let config = PomodoroConfig {
+        work: Duration::from_secs(minutes[0] as u64 * unit),
+        short_break: Duration::from_secs(minutes[1] as u64 * unit),
+        long_break: Duration::from_secs(minutes[2] as u64 * unit),
+        cycles_before_long: cycles,
+    };
+    // by default run until the first long break is over
+    let phases = phases.unwrap_or(cycles.saturating_mul(2));
+    Ok(Options { config, fast, phases })
+}
+
+fn main(){
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            std::process::exit(2);
+        }
+    };
+    if options.fast {
+        println!("fast mode: minutes are seconds");
+    }
+
+    let mut state = PomodoroState::new(options.config);
+    let mut phase = state.phase;
+    for _ in 0..options.phases {
+        let length = options.config.duration_of(phase);
+        println!("[{}] {:?} for {:?}", Local::now().format("%H:%M:%S"), phase, length);
+        thread::sleep(length);
+        phase = state.advance();
+    }
+    let now = Local::now().format("%H:%M:%S");
+    println!("[{}] done after {} work phases", now, state.completed_work);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(cycles_before_long: u32) -> PomodoroConfig {
+        PomodoroConfig {
+            work: Duration::from_secs(25),
+            short_break: Duration::from_secs(5),
+            long_break: Duration::from_secs(15),
+            cycles_before_long,
+        }
+    }
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn eight_advances_with_a_long_break_after_four_work_phases() {
+        use Phase::*;
+        let mut state = PomodoroState::new(config(4));
+        assert_eq!(state.phase, Work);
+        let phases: Vec<Phase> = (0..8).map(|_| state.advance()).collect();
+        assert_eq!(phases, [ShortBreak, Work, ShortBreak, Work, ShortBreak, Work, LongBreak, Work]);
+        assert_eq!(state.completed_work, 4);
+    }
+
+    #[test]
+    fn one_cycle_means_every_break_is_long() {
+        let mut state = PomodoroState::new(config(1));
+        let phases: Vec<Phase> = (0..4).map(|_| state.advance()).collect();
+        assert_eq!(phases, [Phase::LongBreak, Phase::Work, Phase::LongBreak, Phase::Work]);
+    }
+
+    #[test]
+    fn flags_set_the_config() {
+        let flags = args(&["--work", "50", "--break", "10", "--cycles", "2"]);
+        let options = parse_args(&flags).unwrap();
+        assert_eq!(options.config.work, Duration::from_secs(50 * 60));
+        assert_eq!(options.config.short_break, Duration::from_secs(10 * 60));
+        assert_eq!(options.config.long_break, Duration::from_secs(15 * 60));
+        assert_eq!(options.config.cycles_before_long, 2);
+        assert_eq!(options.phases, 4);
+        assert!(!options.fast);
+    }
+
+    #[test]
+    fn fast_mode_counts_minutes_as_seconds() {
+        let options = parse_args(&args(&["--fast", "--work", "25", "--phases", "3"])).unwrap();
+        assert!(options.fast);
+        assert_eq!(options.config.work, Duration::from_secs(25));
+        assert_eq!(options.phases, 3);
+    }
+
+    #[test]
+    fn zero_durations_and_bad_flags_are_rejected() {
+        for flag in ["--work", "--break", "--long-break", "--cycles", "--phases"] {
+            let result = parse_args(&args(&[flag, "0"]));
+            assert!(matches!(result, Err(ConfigError::Zero(f)) if f == flag));
+        }
+        assert!(matches!(parse_args(&args(&["--work"])), Err(ConfigError::MissingValue(_))));
+        assert!(matches!(
+            parse_args(&args(&["--work", "ten"])),
+            Err(ConfigError::BadNumber { .. })
+        ));
+        assert!(matches!(parse_args(&args(&["--nap"])), Err(ConfigError::UnknownFlag(_))));
+    }
+}
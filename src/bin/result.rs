@@ -1,10 +1,41 @@
 // it is used for Ok value or Err value
+//
+// cargo run --bin result
+// cargo run --bin result -- --freq 10 rust.txt
 
 use std::fs::read_to_string;
 
+use rust_tut::report::args_without_report_flags;
+use rust_tut::error::{run_main, AppError, Contextual};
+use rust_tut::text::{parallel_word_count, print_histogram, top_n, word_frequencies};
+
 fn main(){
+    run_main(run)
+}
+
+fn run() -> Result<(), AppError> {
+    let args = args_without_report_flags();
+    if let [flag, count, rest @ ..] = args.as_slice()
+        && flag == "--freq"
+    {
+        let n: usize = count.parse().map_err(|_| {
+            AppError::Usage(format!("--freq expects a whole number, got `{}`", count))
+        })?;
+        let path = rest.first().map(String::as_str).unwrap_or("rust.txt");
+        let data = read_to_string(path).context(format!("could not read {}", path))?;
+        let freqs = word_frequencies(&data);
+        print_histogram(&top_n(&freqs, n), 40);
+        // the same counts again, one scoped thread per core
+        let parallel = parallel_word_count(&data, 0);
+        let agrees = parallel.len() == freqs.len()
+            && freqs.iter().all(|(word, count)| parallel.get(word) == Some(&(count as usize)));
+        println!("parallel count agrees: {}", agrees);
+        return Ok(());
+    }
+
     let ans = read_from_file_rust(String::from("rust.txt"));
     println!("{}",ans);
+    Ok(())
 }
 
 fn read_from_file_rust(file_path: String) -> String {
@@ -13,4 +44,4 @@ fn read_from_file_rust(file_path: String) -> String {
         Ok(data) => data,
         Err(_err) => String::from("File not present!"),
     }
-}
\ No newline at end of file
+}
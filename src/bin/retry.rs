@@ -0,0 +1,48 @@
+// rust_tut::retry: waiting for a file that another thread writes a bit later
+//
+// cargo run --bin retry
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::thread;
+use std::time::Duration;
+
+use rust_tut::retry::{retry, retry_with_sleeper, Backoff, RetryPolicy};
+
+fn main(){
+    let path = std::env::temp_dir().join("rust_tut_retry_demo.txt");
+    let _ = fs::remove_file(&path);
+
+    let writer_path = path.clone();
+    let writer = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(150));
+        fs::write(writer_path, "finally here").unwrap();
+    });
+
+    // only a missing file is worth waiting for, a permission error is not
+    let policy: RetryPolicy<io::Error> = RetryPolicy {
+        max_attempts: 10,
+        base_delay: Duration::from_millis(20),
+        backoff: Backoff::Exponential { factor: 2.0, cap: Duration::from_millis(100) },
+        retry_if: |err| err.kind() == ErrorKind::NotFound,
+    };
+    let mut tries = 0;
+    let read = retry(&policy, || {
+        tries += 1;
+        fs::read_to_string(&path)
+    });
+    match read {
+        Ok(text) => println!("read {:?} on try {}", text, tries),
+        Err(err) => println!("{}", err),
+    }
+    writer.join().unwrap();
+    let _ = fs::remove_file(&path);
+
+    // a fake sleeper shows the delays without waiting for them
+    let mut slept = Vec::new();
+    let failed = retry_with_sleeper(&policy, &mut |d| slept.push(d), || {
+        Err::<(), _>(io::Error::from(ErrorKind::NotFound))
+    });
+    println!("{}", failed.unwrap_err());
+    println!("delays: {:?}", slept);
+}
@@ -0,0 +1,97 @@
+// the seeded example data from rust_tut::sample_data
+// run it twice: the output never changes, try another seed to get new data
+
+use rust_tut::area::Area;
+use rust_tut::collections_ext::{dedup_preserving_order, duplicates, reset_all};
+use rust_tut::extremes::{max_n_by, min_by_key_value, minmax};
+use rust_tut::multimap::MultiMap;
+use rust_tut::point::Point;
+use rust_tut::sample_data::{
+    sample_numbers, sample_points, sample_shapes, sample_users, sample_words,
+};
+use rust_tut::shape::validate_shape;
+use rust_tut::str_ext::StrExt;
+use rust_tut::user::{dedup_by_email, User};
+
+fn main(){
+    let seed = std::env::args().nth(1).map_or(42, |s| s.parse_or(42));
+    println!("seed {}", seed);
+    println!("numbers: {:?}", sample_numbers(seed, 8));
+    println!("words: {:?}", sample_words(seed, 5));
+    for point in sample_points(seed, 3) {
+        println!("point ({}, {})", point.x, point.y);
+    }
+    for user in sample_users(seed, 3) {
+        println!(
+            "user #{} {} <{}> indian? {}",
+            user.user_id, user.name, user.email, user.is_indian
+        );
+    }
+
+    // an upper case copy of the first email, dedup_by_email ignores case
+    let mut users = sample_users(seed, 60);
+    let shouting = users[0].email.to_uppercase();
+    users.push(User::new("Loud", "loud_user", &shouting, 61, false).unwrap());
+    let emails: Vec<String> = users.iter().map(|u| u.email.to_lowercase()).collect();
+    println!("repeated emails: {:?}", duplicates(&emails));
+    let by_email: MultiMap<String, i32> =
+        MultiMap::from(emails.into_iter().zip(users.iter().map(|u| u.user_id)).collect::<Vec<_>>());
+    for email in by_email.keys_with_multiple() {
+        println!("  {} is used by users {:?}", email, by_email.get(email));
+    }
+    // borrowed answers, none of the users are cloned to find them
+    if let Some((user, len)) = min_by_key_value(&users, |u| u.name.len()) {
+        println!("shortest name: {} ({} letters)", user.name, len);
+    }
+    let top: Vec<i32> =
+        max_n_by(&users, 3, |a, b| a.user_id.cmp(&b.user_id)).iter().map(|u| u.user_id).collect();
+    println!("three highest ids: {:?}", top);
+
+    println!("{} users, {} after dedup_by_email", users.len(), dedup_by_email(users).len());
+
+    // the fields are public, so a valid user can be broken afterwards
+    let mut broken = sample_users(seed, 1).remove(0);
+    broken.username = String::from("No Spaces");
+    broken.user_id = 0;
+    match broken.validate() {
+        Ok(()) => println!("{} is still valid", broken.name),
+        Err(issues) => {
+            let messages: Vec<String> = issues.iter().map(|issue| issue.to_string()).collect();
+            println!("{} has {} problems: {}", broken.name, issues.len(), messages.join("; "));
+        }
+    }
+
+    // the smallest and largest valid shape found in a single pass over the areas
+    let areas: Vec<f64> = sample_shapes(seed, 200)
+        .iter()
+        .filter(|shape| validate_shape(shape).is_ok())
+        .map(Area::area)
+        .collect();
+    if let Some((min, max)) = minmax(&areas) {
+        println!("{} valid shapes, areas from {:.2} to {:.2}", areas.len(), min, max);
+    }
+
+    // struct update syntax fills in the rest from the placeholder default,
+    // which is still not a valid user until every field is set
+    let half_done = User { name: String::from("Asha"), ..Default::default() };
+    println!("{:?} valid? {}", half_done.name, half_done.validate().is_ok());
+    let done = User {
+        username: String::from("asha"),
+        email: String::from("asha@example.com"),
+        user_id: 7,
+        ..half_done
+    };
+    println!("{:?} valid? {}", done.name, done.validate().is_ok());
+
+    let mut points = sample_points(seed, 3);
+    reset_all(&mut points);
+    let at_origin = points.iter().all(|p| *p == Point::default());
+    println!("after reset_all: {:?}, all the origin? {}", points, at_origin);
+
+    let words = sample_words(seed, 12);
+    println!("words without repeats: {:?}", dedup_preserving_order(&words));
+
+    println!("same seed, same numbers? {}", sample_numbers(seed, 100) == sample_numbers(seed, 100));
+    let same = sample_numbers(seed, 100) == sample_numbers(seed + 1, 100);
+    println!("next seed, same numbers? {}", same);
+}
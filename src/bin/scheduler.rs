@@ -0,0 +1,28 @@
+// rust_tut::scheduler: tasks come out by priority, oldest first on a tie
+
+use rust_tut::scheduler::{Scheduler, Task};
+
+fn main(){
+    let mut scheduler = Scheduler::new();
+    let plan = [
+        ("write docs", 1),
+        ("fix login bug", 9),
+        ("review PR", 5),
+        ("deploy", 9),
+        ("lunch", 5),
+        ("refactor", 1),
+    ];
+    for (created, (name, priority)) in plan.into_iter().enumerate() {
+        scheduler.add(Task { name: name.to_string(), priority, created: created as u64 });
+    }
+
+    println!("waiting per priority: {:?}", scheduler.pending_by_priority());
+    println!("cancel lunch? {}", scheduler.cancel("lunch"));
+    println!("cancel lunch twice? {}", scheduler.cancel("lunch"));
+    println!("cancel something unknown? {}", scheduler.cancel("nap"));
+    println!("{} left, next up: {:?}", scheduler.len(), scheduler.peek().map(|t| &t.name));
+
+    while let Some(task) = scheduler.next_task() {
+        println!("  [{}] {} (created {})", task.priority, task.name, task.created);
+    }
+}
@@ -0,0 +1,104 @@
+// thread::scope lets threads borrow local data, no Arc needed
+// every thread spawned in the scope is joined before scope returns
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+use rust_tut::parallel::{parallel_map, parallel_sum};
+use rust_tut::rng::Rng;
+
+// the same thing without scope: the data has to be owned and shared
+fn parallel_sum_arc(data: Vec<i64>, threads: usize) -> i64 {
+    let data = Arc::new(data);
+    let total = Arc::new(Mutex::new(0));
+    let chunk_size = data.len().div_ceil(threads.max(1)).max(1);
+    let mut handles = Vec::new();
+
+    for start in (0..data.len()).step_by(chunk_size) {
+        let data = Arc::clone(&data);
+        let total = Arc::clone(&total);
+        handles.push(thread::spawn(move || {
+            let end = (start + chunk_size).min(data.len());
+            let sum: i64 = data[start..end].iter().sum();
+            *total.lock().unwrap() += sum;
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    *total.lock().unwrap()
+}
+
+fn main(){
+    let numbers: Vec<i64> = (1..=1000).collect();
+
+    println!("sequential sum: {}", numbers.iter().sum::<i64>());
+    println!("scoped sum with 4 threads: {}", parallel_sum(&numbers, 4));
+    println!("scoped sum with 3000 threads: {}", parallel_sum(&numbers[..10], 3000));
+    println!("scoped sum of nothing: {}", parallel_sum(&[], 4));
+    println!("scoped sum with all cores: {}", parallel_sum(&numbers, 0));
+    println!("Arc<Mutex> sum with 4 threads: {}", parallel_sum_arc(numbers.clone(), 4));
+
+    let squares = parallel_map(&numbers[..8], 3, |n| n * n);
+    println!("squares of the first 8, mapped on 3 threads: {:?}", squares);
+
+    // sequential vs parallel on a bigger seeded dataset
+    let mut rng = Rng::new(42);
+    let big: Vec<i64> = (0..5_000_000).map(|_| rng.range(-1000, 1000)).collect();
+    let start = Instant::now();
+    let sequential = big.iter().sum::<i64>();
+    let sequential_time = start.elapsed();
+    let start = Instant::now();
+    let parallel = parallel_sum(&big, 0);
+    let parallel_time = start.elapsed();
+    println!("sequential sum {} took {:?}", sequential, sequential_time);
+    println!("parallel sum   {} took {:?}", parallel, parallel_time);
+
+    // without scope this does not compile: thread::spawn needs 'static,
+    // but the closure borrows `numbers`, which could die before the thread
+    //
+    // let handle = thread::spawn(|| numbers.iter().sum::<i64>());
+    // error[E0373]: closure may outlive the current function, but it borrows `numbers`
+    //
+    // and adding `move` moves `numbers` into the first thread, so a second
+    // thread (or main) can't use it anymore
+
+    // numbers is still ours after the scope
+    println!("numbers still has {} items", numbers.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(len: usize) -> Vec<i64> {
+        let mut rng = Rng::new(len as u64);
+        (0..len).map(|_| rng.range(-1000, 1000)).collect()
+    }
+
+    #[test]
+    fn scoped_and_arc_sums_match_the_sequential_sum() {
+        for len in [0, 1, 2, 7, 100, 1001] {
+            let numbers = data(len);
+            let expected: i64 = numbers.iter().sum();
+            for threads in [0, 1, 3, 8] {
+                let got = parallel_sum(&numbers, threads);
+                assert_eq!(got, expected, "len {} threads {}", len, threads);
+                assert_eq!(parallel_sum_arc(numbers.clone(), threads), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn more_threads_than_elements() {
+        assert_eq!(parallel_sum(&[1, 2, 3], 50), 6);
+        assert_eq!(parallel_sum_arc(vec![1, 2, 3], 50), 6);
+    }
+
+    #[test]
+    fn an_empty_slice_sums_to_zero() {
+        assert_eq!(parallel_sum(&[], 4), 0);
+        assert_eq!(parallel_sum_arc(Vec::new(), 4), 0);
+    }
+}
@@ -0,0 +1,65 @@
+// rust_tut::rw_cache: several threads sharing the areas of parsed shapes
+//
+// cargo run --bin shape_cache
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use rust_tut::error::{AppError, OptionExt};
+use rust_tut::exit_code::Exit;
+use rust_tut::rw_cache::RwCache;
+use rust_tut::shape::{calc_area, parse_shape};
+
+const SHAPES: [&str; 5] = ["rect 3 4", "circle 1", "scaled 2 rect 1 1", "circle -1", "hexagon 2"];
+
+fn main() -> Exit {
+    run().into()
+}
+
+fn run() -> Result<(), AppError> {
+    let cache: RwCache<String, Result<f64, String>> = RwCache::new();
+    let parses = AtomicUsize::new(0);
+
+    thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| {
+                for _ in 0..50 {
+                    for text in SHAPES {
+                        // errors are cached too, the result is read back below
+                        let _ = cache.get_or_insert_with(text.to_string(), || {
+                            parses.fetch_add(1, Ordering::SeqCst);
+                            // pretend parsing is expensive
+                            thread::sleep(Duration::from_millis(5));
+                            let shape = parse_shape(text).map_err(|err| err.to_string())?;
+                            calc_area(&shape).map_err(|err| err.to_string())
+                        });
+                    }
+                }
+            });
+        }
+    });
+
+    for text in SHAPES {
+        let area = cache.get(&text.to_string()).ok_or_msg("a shape went missing from the cache")?;
+        println!("{:<20} {:?}", text, area);
+    }
+    let metrics = cache.metrics();
+    println!(
+        "{} lookups: {} hits, {} misses, {} parses",
+        metrics.total(),
+        metrics.hits,
+        metrics.misses,
+        parses.load(Ordering::SeqCst)
+    );
+
+    let circle = "circle 1".to_string();
+    cache.invalidate(&circle);
+    println!(
+        "after invalidating {}: {} entries, get gives {:?}",
+        circle,
+        cache.len(),
+        cache.get(&circle)
+    );
+    Ok(())
+}
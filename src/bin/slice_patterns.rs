@@ -0,0 +1,148 @@
+// slice patterns and the windows / chunks helpers on slices
+
+use rust_tut::iter_ext::IterExt;
+
+fn first_and_last(values: &[i32]) -> Option<(i32, i32)> {
+    match values {
+        [] => None,
+        [only] => Some((*only, *only)),
+        [first, .., last] => Some((*first, *last)),
+    }
+}
+
+fn starts_with_pair(values: &[i32], pair: (i32, i32)) -> bool {
+    match values {
+        [a, b, ..] => (*a, *b) == pair,
+        _ => false,
+    }
+}
+
+// `rest @ ..` binds whatever is left as a sub-slice
+fn sum_recursive(values: &[i32]) -> i32 {
+    match values {
+        [] => 0,
+        [head, rest @ ..] => head + sum_recursive(rest),
+    }
+}
+
+fn describe(values: &[i32]) -> String {
+    match values {
+        [] => String::from("empty"),
+        [x] => format!("just {}", x),
+        [a, b] => format!("a pair {} and {}", a, b),
+        [a, b, rest @ ..] => format!("{} and {} then {} more", a, b, rest.len()),
+    }
+}
+
+// averages every full window, a window larger than the slice gives nothing
+fn moving_average(values: &[f64], window: usize) -> Vec<f64> {
+    if window == 0 {
+        return Vec::new();
+    }
+    values
+        .windows(window)
+        .map(|w| w.iter().sum::<f64>() / window as f64)
+        .collect()
+}
+
+fn is_sorted_by_windows(values: &[i32]) -> bool {
+    values.windows(2).all(|w| w[0] <= w[1])
+}
+
+// chunks keeps the short last piece, chunks_exact would drop it
+fn chunk_sums(values: &[i32], size: usize) -> Vec<i32> {
+    values.chunks(size).map(|c| c.iter().sum()).collect()
+}
+
+fn main(){
+    let numbers = [3, 1, 4, 1, 5, 9, 2, 6];
+
+    println!("first and last of {:?}: {:?}", numbers, first_and_last(&numbers));
+    println!("first and last of [7]: {:?}", first_and_last(&[7]));
+    println!("first and last of []: {:?}", first_and_last(&[]));
+
+    println!("starts with (3, 1)? {}", starts_with_pair(&numbers, (3, 1)));
+    println!("[3] starts with (3, 1)? {}", starts_with_pair(&[3], (3, 1)));
+
+    println!("recursive sum is {}", sum_recursive(&numbers));
+    for slice in [&numbers[..0], &numbers[..1], &numbers[..2], &numbers[..]] {
+        println!("{:?} is {}", slice, describe(slice));
+    }
+
+    let prices = [1.0, 2.0, 3.0, 4.0, 5.0];
+    println!("moving average (3) of {:?}: {:?}", prices, moving_average(&prices, 3));
+    println!("moving average (10) of {:?}: {:?}", prices, moving_average(&prices, 10));
+
+    println!("is {:?} sorted? {}", numbers, is_sorted_by_windows(&numbers));
+    println!("is [1, 2, 2, 3] sorted? {}", is_sorted_by_windows(&[1, 2, 2, 3]));
+
+    println!("sums of chunks of 3: {:?}", chunk_sums(&numbers, 3));
+
+    // the same two ideas for any iterator, not just slices
+    let steps: Vec<i32> = numbers.iter().pairs().map(|(a, b)| b - a).collect();
+    println!("steps between neighbours: {:?}", steps);
+    let batches: Vec<Vec<char>> = "iterator".chars().batched(3).collect();
+    println!("\"iterator\" in batches of 3: {:?}", batches);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_and_last_of_every_length() {
+        assert_eq!(first_and_last(&[]), None);
+        assert_eq!(first_and_last(&[7]), Some((7, 7)));
+        assert_eq!(first_and_last(&[1, 2]), Some((1, 2)));
+        assert_eq!(first_and_last(&[3, 1, 4, 1, 5]), Some((3, 5)));
+    }
+
+    #[test]
+    fn starts_with_pair_needs_two_elements() {
+        assert!(starts_with_pair(&[3, 1, 4], (3, 1)));
+        assert!(!starts_with_pair(&[3, 2, 4], (3, 1)));
+        assert!(!starts_with_pair(&[3], (3, 1)));
+        assert!(!starts_with_pair(&[], (3, 1)));
+    }
+
+    #[test]
+    fn rest_patterns() {
+        assert_eq!(sum_recursive(&[]), 0);
+        assert_eq!(sum_recursive(&[3, 1, 4, 1, 5]), 14);
+        assert_eq!(describe(&[]), "empty");
+        assert_eq!(describe(&[9]), "just 9");
+        assert_eq!(describe(&[1, 2]), "a pair 1 and 2");
+        assert_eq!(describe(&[1, 2, 3, 4]), "1 and 2 then 2 more");
+    }
+
+    #[test]
+    fn moving_average_values() {
+        let prices = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(moving_average(&prices, 3), [2.0, 3.0, 4.0]);
+        assert_eq!(moving_average(&prices, 1), prices);
+        assert_eq!(moving_average(&prices, 5), [3.0]);
+        assert_eq!(moving_average(&[0.5, 1.5], 2), [1.0]);
+    }
+
+    #[test]
+    fn moving_average_edge_cases() {
+        assert!(moving_average(&[1.0, 2.0], 10).is_empty());
+        assert!(moving_average(&[], 3).is_empty());
+        assert!(moving_average(&[1.0], 0).is_empty());
+        assert_eq!(moving_average(&[4.0], 1), [4.0]);
+    }
+
+    #[test]
+    fn sorted_by_windows() {
+        assert!(is_sorted_by_windows(&[]));
+        assert!(is_sorted_by_windows(&[5]));
+        assert!(is_sorted_by_windows(&[1, 2, 2, 3]));
+        assert!(!is_sorted_by_windows(&[1, 3, 2]));
+    }
+
+    #[test]
+    fn chunk_sums_keep_the_short_last_chunk() {
+        assert_eq!(chunk_sums(&[3, 1, 4, 1, 5, 9, 2, 6], 3), [8, 15, 8]);
+        assert!(chunk_sums(&[], 3).is_empty());
+    }
+}
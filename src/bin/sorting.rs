@@ -0,0 +1,45 @@
+// rust_tut::sorting next to slice::sort
+//
+// cargo run --release --bin sorting
+// the timings mean little in a debug build
+
+use std::time::{Duration, Instant};
+
+use rust_tut::rng::Rng;
+use rust_tut::sorting::{is_sorted, sort_stats, Algorithm};
+
+fn random_values(n: usize, seed: u64) -> Vec<i64> {
+    let mut rng = Rng::new(seed);
+    (0..n).map(|_| rng.range(0, 1_000_000)).collect()
+}
+
+fn time(values: &[i64], sort: impl Fn(&mut [i64])) -> Duration {
+    let mut copy = values.to_vec();
+    let start = Instant::now();
+    sort(&mut copy);
+    let took = start.elapsed();
+    assert!(is_sorted(&copy));
+    took
+}
+
+fn main(){
+    for input in [random_values(10, 1), (0..10).collect(), (0..10).rev().collect()] {
+        println!("{:?}", input);
+        for algorithm in Algorithm::ALL {
+            let stats = sort_stats(algorithm, &mut input.clone());
+            println!("  {:?}: {} comparisons, {} swaps", algorithm, stats.comparisons, stats.swaps);
+        }
+    }
+
+    for n in [100, 10_000] {
+        let values = random_values(n, 7);
+        println!("{} random values:", n);
+        for algorithm in Algorithm::ALL {
+            let took = time(&values, |v| {
+                sort_stats(algorithm, v);
+            });
+            println!("  {:<11} {:?}", format!("{:?}", algorithm), took);
+        }
+        println!("  {:<11} {:?}", "slice::sort", time(&values, |v| v.sort()));
+    }
+}
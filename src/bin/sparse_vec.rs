@@ -0,0 +1,29 @@
+// rust_tut::sparse::SparseVec, only the non-zero entries are stored
+
+use rust_tut::sparse::SparseVec;
+
+fn main(){
+    let mut a = SparseVec::new(1_000_000);
+    let mut b = SparseVec::new(1_000_000);
+    a.set(3, 2.0).unwrap();
+    a.set(999_999, 1.5).unwrap();
+    b.set(3, 4.0).unwrap();
+    b.set(10, 7.0).unwrap();
+    println!("a has {} of {} entries set, density {}", a.nnz(), a.len(), a.density());
+    println!("a . b = {:?}", a.dot(&b));
+
+    let sum = a.add(&b).unwrap();
+    println!("a + b has {} entries, [3] = {:?}", sum.nnz(), sum.get(3));
+
+    a.set(3, 0.0).unwrap();
+    println!("after setting a[3] to 0 it stores {} entries", a.nnz());
+    println!("a . b now = {:?} (no shared indexes)", a.dot(&b));
+
+    println!("setting past the end: {:?}", a.set(1_000_000, 1.0));
+    println!("dot with a shorter vector: {:?}", a.dot(&SparseVec::new(5)));
+
+    let dense = [0.0, 1.0, 0.0, 0.0, -3.0];
+    let round_trip = SparseVec::from_dense(&dense);
+    println!("{:?} -> {} entries -> {:?}", dense, round_trip.nnz(), round_trip.to_dense());
+    println!("all zeros stores {} entries", SparseVec::from_dense(&[0.0; 4]).nnz());
+}
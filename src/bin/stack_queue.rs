@@ -0,0 +1,47 @@
+// rust_tut::collections_ext: a Stack (LIFO) and a Queue (FIFO)
+
+use rust_tut::collections_ext::{hot_potato, is_balanced, Queue, Stack};
+use rust_tut::container::{drain_into, transfer, Container};
+use rust_tut::ring_buffer::RingBuffer;
+
+fn main(){
+    let mut stack: Stack<i32> = (1..=3).collect();
+    stack.push(4);
+    println!("stack top to bottom: {:?}", stack.iter().collect::<Vec<_>>());
+    println!("peek {:?}", stack.peek());
+    println!("pop {:?}, len now {}", stack.pop(), stack.len());
+    stack.extend([10, 20]);
+    println!("after extend, popping everything: {:?}", stack.into_iter().collect::<Vec<_>>());
+
+    let mut queue: Queue<&str> = ["first", "second"].into_iter().collect();
+    queue.enqueue("third");
+    println!("queue front to back: {:?}", queue.iter().collect::<Vec<_>>());
+    println!("front {:?}", queue.front());
+    println!("dequeue {:?}", queue.dequeue());
+    for name in &queue {
+        println!("  still waiting: {}", name);
+    }
+
+    // the same items through Container: a stack hands them over newest first,
+    // so moving them into a queue turns the order around
+    let mut stack: Stack<i32> = (1..=4).collect();
+    let mut queue = Queue::new();
+    println!("moved {} from the stack into the queue", transfer(&mut stack, &mut queue));
+    println!("stack size {}, queue size {}", stack.size(), queue.size());
+    println!("the queue now gives {:?}", drain_into(&mut queue));
+
+    // a ring buffer with room for 3 keeps only the last 3 it was given
+    let mut ring = RingBuffer::with_capacity(3).unwrap();
+    for n in 1..=5 {
+        ring.put(n);
+    }
+    println!("ring buffer gives {:?}", drain_into(&mut ring));
+
+    for text in ["(a[b]{c})", "([)]", "((", "", "fn main() { v[0]; }"] {
+        println!("{:?} balanced? {}", text, is_balanced(text));
+    }
+
+    let players = ["Bill", "David", "Susan", "Jane", "Kent", "Brad"];
+    println!("hot potato with k = 7, winner: {:?}", hot_potato(&players, 7));
+    println!("hot potato with nobody: {:?}", hot_potato(&[], 3));
+}
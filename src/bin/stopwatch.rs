@@ -0,0 +1,44 @@
+// timing a loop with rust_tut::stopwatch::Stopwatch
+
+use std::thread;
+use std::time::Duration;
+
+use rust_tut::ring_buffer::RingBuffer;
+use rust_tut::stopwatch::{Stopwatch, RECENT_LAPS};
+
+fn main(){
+    let mut watch = Stopwatch::start();
+
+    for round in 1..=3 {
+        let mut total: u64 = 0;
+        for i in 0..(round * 1_000_000) {
+            total = total.wrapping_add(i);
+        }
+        std::hint::black_box(total);
+        println!("lap {}: {:?}", round, watch.lap());
+    }
+
+    // time spent paused is not counted
+    watch.pause();
+    thread::sleep(Duration::from_millis(200));
+    watch.resume();
+    println!("after a 200ms pause, elapsed is still {:?}", watch.elapsed());
+
+    println!("all laps: {:?}", watch.laps());
+
+    for _ in 0..4 {
+        watch.lap();
+    }
+    let recent: Vec<_> = watch.recent_laps().collect();
+    println!("{} laps in total, the last {}: {:?}", watch.laps().len(), RECENT_LAPS, recent);
+
+    // the ring buffer on its own: room for 3, so pushing 4 and 5 overwrites 1 and 2
+    let mut ring = RingBuffer::with_capacity(3).unwrap();
+    for value in 1..=5 {
+        if let Some(old) = ring.push(value) {
+            println!("pushing {} overwrote {}", value, old);
+        }
+    }
+    println!("oldest to newest: {:?}, latest {:?}", ring.iter().collect::<Vec<_>>(), ring.latest());
+    println!("zero capacity: {:?}", RingBuffer::<i32>::with_capacity(0).err());
+}
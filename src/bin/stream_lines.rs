@@ -0,0 +1,57 @@
+// rust_tut::line_reader: count matching lines while the file is still being read
+//
+// cargo run --bin stream_lines -- rust rust.txt
+
+use std::path::PathBuf;
+
+use rust_tut::report::args_without_report_flags;
+use rust_tut::error::{AppError, ResultExt};
+use rust_tut::exit_code::Exit;
+use rust_tut::line_reader::spawn_line_reader;
+use rust_tut::pairs::MinMax;
+
+// a missing file ends with exit code 66, see rust_tut::exit_code
+fn main() -> Exit {
+    run().into()
+}
+
+fn run() -> Result<(), AppError> {
+    let args = args_without_report_flags();
+    let pattern = args.first().map(String::as_str).unwrap_or("rust");
+    let path = PathBuf::from(args.get(1).map(String::as_str).unwrap_or("rust.txt"));
+
+    let (lines, reader) = spawn_line_reader(path.clone());
+    let mut total = 0;
+    let mut matching = 0;
+    let mut failed = None;
+    // the shortest and longest line, in chars, kept as (length, line number)
+    let mut lengths = MinMax::new();
+    // the loop ends when the reader thread drops its sender
+    for line in lines {
+        match line {
+            Ok(line) => {
+                total += 1;
+                lengths.observe((line.chars().count(), total));
+                if line.contains(pattern) {
+                    matching += 1;
+                    println!("{:>4}: {}", total, line);
+                }
+            }
+            // the reader sends at most one Err and stops right after it
+            Err(err) => failed = Some(err),
+        }
+    }
+    reader.join().unwrap();
+    // the size is only extra information, a failure to get it is not fatal
+    let bytes = std::fs::metadata(&path).map(|meta| meta.len()).warn_default();
+    println!("{} is {} bytes", path.display(), bytes);
+    println!("{} of {} lines in {} contain `{}`", matching, total, path.display(), pattern);
+    if let Some(((short, short_at), (long, long_at))) = lengths.get() {
+        println!("shortest line: {} chars (line {})", short, short_at);
+        println!("longest line: {} chars (line {})", long, long_at);
+    }
+    match failed {
+        Some(err) => Err(err.into()),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,47 @@
+// rust_tut::row: one ToRow/FromRow impl per type gives a CSV file and a
+// printed table for it, the CSV and table code never names the type
+
+use chrono::{TimeZone, Utc};
+
+use rust_tut::datetime::events::Event;
+use rust_tut::rect::Rect;
+use rust_tut::row::{format_table, read_csv, to_csv, FromRow, ToRow};
+use rust_tut::sample_data::sample_users;
+use rust_tut::str_ext::StrExt;
+use rust_tut::user::User;
+
+// out to CSV, back in, and a check that nothing changed on the way
+fn round_trip<T: ToRow + FromRow + PartialEq>(label: &str, items: &[T]) {
+    let csv = to_csv(items);
+    println!("{} as CSV:\n{}", label, csv);
+    match read_csv::<T>(&csv) {
+        Ok(back) => println!("read back {} {}, same? {}", back.len(), label, back == items),
+        Err(err) => println!("error: {}", err),
+    }
+    match format_table(items) {
+        Ok(table) => println!("{}", table.indent(2)),
+        Err(err) => println!("error: {}", err),
+    }
+}
+
+fn main(){
+    round_trip("users", &sample_users(5, 4));
+    round_trip("rects", &[Rect::new(20, 40), Rect::new(3, 4)]);
+
+    let at = |h| Utc.with_ymd_and_hms(2025, 3, 10, h, 0, 0).unwrap();
+    let events = vec![
+        Event::new("standup, short", at(9), at(10)).unwrap(),
+        Event::new("review \"v2\"", at(13), at(15)).unwrap(),
+    ];
+    round_trip("events", &events);
+
+    // errors name the line and the column that was wrong
+    let bad = "len,breadth\n3,4\n5,wide\n";
+    if let Err(err) = read_csv::<Rect>(bad) {
+        println!("error: {}\n  caused by: {}", err, std::error::Error::source(&err).unwrap());
+    }
+    println!("{:?}", User::from_row(&["Asha", "asha"]));
+    println!("{:?}", read_csv::<Rect>("width,height\n1,2\n").map(|_| ()));
+    // header names are compared in snake_case, so other spellings still match
+    println!("{:?}", read_csv::<Rect>("Len,Breadth\n1,2\n"));
+}
@@ -0,0 +1,59 @@
+// rust_tut::thread_pool::ThreadPool: named workers that survive panicking jobs
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use rust_tut::error::OptionExt;
+use rust_tut::sample_data::sample_shapes;
+use rust_tut::shape::{calc_area, compute_areas_parallel};
+use rust_tut::thread_pool::ThreadPool;
+
+fn main(){
+    let pool = ThreadPool::new(4).unwrap();
+    let counter = Arc::new(AtomicUsize::new(0));
+    for _ in 0..100 {
+        let counter = Arc::clone(&counter);
+        pool.execute(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+
+    pool.execute(|| panic!("this job always fails"));
+    let after = Arc::clone(&counter);
+    pool.execute(move || {
+        let name = thread::current().name().expect_ctx("naming the worker").to_string();
+        println!("{} still runs jobs after the panic", name);
+        after.fetch_add(1, Ordering::SeqCst);
+    });
+
+    for _ in 0..8 {
+        pool.execute(|| thread::sleep(Duration::from_millis(50)));
+    }
+    thread::sleep(Duration::from_millis(20));
+    println!("active {} completed {}", pool.active_jobs(), pool.completed_jobs());
+
+    // dropping the pool waits for the queued sleeps to finish
+    drop(pool);
+    println!("counter is {} (100 jobs + 1 after the panic)", counter.load(Ordering::SeqCst));
+
+    // areas of seeded shapes on a fresh pool, checked against one thread doing it all
+    let shapes = sample_shapes(7, 5000);
+    let sequential: Vec<_> = shapes.iter().map(calc_area).enumerate().collect();
+    let pool = ThreadPool::new(4).unwrap();
+    let parallel = compute_areas_parallel(shapes, &pool);
+    let invalid = parallel.iter().filter(|(_, area)| area.is_err()).count();
+    println!(
+        "{} areas, {} invalid, same as sequential? {}",
+        parallel.len(),
+        invalid,
+        parallel == sequential
+    );
+    drop(pool);
+
+    match ThreadPool::new(0) {
+        Ok(_) => println!("a pool with no workers?"),
+        Err(err) => println!("error: {}", err),
+    }
+}
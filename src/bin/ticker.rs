@@ -0,0 +1,55 @@
+// a ticker thread from rust_tut::ticker, and stopping it early
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rust_tut::cancel::CancellationToken;
+use rust_tut::rate_limiter::RateLimiter;
+use rust_tut::ticker::Ticker;
+
+fn main(){
+    let ticks = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::clone(&ticks);
+    let ticker = Ticker::start(Duration::from_millis(100), move |tick| {
+        println!("tick {}", tick);
+        seen.lock().unwrap().push(tick);
+    });
+    thread::sleep(Duration::from_millis(550));
+    ticker.stop();
+    println!("collected {:?}", ticks.lock().unwrap());
+
+    // stop doesn't wait for a 10 second interval to run out
+    let slow = Ticker::start(Duration::from_secs(10), |tick| println!("slow tick {}", tick));
+    let start = Instant::now();
+    slow.stop();
+    println!("stopping a 10s ticker took {:?}", start.elapsed());
+
+    {
+        let _dropped = Ticker::start(Duration::from_secs(10), |_| {});
+    }
+    println!("a dropped ticker stops as well");
+
+    // a fast ticker behind a rate limiter: a burst of 3, then 5 a second
+    let limiter = RateLimiter::new(3, 5.0).unwrap();
+    let passed = Arc::new(Mutex::new(Vec::new()));
+    let kept = Arc::clone(&passed);
+    let fast = Ticker::start(Duration::from_millis(20), move |tick| {
+        if limiter.try_acquire() {
+            kept.lock().unwrap().push(tick);
+        }
+    });
+    thread::sleep(Duration::from_millis(500));
+    fast.stop();
+    println!("ticks let through by the limiter: {:?}", passed.lock().unwrap());
+
+    // one token shared by two tickers, cancelling it stops both
+    let token = CancellationToken::new();
+    let first = Ticker::start_with_token(Duration::from_secs(10), &token, |_| {});
+    let second = Ticker::start_with_token(Duration::from_secs(10), &token, |_| {});
+    let start = Instant::now();
+    token.cancel();
+    first.stop();
+    second.stop();
+    println!("cancelling a shared token stopped two 10s tickers in {:?}", start.elapsed());
+}
@@ -0,0 +1,181 @@
+// trait bounds, where clauses, conditional methods and blanket impls
+
+use std::fmt::{Debug, Display};
+use std::io;
+
+use rust_tut::area::{largest_by_area, total_area, total_area_dyn, Area};
+use rust_tut::pairs::{map_pair, swap, zip_with, MinMax};
+use rust_tut::point::Point;
+use rust_tut::rect::Rect;
+use rust_tut::shape::Shape;
+use rust_tut::summary::{print_summary, Summarize, SummaryReport};
+use rust_tut::user::User;
+
+struct Pair<T> {
+    x: T,
+    y: T,
+}
+
+impl<T> Pair<T> {
+    fn new(x: T, y: T) -> Pair<T> {
+        Pair { x, y }
+    }
+}
+
+// cmp_display only exists for pairs whose T can be shown and compared
+impl<T: Display + PartialOrd> Pair<T> {
+    fn cmp_display(&self) -> String {
+        if self.x >= self.y {
+            format!("the largest member is x = {}", self.x)
+        } else {
+            format!("the largest member is y = {}", self.y)
+        }
+    }
+}
+
+// an empty slice has no largest element, so we return an Option
+fn largest<T: PartialOrd>(list: &[T]) -> Option<&T> {
+    let mut largest = list.first()?;
+    for item in list {
+        if item > largest {
+            largest = item;
+        }
+    }
+    Some(largest)
+}
+
+// Summarize lives in rust_tut::summary, a bin can still implement it for its own types
+struct Wrapper<T>(T);
+
+// blanket impl: every Wrapper of something Display is Summarize
+impl<T: Display> Summarize for Wrapper<T> {
+    fn summary(&self) -> String {
+        format!("Wrapper({})", self.0)
+    }
+}
+
+// with three bounds the where clause keeps the signature readable
+fn describe_all<T, U>(items: &[T], label: U) -> String
+where
+    T: Display + Clone + PartialOrd,
+    U: Debug,
+{
+    let mut sorted = items.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let parts: Vec<String> = sorted.iter().map(|item| item.to_string()).collect();
+    format!("{:?}: {}", label, parts.join(", "))
+}
+
+fn main(){
+    let numbers = Pair::new(3, 8);
+    println!("{}", numbers.cmp_display());
+
+    // Pair<Vec<i32>> works, but it has no cmp_display since Vec isn't Display
+    let vectors = Pair::new(vec![1], vec![2]);
+    println!("a pair of vectors: {:?} and {:?}", vectors.x, vectors.y);
+
+    println!("largest i32 is {:?}", largest(&[34, 50, 25, 100, 65]));
+    let words = vec![String::from("apple"), String::from("pear"), String::from("fig")];
+    println!("largest String is {:?}", largest(&words));
+    let empty: [i32; 0] = [];
+    println!("largest of nothing is {:?}", largest(&empty));
+
+    println!("{}", Wrapper(42).summary());
+    println!("{}", Wrapper("hello").summary());
+
+    // only summary is written for these, summary_short and summarize_to are defaults
+    let user = User::new("Pawan Kumar", "pawan", "pawan@example.com", 1, true).unwrap();
+    print_summary(&user);
+    print_summary(&Rect::new(20, 40));
+    print_summary(&Shape::Circle(1.5));
+    let long = Wrapper("a wrapper with far too much text to fit on one short line");
+    print_summary(&long);
+    long.summarize_to(&mut io::stdout()).unwrap();
+
+    // &&Point goes through the &T impl twice, Point's own summary_short still wins
+    let point = Point::new(3, -4);
+    print_summary(&&point);
+    println!("{}", point.summary());
+
+    let boxed: Box<dyn Summarize> = Box::new(Rect::new(2, 3));
+    print_summary(&boxed);
+
+    // users, points and shapes side by side in one report
+    let mut report = SummaryReport::new();
+    report.add_item(user);
+    report.add_item(point);
+    report.add(Box::new(Shape::Rect(2.0, 3.0)));
+    report.add_item(Point::new(0, 0));
+    print!("{}", report.render());
+    print!("sorted:\n{}", report.render_sorted());
+
+    // Rect and Shape have nothing in common except the Area trait
+    let rects = [Rect::new(20, 40), Rect::new(3, 4)];
+    let shapes = [Shape::Circle(1.0), Shape::Rect(2.0, 2.5)];
+    println!("rects cover {} {}", total_area(&rects), rects[0].unit_label());
+    println!("shapes cover {:.2}", total_area(&shapes));
+    println!("largest shape is {:?}", largest_by_area(&shapes));
+    let mixed: [&dyn Area; 3] = [&rects[0], &shapes[0], &shapes[1]];
+    println!("mixed slice covers {:.2}", total_area_dyn(&mixed));
+
+    // + covers both rects, * scales them, < means "fits inside"
+    let (wide, tall) = (Rect::new(5, 2), Rect::new(2, 5));
+    println!("{:?} + {:?} = {:?}", wide, tall, wide + tall);
+    println!("{:?} * 3 = {:?}", wide, wide * 3);
+    println!("{:?} < {:?}? {}", Rect::new(1, 1), wide, Rect::new(1, 1) < wide);
+    println!("{:?} vs {:?}: {:?}", wide, tall, wide.partial_cmp(&tall));
+
+    // the pairs helpers only ask for what they use: zip_with needs nothing of
+    // A and B, MinMax needs PartialOrd, so both i32 and String work
+    println!("swap (1, 2) = {:?}", swap((1, 2)));
+    println!("lengths of (\"ab\", \"xyz\") = {:?}", map_pair(("ab", "xyz"), str::len));
+    println!("zip_with on 3 and 2 items: {:?}", zip_with(&[1, 2, 3], &[10, 20], |a, b| a * b));
+    let mut numbers = MinMax::new();
+    numbers.extend([5, 3, 9, 1]);
+    let mut names = MinMax::new();
+    names.extend(["pear", "apple", "fig"].map(String::from));
+    println!("numbers {:?}, names {:?}", numbers.get(), names.get());
+
+    println!("{}", describe_all(&[3.5, 1.25, 2.0], "floats"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cmp_display_names_the_larger_member() {
+        assert_eq!(Pair::new(3, 8).cmp_display(), "the largest member is y = 8");
+        assert_eq!(Pair::new("b", "a").cmp_display(), "the largest member is x = b");
+        // a tie goes to x
+        assert_eq!(Pair::new(1.5, 1.5).cmp_display(), "the largest member is x = 1.5");
+    }
+
+    #[test]
+    fn largest_works_for_numbers_and_strings() {
+        assert_eq!(largest(&[34, 50, 25, 100, 65]), Some(&100));
+        let words = [String::from("apple"), String::from("pear"), String::from("fig")];
+        assert_eq!(largest(&words).map(String::as_str), Some("pear"));
+        // the first of equal largest values is the one returned
+        let pairs = [(1, 'a'), (1, 'a')];
+        assert!(std::ptr::eq(largest(&pairs).unwrap(), &pairs[0]));
+    }
+
+    #[test]
+    fn largest_of_an_empty_slice_is_none() {
+        let empty: [i32; 0] = [];
+        assert_eq!(largest(&empty), None);
+    }
+
+    #[test]
+    fn blanket_impl_covers_any_display() {
+        assert_eq!(Wrapper(42).summary(), "Wrapper(42)");
+        assert_eq!(Wrapper("hello").summary(), "Wrapper(hello)");
+    }
+
+    #[test]
+    fn describe_all_sorts_and_labels() {
+        assert_eq!(describe_all(&[3.5, 1.25, 2.0], "floats"), "\"floats\": 1.25, 2, 3.5");
+        assert_eq!(describe_all::<i32, _>(&[], 7), "7: ");
+    }
+}
@@ -0,0 +1,136 @@
+// typestate: encode the state in the type so wrong calls don't compile
+// PhantomData lets a struct carry a type parameter it doesn't store
+
+use std::marker::PhantomData;
+use std::ops::Add;
+
+// zero sized marker types, they only exist at compile time
+struct Locked;
+struct Unlocked;
+
+struct Door<State> {
+    name: String,
+    _state: PhantomData<State>,
+}
+
+impl Door<Locked> {
+    fn new(name: &str) -> Door<Locked> {
+        Door { name: name.to_string(), _state: PhantomData }
+    }
+
+    // takes self by value, so the locked door can't be used afterwards
+    fn unlock(self, key: &str) -> Result<Door<Unlocked>, Door<Locked>> {
+        if key == "secret" {
+            Ok(Door { name: self.name, _state: PhantomData })
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Door<Unlocked> {
+    fn open(self) -> String {
+        format!("the {} is open", self.name)
+    }
+
+    fn lock(self) -> Door<Locked> {
+        Door { name: self.name, _state: PhantomData }
+    }
+}
+
+// units: the same f64 underneath, but meters and seconds don't mix
+struct Meters;
+struct Seconds;
+
+struct Quantity<Unit> {
+    value: f64,
+    _unit: PhantomData<Unit>,
+}
+
+impl<Unit> Quantity<Unit> {
+    fn new(value: f64) -> Quantity<Unit> {
+        Quantity { value, _unit: PhantomData }
+    }
+}
+
+// only quantities with the same unit can be added
+impl<Unit> Add for Quantity<Unit> {
+    type Output = Quantity<Unit>;
+
+    fn add(self, other: Quantity<Unit>) -> Quantity<Unit> {
+        Quantity::new(self.value + other.value)
+    }
+}
+
+fn main(){
+    println!("Door<Locked> only has unlock(), Door<Unlocked> only has open() and lock()");
+    println!("each transition consumes the old door and returns one in the new state");
+    let extra = std::mem::size_of::<Door<Locked>>() - std::mem::size_of::<String>();
+    println!("a Door<Locked> takes {} bytes more than its name", extra);
+
+    let door = Door::new("front door");
+    let door = match door.unlock("wrong key") {
+        Ok(_) => unreachable!(),
+        Err(still_locked) => {
+            println!("wrong key, the {} stays locked", still_locked.name);
+            still_locked
+        }
+    };
+    let door = door.unlock("secret").ok().unwrap();
+    let door = door.lock().unlock("secret").ok().unwrap();
+    println!("{}", door.open());
+
+    // these don't compile, the methods simply don't exist for that state:
+    // Door::new("back door").open();          // no method `open` on Door<Locked>
+    // door_unlocked.unlock("secret");         // no method `unlock` on Door<Unlocked>
+    // door.open(); door.open();               // use of moved value: `door`
+
+    let run: Quantity<Meters> = Quantity::new(100.0) + Quantity::new(50.0);
+    let time: Quantity<Seconds> = Quantity::new(12.5);
+    println!("ran {} m in {} s", run.value, time.value);
+
+    // let nonsense = run + time; // error: expected `Quantity<Meters>`, found `Quantity<Seconds>`
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_right_key_unlocks_and_the_name_survives() {
+        let door = Door::new("front door").unlock("secret").ok().unwrap();
+        assert_eq!(door.name, "front door");
+        assert_eq!(door.open(), "the front door is open");
+    }
+
+    #[test]
+    fn the_wrong_key_gives_the_locked_door_back() {
+        let door = match Door::new("shed").unlock("guess") {
+            Ok(_) => panic!("the wrong key opened the door"),
+            Err(door) => door,
+        };
+        assert_eq!(door.name, "shed");
+        assert!(door.unlock("secret").is_ok());
+    }
+
+    #[test]
+    fn lock_and_unlock_round_trip() {
+        let door = Door::new("gate").unlock("secret").ok().unwrap();
+        let door = door.lock().unlock("secret").ok().unwrap().lock();
+        assert_eq!(door.name, "gate");
+    }
+
+    #[test]
+    fn markers_take_no_space() {
+        assert_eq!(std::mem::size_of::<Door<Locked>>(), std::mem::size_of::<String>());
+        assert_eq!(std::mem::size_of::<Quantity<Meters>>(), std::mem::size_of::<f64>());
+    }
+
+    #[test]
+    fn quantities_of_one_unit_add_up() {
+        let run: Quantity<Meters> = Quantity::new(100.0) + Quantity::new(50.0);
+        assert_eq!(run.value, 150.0);
+        let time: Quantity<Seconds> = Quantity::new(1.5) + Quantity::new(-0.5);
+        assert_eq!(time.value, 1.0);
+    }
+}
@@ -0,0 +1,204 @@
+// a pool of worker threads sharing one job queue
+// jobs go in on one channel, results come back on another with the job's id
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use rust_tut::cancel::CancellationToken;
+use rust_tut::isolate::panic_message;
+use rust_tut::iter_ext::IterExt;
+use rust_tut::shape::{calc_area_unchecked, Shape};
+
+type JobResult = f64;
+type Job = Box<dyn FnOnce() -> JobResult + Send>;
+
+struct JobOutput {
+    id: usize,
+    // Err holds the panic message when the job panicked
+    result: Result<JobResult, String>,
+}
+
+struct WorkerPool {
+    sender: Sender<(usize, Job)>,
+    results: Receiver<JobOutput>,
+    workers: Vec<JoinHandle<()>>,
+    next_id: usize,
+}
+
+impl WorkerPool {
+    // once `token` is cancelled the workers stop picking up queued jobs
+    fn new(n_workers: usize, token: &CancellationToken) -> WorkerPool {
+        let (sender, receiver) = channel::<(usize, Job)>();
+        let (result_sender, results) = channel();
+        // only one worker can wait on the receiver at a time, hence the Mutex
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..n_workers.max(1))
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                let result_sender = result_sender.clone();
+                let token = token.clone();
+                thread::spawn(move || worker_loop(receiver, result_sender, token))
+            })
+            .collect();
+
+        WorkerPool { sender, results, workers, next_id: 0 }
+    }
+
+    // queues a job and returns the id its result will come back with
+    fn submit<F>(&mut self, job: F) -> usize
+    where
+        F: FnOnce() -> JobResult + Send + 'static,
+    {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.sender.send((id, Box::new(job))).unwrap();
+        id
+    }
+
+    // closes the queue, lets the workers finish what is left and joins them
+    fn shutdown(self) -> Vec<JobOutput> {
+        drop(self.sender);
+        for worker in self.workers {
+            worker.join().unwrap();
+        }
+        self.results.iter().collect()
+    }
+}
+
+fn worker_loop(
+    receiver: Arc<Mutex<Receiver<(usize, Job)>>>,
+    results: Sender<JobOutput>,
+    token: CancellationToken,
+) {
+    while !token.is_cancelled() {
+        // the lock is released at the end of this statement, before running the job
+        let message = receiver.lock().unwrap().recv();
+        let Ok((id, job)) = message else {
+            // every sender is gone, so no more jobs will come
+            break;
+        };
+        // cancelled while waiting for this job, so it is dropped unrun
+        if token.is_cancelled() {
+            break;
+        }
+        let result =
+            catch_unwind(AssertUnwindSafe(job)).map_err(|payload| panic_message(&*payload));
+        if results.send(JobOutput { id, result }).is_err() {
+            break;
+        }
+    }
+}
+
+fn generate_shapes(count: usize) -> Vec<Shape> {
+    (0..count)
+        .map(|i| {
+            let size = (i % 10 + 1) as f64;
+            if i % 2 == 0 { Shape::Rect(size, size + 1.0) } else { Shape::Circle(size) }
+        })
+        .collect()
+}
+
+fn main(){
+    let shapes = generate_shapes(1000);
+    // the generated shapes are all valid, so the unchecked area is fine
+    let expected: f64 = shapes.iter().map(calc_area_unchecked).sum();
+
+    let mut pool = WorkerPool::new(4, &CancellationToken::new());
+    // one job per 64 shapes, a job per shape would mostly measure the channel
+    for batch in shapes.into_iter().batched(64) {
+        pool.submit(move || batch.iter().map(calc_area_unchecked).sum());
+    }
+    let bad_id = pool.submit(|| panic!("this job always fails"));
+    let good_id = pool.submit(|| 1.0);
+
+    let mut outputs = pool.shutdown();
+    outputs.sort_by_key(|output| output.id);
+
+    let total: f64 = outputs.iter().filter_map(|o| o.result.as_ref().ok()).sum();
+    println!("got {} results", outputs.len());
+    println!("total area {:.2} (sequential {:.2})", total - 1.0, expected);
+    println!("job {} -> {:?}", bad_id, outputs[bad_id].result);
+    println!("job {} after the panic -> {:?}", good_id, outputs[good_id].result);
+
+    // shutting down a pool that never got any work returns right away
+    let idle = WorkerPool::new(2, &CancellationToken::new());
+    println!("idle pool returned {} results", idle.shutdown().len());
+
+    // cancelling a pool with slow jobs queued: the running jobs finish,
+    // the rest are skipped
+    let token = CancellationToken::new();
+    let mut slow = WorkerPool::new(2, &token);
+    for _ in 0..20 {
+        slow.submit(|| {
+            thread::sleep(Duration::from_millis(50));
+            1.0
+        });
+    }
+    thread::sleep(Duration::from_millis(120));
+    token.cancel();
+    println!("cancelled pool ran {} of 20 slow jobs", slow.shutdown().len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_job_gets_a_result_with_its_id() {
+        let mut pool = WorkerPool::new(3, &CancellationToken::new());
+        let ids: Vec<usize> = (0..50).map(|i| pool.submit(move || i as f64 * 2.0)).collect();
+        let mut outputs = pool.shutdown();
+        outputs.sort_by_key(|output| output.id);
+        assert_eq!(outputs.iter().map(|o| o.id).collect::<Vec<_>>(), ids);
+        for output in &outputs {
+            assert_eq!(output.result, Ok(output.id as f64 * 2.0));
+        }
+    }
+
+    #[test]
+    fn pool_areas_match_the_sequential_total() {
+        let shapes = generate_shapes(1000);
+        let expected = shapes.iter().map(calc_area_unchecked).fold(0.0, |t, a| t + a);
+        let mut pool = WorkerPool::new(4, &CancellationToken::new());
+        for batch in shapes.into_iter().batched(64) {
+            pool.submit(move || batch.iter().map(calc_area_unchecked).sum());
+        }
+        let outputs = pool.shutdown();
+        assert_eq!(outputs.len(), 1000_usize.div_ceil(64));
+        let total = outputs.iter().map(|o| *o.result.as_ref().unwrap()).fold(0.0, |t, a| t + a);
+        assert!((total - expected).abs() < 1e-6 * expected);
+    }
+
+    #[test]
+    fn an_idle_pool_shuts_down() {
+        let pool = WorkerPool::new(4, &CancellationToken::new());
+        assert!(pool.shutdown().is_empty());
+    }
+
+    #[test]
+    fn a_panicking_job_does_not_kill_the_pool() {
+        // one worker, so the job after the panic has to run on the same thread
+        let mut pool = WorkerPool::new(1, &CancellationToken::new());
+        let bad = pool.submit(|| panic!("job failed on purpose"));
+        let good = pool.submit(|| 1.5);
+        let mut outputs = pool.shutdown();
+        outputs.sort_by_key(|output| output.id);
+        assert_eq!(outputs[bad].result, Err(String::from("job failed on purpose")));
+        assert_eq!(outputs[good].result, Ok(1.5));
+    }
+
+    #[test]
+    fn a_cancelled_pool_skips_queued_jobs() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let mut pool = WorkerPool::new(2, &token);
+        for _ in 0..10 {
+            pool.submit(|| 1.0);
+        }
+        assert!(pool.shutdown().is_empty());
+    }
+}
@@ -0,0 +1,141 @@
+// producers and consumers joined by a bounded sync_channel.
+// once `capacity` items are waiting, send blocks until a consumer catches up
+
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineReport {
+    pub produced: usize,
+    pub consumed: usize,
+    // the longest a producer sat in send waiting for room in the buffer
+    pub max_observed_queue_wait: Duration,
+    // every item id the consumers saw, sorted
+    pub consumed_ids: Vec<usize>,
+}
+
+// producer p makes ids p * items_per_producer .. (p + 1) * items_per_producer
+pub fn run_pipeline(
+    producers: usize,
+    consumers: usize,
+    items_per_producer: usize,
+    capacity: usize,
+) -> PipelineReport {
+    run_pipeline_with_delay(producers, consumers, items_per_producer, capacity, Duration::ZERO)
+}
+
+// the same, but every consumer sleeps for `work` per item so the buffer fills up
+pub fn run_pipeline_with_delay(
+    producers: usize,
+    consumers: usize,
+    items_per_producer: usize,
+    capacity: usize,
+    work: Duration,
+) -> PipelineReport {
+    assert!(producers > 0, "the pipeline needs at least one producer");
+    assert!(consumers > 0, "the pipeline needs at least one consumer");
+
+    let (sender, receiver) = sync_channel::<usize>(capacity);
+    let receiver = Arc::new(Mutex::new(receiver));
+
+    let consumer_handles: Vec<_> = (0..consumers)
+        .map(|_| {
+            let receiver = Arc::clone(&receiver);
+            thread::spawn(move || consume(&receiver, work))
+        })
+        .collect();
+
+    let producer_handles: Vec<_> = (0..producers)
+        .map(|p| {
+            let sender = sender.clone();
+            thread::spawn(move || {
+                let mut longest = Duration::ZERO;
+                for k in 0..items_per_producer {
+                    let start = Instant::now();
+                    sender.send(p * items_per_producer + k).unwrap();
+                    longest = longest.max(start.elapsed());
+                }
+                longest
+            })
+        })
+        .collect();
+    // only the producers' clones are left, when they finish the consumers see the end
+    drop(sender);
+
+    let mut max_observed_queue_wait = Duration::ZERO;
+    for handle in producer_handles {
+        max_observed_queue_wait = max_observed_queue_wait.max(handle.join().unwrap());
+    }
+    let mut consumed_ids = Vec::new();
+    for handle in consumer_handles {
+        consumed_ids.extend(handle.join().unwrap());
+    }
+    consumed_ids.sort_unstable();
+
+    PipelineReport {
+        produced: producers * items_per_producer,
+        consumed: consumed_ids.len(),
+        max_observed_queue_wait,
+        consumed_ids,
+    }
+}
+
+fn consume(receiver: &Mutex<Receiver<usize>>, work: Duration) -> Vec<usize> {
+    let mut seen = Vec::new();
+    loop {
+        // recv fails once every sender is gone and the buffer is empty
+        let item = receiver.lock().unwrap().recv();
+        match item {
+            Ok(id) => {
+                if !work.is_zero() {
+                    thread::sleep(work);
+                }
+                seen.push(id);
+            }
+            Err(_) => return seen,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn everything_produced_is_consumed_once() {
+        let setups = [(1, 1, 50, 1), (4, 3, 250, 8), (3, 5, 7, 0)];
+        for (producers, consumers, items, capacity) in setups {
+            let report = run_pipeline(producers, consumers, items, capacity);
+            assert_eq!(report.produced, producers * items);
+            assert_eq!(report.consumed, report.produced);
+            let expected: Vec<usize> = (0..producers * items).collect();
+            assert_eq!(report.consumed_ids, expected);
+        }
+    }
+
+    #[test]
+    fn ends_when_the_producers_are_done() {
+        // returning at all means the consumers saw every sender go away
+        let report = run_pipeline(2, 4, 0, 4);
+        assert_eq!((report.produced, report.consumed), (0, 0));
+        assert!(report.consumed_ids.is_empty());
+    }
+
+    #[test]
+    fn a_full_buffer_makes_producers_wait() {
+        let work = Duration::from_millis(5);
+        let report = run_pipeline_with_delay(2, 1, 10, 1, work);
+        assert_eq!(report.consumed, 20);
+        // two producers share one slot and one slow consumer, so once it fills up
+        // a send waits about two items of work. half of one is a safe floor
+        assert!(report.max_observed_queue_wait >= work / 2, "{:?}", report.max_observed_queue_wait);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one consumer")]
+    fn no_consumers_is_refused() {
+        run_pipeline(1, 0, 1, 1);
+    }
+}
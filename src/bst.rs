@@ -0,0 +1,305 @@
+// a binary search tree where every child is an Option<Box<Node>>:
+// Box gives the recursive type a known size, Option marks a missing child.
+// the walks are loops instead of recursion, so a lopsided tree is no problem
+
+use std::cmp::Ordering;
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+pub struct Bst<T: Ord> {
+    root: Link<T>,
+    len: usize,
+}
+
+// removes the smallest node under `link` and returns its value, the
+// node's right child (it can't have a left one) takes its place
+fn take_min<T>(mut link: &mut Link<T>) -> Option<T> {
+    while link.as_ref()?.left.is_some() {
+        link = &mut link.as_mut().unwrap().left;
+    }
+    let node = link.take()?;
+    *link = node.right;
+    Some(node.value)
+}
+
+impl<T: Ord> Bst<T> {
+    pub fn new() -> Bst<T> {
+        Bst { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // false when the value was already in the tree
+    pub fn insert(&mut self, value: T) -> bool {
+        let mut link = &mut self.root;
+        while let Some(node) = link {
+            link = match value.cmp(&node.value) {
+                Ordering::Less => &mut node.left,
+                Ordering::Greater => &mut node.right,
+                Ordering::Equal => return false,
+            };
+        }
+        *link = Some(Box::new(Node { value, left: None, right: None }));
+        self.len += 1;
+        true
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let mut link = &self.root;
+        while let Some(node) = link {
+            link = match value.cmp(&node.value) {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+
+    // false when the value was not in the tree
+    pub fn remove(&mut self, value: &T) -> bool {
+        let mut link = &mut self.root;
+        // compare through a shared borrow first, then step down with a fresh
+        // mutable one, so `link` is still usable when the value is found
+        loop {
+            let order = match link {
+                Some(node) => value.cmp(&node.value),
+                None => return false,
+            };
+            link = match order {
+                Ordering::Less => &mut link.as_mut().unwrap().left,
+                Ordering::Greater => &mut link.as_mut().unwrap().right,
+                Ordering::Equal => break,
+            };
+        }
+
+        let node = link.take().unwrap();
+        *link = match (node.left, node.right) {
+            // a leaf just goes away
+            (None, None) => None,
+            // one child moves up into the node's place
+            (Some(child), None) | (None, Some(child)) => Some(child),
+            // two children: the smallest value on the right replaces it
+            (Some(left), Some(right)) => {
+                let mut right = Some(right);
+                let value = take_min(&mut right).unwrap();
+                Some(Box::new(Node { value, left: Some(left), right }))
+            }
+        };
+        self.len -= 1;
+        true
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(left) = &node.left {
+            node = left;
+        }
+        Some(&node.value)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(right) = &node.right {
+            node = right;
+        }
+        Some(&node.value)
+    }
+
+    // the number of nodes on the longest path from the root, 0 when empty
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut pending: Vec<(&Node<T>, usize)> = self.root.iter().map(|n| (&**n, 1)).collect();
+        while let Some((node, depth)) = pending.pop() {
+            height = height.max(depth);
+            for child in [&node.left, &node.right].into_iter().flatten() {
+                pending.push((child, depth + 1));
+            }
+        }
+        height
+    }
+
+    // smallest to largest
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left(&self.root);
+        iter
+    }
+}
+
+impl<T: Ord> Default for Bst<T> {
+    fn default() -> Bst<T> {
+        Bst::new()
+    }
+}
+
+// the default drop would recurse once per level
+impl<T: Ord> Drop for Bst<T> {
+    fn drop(&mut self) {
+        let mut pending: Vec<Box<Node<T>>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = pending.pop() {
+            pending.extend(node.left.take());
+            pending.extend(node.right.take());
+        }
+    }
+}
+
+// the stack holds the nodes whose value hasn't been given out yet,
+// with the next one on top
+pub struct Iter<'a, T> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn push_left(&mut self, mut link: &'a Link<T>) {
+        while let Some(node) = link {
+            self.stack.push(node);
+            link = &node.left;
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        self.push_left(&node.right);
+        Some(&node.value)
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for &'a Bst<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    fn shuffled(n: i64, seed: u64) -> Vec<i64> {
+        let mut values: Vec<i64> = (1..=n).collect();
+        let mut rng = Rng::new(seed);
+        for i in (1..values.len()).rev() {
+            let j = rng.range(0, i as i64 + 1) as usize;
+            values.swap(i, j);
+        }
+        values
+    }
+
+    fn sorted(tree: &Bst<i64>) -> Vec<i64> {
+        tree.iter().copied().collect()
+    }
+
+    //        50
+    //     30     70
+    //   20  40  60  80
+    //      35
+    fn sample() -> Bst<i64> {
+        let mut tree = Bst::new();
+        for value in [50, 30, 70, 20, 40, 60, 80, 35] {
+            assert!(tree.insert(value));
+        }
+        tree
+    }
+
+    #[test]
+    fn a_shuffled_range_comes_out_sorted() {
+        let values = shuffled(100, 7);
+        assert_ne!(values, (1..=100).collect::<Vec<_>>());
+        let mut tree = Bst::new();
+        for &value in &values {
+            assert!(tree.insert(value));
+        }
+        assert_eq!(tree.len(), 100);
+        assert_eq!(sorted(&tree), (1..=100).collect::<Vec<_>>());
+        assert_eq!((tree.min(), tree.max()), (Some(&1), Some(&100)));
+        assert!(tree.contains(&42));
+        assert!(!tree.contains(&0) && !tree.contains(&101));
+        // duplicates are ignored
+        assert!(!tree.insert(42));
+        assert_eq!(tree.len(), 100);
+        assert_eq!((&tree).into_iter().count(), 100);
+    }
+
+    #[test]
+    fn remove_a_leaf() {
+        let mut tree = sample();
+        assert!(tree.remove(&20));
+        assert!(!tree.contains(&20));
+        assert_eq!(sorted(&tree), [30, 35, 40, 50, 60, 70, 80]);
+        assert_eq!(tree.len(), 7);
+    }
+
+    #[test]
+    fn remove_a_node_with_one_child() {
+        let mut tree = sample();
+        assert!(tree.remove(&40));
+        assert_eq!(sorted(&tree), [20, 30, 35, 50, 60, 70, 80]);
+        assert!(tree.contains(&35));
+        assert_eq!(tree.height(), 3);
+    }
+
+    #[test]
+    fn remove_nodes_with_two_children() {
+        let mut tree = sample();
+        assert!(tree.remove(&30));
+        assert_eq!(sorted(&tree), [20, 35, 40, 50, 60, 70, 80]);
+        // the root, 60 takes its place
+        assert!(tree.remove(&50));
+        assert_eq!(sorted(&tree), [20, 35, 40, 60, 70, 80]);
+        assert_eq!(tree.len(), 6);
+        assert!(!tree.remove(&50));
+        assert!(!tree.remove(&99));
+        assert_eq!(tree.len(), 6);
+    }
+
+    #[test]
+    fn removing_everything_in_random_order() {
+        let mut tree = Bst::new();
+        for value in shuffled(200, 1) {
+            tree.insert(value);
+        }
+        let order = shuffled(200, 2);
+        for (n, value) in order.iter().enumerate() {
+            assert!(tree.remove(value));
+            assert_eq!(tree.len(), 199 - n);
+            let left = sorted(&tree);
+            assert!(left.windows(2).all(|w| w[0] < w[1]));
+        }
+        assert!(tree.is_empty());
+        assert_eq!((tree.min(), tree.max(), tree.height()), (None, None, 0));
+    }
+
+    #[test]
+    fn sorted_inserts_make_a_degenerate_tree() {
+        let mut tree = Bst::new();
+        for value in 1..=2000 {
+            tree.insert(value);
+        }
+        assert_eq!(tree.height(), 2000);
+        assert_eq!(sorted(&tree), (1..=2000).collect::<Vec<_>>());
+        assert_eq!(sample().height(), 4);
+        assert_eq!(Bst::<i64>::new().height(), 0);
+        // dropping a 2000 deep tree is a loop, not 2000 nested calls
+        drop(tree);
+    }
+}
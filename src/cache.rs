@@ -0,0 +1,203 @@
+// one Cache trait so code that caches can leave the eviction policy to its caller
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::lru::LruCache;
+use crate::rw_cache::CacheMetrics;
+
+pub trait Cache<K, V> {
+    fn get(&mut self, k: &K) -> Option<&V>;
+    fn put(&mut self, k: K, v: V);
+    fn len(&self) -> usize;
+    fn clear(&mut self);
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// never throws anything out, so it grows with every new key
+pub struct UnboundedCache<K, V> {
+    values: HashMap<K, V>,
+}
+
+impl<K: Hash + Eq, V> UnboundedCache<K, V> {
+    pub fn new() -> UnboundedCache<K, V> {
+        UnboundedCache { values: HashMap::new() }
+    }
+}
+
+impl<K: Hash + Eq, V> Default for UnboundedCache<K, V> {
+    fn default() -> UnboundedCache<K, V> {
+        UnboundedCache::new()
+    }
+}
+
+impl<K: Hash + Eq, V> Cache<K, V> for UnboundedCache<K, V> {
+    fn get(&mut self, k: &K) -> Option<&V> {
+        self.values.get(k)
+    }
+
+    fn put(&mut self, k: K, v: V) {
+        self.values.insert(k, v);
+    }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+// the trait's put has nowhere to hand back an evicted value, so it is dropped
+impl<K: Hash + Eq + Clone, V> Cache<K, V> for LruCache<K, V> {
+    fn get(&mut self, k: &K) -> Option<&V> {
+        LruCache::get(self, k)
+    }
+
+    fn put(&mut self, k: K, v: V) {
+        LruCache::put(self, k, v);
+    }
+
+    fn len(&self) -> usize {
+        LruCache::len(self)
+    }
+
+    fn clear(&mut self) {
+        LruCache::clear(self);
+    }
+}
+
+// wraps any cache and counts its hits and misses, everything else is passed on.
+// the counts use rw_cache's CacheMetrics, so both caches report the same way
+pub struct Metered<C> {
+    inner: C,
+    hits: u64,
+    misses: u64,
+}
+
+impl<C> Metered<C> {
+    pub fn new(inner: C) -> Metered<C> {
+        Metered { inner, hits: 0, misses: 0 }
+    }
+
+    pub fn metrics(&self) -> CacheMetrics {
+        CacheMetrics { hits: self.hits, misses: self.misses }
+    }
+
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+// clear empties the cache but keeps the counts
+impl<K, V, C: Cache<K, V>> Cache<K, V> for Metered<C> {
+    fn get(&mut self, k: &K) -> Option<&V> {
+        let found = self.inner.get(k);
+        if found.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        found
+    }
+
+    fn put(&mut self, k: K, v: V) {
+        self.inner.put(k, v);
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // what every cache has to do, as long as it can hold three entries
+    fn behaves_like_a_cache<C: Cache<String, i32>>(mut cache: C) {
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&"a".to_string()), None);
+        cache.put("a".to_string(), 1);
+        cache.put("b".to_string(), 2);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&"a".to_string()), Some(&1));
+        assert_eq!(cache.get(&"b".to_string()), Some(&2));
+        // putting an existing key replaces the value
+        cache.put("a".to_string(), 10);
+        assert_eq!(cache.get(&"a".to_string()), Some(&10));
+        assert_eq!(cache.len(), 2);
+        cache.put("c".to_string(), 3);
+        assert_eq!(cache.len(), 3);
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&"c".to_string()), None);
+        // still usable after a clear
+        cache.put("d".to_string(), 4);
+        assert_eq!(cache.get(&"d".to_string()), Some(&4));
+    }
+
+    #[test]
+    fn the_unbounded_cache_passes_the_suite() {
+        behaves_like_a_cache(UnboundedCache::new());
+    }
+
+    #[test]
+    fn the_lru_cache_passes_the_suite() {
+        behaves_like_a_cache(LruCache::new(3).unwrap());
+    }
+
+    #[test]
+    fn a_metered_cache_passes_the_suite() {
+        behaves_like_a_cache(Metered::new(UnboundedCache::new()));
+        behaves_like_a_cache(Metered::new(LruCache::new(3).unwrap()));
+    }
+
+    #[test]
+    fn only_the_lru_cache_throws_entries_out() {
+        let mut lru = LruCache::new(2).unwrap();
+        let mut unbounded = UnboundedCache::new();
+        for n in 0..5 {
+            Cache::put(&mut lru, n, n * n);
+            unbounded.put(n, n * n);
+        }
+        assert_eq!(Cache::len(&lru), 2);
+        assert_eq!(unbounded.len(), 5);
+        assert_eq!(Cache::get(&mut lru, &0), None);
+        assert_eq!(Cache::get(&mut lru, &4), Some(&16));
+        assert_eq!(unbounded.get(&0), Some(&0));
+    }
+
+    #[test]
+    fn metered_counts_hits_and_misses_and_passes_everything_on() {
+        let mut cache = Metered::new(LruCache::new(2).unwrap());
+        assert_eq!(cache.metrics(), CacheMetrics::default());
+        cache.put(1, "one");
+        cache.put(2, "two");
+        assert_eq!(cache.get(&1), Some(&"one"));
+        assert_eq!(cache.get(&3), None);
+        // 1 was used last, so 2 goes when 3 comes in
+        cache.put(3, "three");
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get(&3), Some(&"three"));
+        assert_eq!(cache.metrics(), CacheMetrics { hits: 2, misses: 2 });
+        assert_eq!(cache.len(), 2);
+
+        // puts and clears don't count, and clear keeps the counts
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.metrics().total(), 4);
+
+        let inner = cache.into_inner();
+        assert_eq!(inner.capacity(), 2);
+        assert!(inner.is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+// a cancellation flag that can be handed to any number of threads.
+// loops check it between steps, sleepers get woken the moment it is set
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::time::Duration;
+
+struct Inner {
+    cancelled: AtomicBool,
+    // the condvar needs a mutex anyway, so it guards the list of children
+    children: Mutex<Vec<Weak<Inner>>>,
+    wake: Condvar,
+}
+
+// clones share one flag, cancelling any of them cancels them all
+#[derive(Clone)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        CancellationToken {
+            inner: Arc::new(Inner {
+                cancelled: AtomicBool::new(false),
+                children: Mutex::new(Vec::new()),
+                wake: Condvar::new(),
+            }),
+        }
+    }
+
+    // cancelling twice does nothing the second time
+    pub fn cancel(&self) {
+        let children = {
+            // setting the flag under the lock means a sleeper can't miss the wakeup
+            let mut children = self.inner.children.lock().unwrap();
+            if self.inner.cancelled.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            std::mem::take(&mut *children)
+        };
+        self.inner.wake.notify_all();
+        for child in children.iter().filter_map(Weak::upgrade) {
+            CancellationToken { inner: child }.cancel();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    // sleeps for `d` unless cancelled first, true means it was cancelled
+    pub fn cancelled_or_sleep(&self, d: Duration) -> bool {
+        let guard = self.inner.children.lock().unwrap();
+        // wait_timeout_while also copes with spurious wakeups
+        let (_guard, _) = self
+            .inner
+            .wake
+            .wait_timeout_while(guard, d, |_| !self.is_cancelled())
+            .unwrap();
+        self.is_cancelled()
+    }
+
+    // a token that is cancelled along with this one but can also be
+    // cancelled on its own without touching the parent
+    pub fn child(&self) -> CancellationToken {
+        let child = CancellationToken::new();
+        let mut children = self.inner.children.lock().unwrap();
+        if self.is_cancelled() {
+            child.inner.cancelled.store(true, Ordering::SeqCst);
+        } else {
+            // children that were already dropped don't need to be told
+            children.retain(|weak| weak.strong_count() > 0);
+            children.push(Arc::downgrade(&child.inner));
+        }
+        child
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        CancellationToken::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+    use std::thread;
+    use std::time::Instant;
+
+    #[test]
+    fn a_sleeping_worker_wakes_on_cancel() {
+        let token = CancellationToken::new();
+        let (started, wait_for_start) = channel();
+        let worker = {
+            let token = token.clone();
+            thread::spawn(move || {
+                let mut rounds = 0;
+                started.send(()).unwrap();
+                while !token.cancelled_or_sleep(Duration::from_secs(10)) {
+                    rounds += 1;
+                }
+                rounds
+            })
+        };
+        wait_for_start.recv().unwrap();
+        thread::sleep(Duration::from_millis(20));
+        let cancelled_at = Instant::now();
+        token.cancel();
+        assert_eq!(worker.join().unwrap(), 0);
+        // about 50 ms is the goal, the slack is for a busy test machine
+        let took = cancelled_at.elapsed();
+        assert!(took < Duration::from_millis(250), "took {:?}", took);
+    }
+
+    #[test]
+    fn an_uncancelled_sleep_runs_its_full_time() {
+        let token = CancellationToken::new();
+        let start = Instant::now();
+        assert!(!token.cancelled_or_sleep(Duration::from_millis(30)));
+        assert!(start.elapsed() >= Duration::from_millis(30));
+        token.cancel();
+        // already cancelled, no sleep at all
+        let start = Instant::now();
+        assert!(token.cancelled_or_sleep(Duration::from_secs(10)));
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn children_follow_the_parent_but_not_the_other_way() {
+        let parent = CancellationToken::new();
+        let child = parent.child();
+        let grandchild = child.child();
+        let sibling = parent.child();
+        sibling.cancel();
+        assert!(sibling.is_cancelled());
+        assert!(!parent.is_cancelled() && !child.is_cancelled());
+        parent.cancel();
+        assert!(child.is_cancelled());
+        assert!(grandchild.is_cancelled());
+        // a child made after the cancel starts out cancelled
+        assert!(parent.child().is_cancelled());
+    }
+
+    #[test]
+    fn a_child_sleeper_is_woken_by_the_parent() {
+        let parent = CancellationToken::new();
+        let child = parent.child();
+        let worker = thread::spawn(move || child.cancelled_or_sleep(Duration::from_secs(10)));
+        thread::sleep(Duration::from_millis(20));
+        parent.cancel();
+        assert!(worker.join().unwrap());
+    }
+
+    #[test]
+    fn cancel_is_idempotent_and_shared_by_clones() {
+        let token = CancellationToken::default();
+        let clone = token.clone();
+        let child = token.child();
+        assert!(!clone.is_cancelled());
+        clone.cancel();
+        clone.cancel();
+        token.cancel();
+        assert!(token.is_cancelled() && clone.is_cancelled() && child.is_cancelled());
+    }
+
+    #[test]
+    fn dropped_children_are_forgotten() {
+        let parent = CancellationToken::new();
+        for _ in 0..100 {
+            drop(parent.child());
+        }
+        let kept = parent.child();
+        assert_eq!(parent.inner.children.lock().unwrap().len(), 1);
+        parent.cancel();
+        assert!(kept.is_cancelled());
+    }
+}
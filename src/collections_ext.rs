@@ -0,0 +1,552 @@
+// a Stack and a Queue with just the operations their names promise,
+// and two small problems each one is good at
+
+use std::cmp::Reverse;
+use std::collections::{vec_deque, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::iter::Rev;
+use std::slice;
+use std::vec;
+
+// last in, first out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stack<T> {
+    items: Vec<T>,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Stack<T> {
+        Stack { items: Vec::new() }
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.items.push(item);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.items.pop()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.items.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    // top to bottom, the order pop would give
+    pub fn iter(&self) -> Rev<slice::Iter<'_, T>> {
+        self.items.iter().rev()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Stack<T> {
+        Stack::new()
+    }
+}
+
+// pushed in order, so the last item ends up on top
+impl<T> FromIterator<T> for Stack<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Stack<T> {
+        Stack { items: iter.into_iter().collect() }
+    }
+}
+
+impl<T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.items.extend(iter);
+    }
+}
+
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = Rev<vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter().rev()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = Rev<slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// first in, first out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Queue<T> {
+    items: VecDeque<T>,
+}
+
+impl<T> Queue<T> {
+    pub fn new() -> Queue<T> {
+        Queue { items: VecDeque::new() }
+    }
+
+    pub fn enqueue(&mut self, item: T) {
+        self.items.push_back(item);
+    }
+
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.items.pop_front()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.items.front()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    // front to back, the order dequeue would give
+    pub fn iter(&self) -> vec_deque::Iter<'_, T> {
+        self.items.iter()
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Queue<T> {
+        Queue::new()
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Queue<T> {
+        Queue { items: iter.into_iter().collect() }
+    }
+}
+
+impl<T> Extend<T> for Queue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.items.extend(iter);
+    }
+}
+
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = vec_deque::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = vec_deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// (), [] and {} must close in the reverse order they opened,
+// anything that is not a bracket is ignored
+pub fn is_balanced(text: &str) -> bool {
+    let mut open = Stack::new();
+    for c in text.chars() {
+        match c {
+            '(' | '[' | '{' => open.push(c),
+            ')' | ']' | '}' => {
+                let expected = match c {
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '{',
+                };
+                if open.pop() != Some(expected) {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+    }
+    open.is_empty()
+}
+
+// the potato is passed k times, then whoever holds it is out.
+// returns the last one left, None when nobody played
+pub fn hot_potato<'a>(names: &[&'a str], k: usize) -> Option<&'a str> {
+    let mut circle: Queue<&str> = names.iter().copied().collect();
+    while circle.len() > 1 {
+        for _ in 0..k {
+            let holder = circle.dequeue().unwrap();
+            circle.enqueue(holder);
+        }
+        circle.dequeue();
+    }
+    circle.dequeue()
+}
+
+// puts every item back to its Default, the slice keeps its length
+pub fn reset_all<T: Default>(items: &mut [T]) {
+    for item in items {
+        *item = T::default();
+    }
+}
+
+// the first copy of every item, in the order they first appeared
+pub fn dedup_preserving_order<T: Eq + Hash + Clone>(items: &[T]) -> Vec<T> {
+    let mut seen = HashSet::new();
+    items.iter().filter(|item| seen.insert(*item)).cloned().collect()
+}
+
+// keeps the first item for each key, later items with the same key are dropped
+pub fn unique_by<T, K: Eq + Hash, F: Fn(&T) -> K>(items: Vec<T>, key: F) -> Vec<T> {
+    let mut seen = HashSet::new();
+    items.into_iter().filter(|item| seen.insert(key(item))).collect()
+}
+
+// items that show up more than once and how often, in the order each
+// was first seen
+pub fn duplicates<T: Eq + Hash + Clone>(items: &[T]) -> Vec<(T, usize)> {
+    let mut counts: HashMap<&T, usize> = HashMap::new();
+    for item in items {
+        *counts.entry(item).or_insert(0) += 1;
+    }
+    dedup_preserving_order(items)
+        .into_iter()
+        .filter_map(|item| match counts[&item] {
+            1 => None,
+            count => Some((item, count)),
+        })
+        .collect()
+}
+
+// every item filed under its key, the items of a key keep their order
+pub fn group_by<T, K: Eq + Hash, F: Fn(&T) -> K>(
+    items: impl IntoIterator<Item = T>,
+    key: F,
+) -> HashMap<K, Vec<T>> {
+    let mut groups: HashMap<K, Vec<T>> = HashMap::new();
+    for item in items {
+        groups.entry(key(&item)).or_default().push(item);
+    }
+    groups
+}
+
+// like group_by, but the groups come out in the order their keys were first seen
+pub fn group_by_ordered<T, K: Eq + Hash + Clone, F: Fn(&T) -> K>(
+    items: impl IntoIterator<Item = T>,
+    key: F,
+) -> Vec<(K, Vec<T>)> {
+    let mut groups: Vec<(K, Vec<T>)> = Vec::new();
+    // key -> position in `groups`
+    let mut index: HashMap<K, usize> = HashMap::new();
+    for item in items {
+        let k = key(&item);
+        match index.get(&k) {
+            Some(&i) => groups[i].1.push(item),
+            None => {
+                index.insert(k.clone(), groups.len());
+                groups.push((k, vec![item]));
+            }
+        }
+    }
+    groups
+}
+
+pub fn counts_by<T, K: Eq + Hash, F: Fn(&T) -> K>(
+    items: impl IntoIterator<Item = T>,
+    key: F,
+) -> HashMap<K, usize> {
+    let mut counts = HashMap::new();
+    for item in items {
+        *counts.entry(key(&item)).or_insert(0) += 1;
+    }
+    counts
+}
+
+// the k items with the highest counts, most frequent first and equal counts
+// in ascending order. keeps a heap of at most k entries instead of sorting
+// every count, so it is O(n log k)
+pub fn top_k_counts<T: Ord + Hash + Clone, C: Ord + Copy>(
+    counts: &HashMap<T, C>,
+    k: usize,
+) -> Vec<(T, C)> {
+    if k == 0 {
+        return Vec::new();
+    }
+    // a bigger (count, Reverse(item)) ranks higher, wrapping the whole thing
+    // in Reverse puts the lowest ranked entry on top of the heap
+    // k comes from the caller and can be far more than there are counts
+    let mut heap = BinaryHeap::with_capacity(k.min(counts.len()) + 1);
+    for (item, &count) in counts {
+        heap.push(Reverse((count, Reverse(item))));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+    // into_sorted_vec is ascending, which for Reverse means best first
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((count, Reverse(item)))| (item.clone(), count))
+        .collect()
+}
+
+pub fn top_k_frequent<T: Ord + Hash + Clone>(items: &[T], k: usize) -> Vec<(T, usize)> {
+    let mut counts = HashMap::new();
+    for item in items {
+        *counts.entry(item.clone()).or_insert(0) += 1;
+    }
+    top_k_counts(&counts, k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stack_is_last_in_first_out() {
+        let mut stack = Stack::new();
+        assert!(stack.is_empty());
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+        assert_eq!(stack.peek(), Some(&3));
+        assert_eq!(stack.len(), 3);
+        assert_eq!(stack.iter().copied().collect::<Vec<_>>(), [3, 2, 1]);
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), Some(2));
+        stack.push(4);
+        assert_eq!(stack.pop(), Some(4));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.peek(), None);
+    }
+
+    #[test]
+    fn queue_is_first_in_first_out() {
+        let mut queue = Queue::new();
+        queue.enqueue('a');
+        queue.enqueue('b');
+        queue.enqueue('c');
+        assert_eq!(queue.front(), Some(&'a'));
+        assert_eq!(queue.iter().collect::<String>(), "abc");
+        assert_eq!(queue.dequeue(), Some('a'));
+        queue.enqueue('d');
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.dequeue(), Some('b'));
+        assert_eq!(queue.dequeue(), Some('c'));
+        assert_eq!(queue.dequeue(), Some('d'));
+        assert_eq!(queue.dequeue(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn stack_collect_extend_and_into_iter() {
+        let mut stack: Stack<i32> = (1..=3).collect();
+        assert_eq!(stack.peek(), Some(&3));
+        stack.extend([4, 5]);
+        assert_eq!(stack.peek(), Some(&5));
+        let by_ref: Vec<i32> = (&stack).into_iter().copied().collect();
+        assert_eq!(by_ref, [5, 4, 3, 2, 1]);
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), [5, 4, 3, 2, 1]);
+        assert_eq!(Stack::<i32>::default(), Stack::new());
+    }
+
+    #[test]
+    fn queue_collect_extend_and_into_iter() {
+        let mut queue: Queue<i32> = (1..=3).collect();
+        assert_eq!(queue.front(), Some(&1));
+        queue.extend([4, 5]);
+        let by_ref: Vec<i32> = (&queue).into_iter().copied().collect();
+        assert_eq!(by_ref, [1, 2, 3, 4, 5]);
+        assert_eq!(queue.into_iter().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+        assert_eq!(Queue::<i32>::default(), Queue::new());
+    }
+
+    #[test]
+    fn brackets_nested_crossed_and_unmatched() {
+        for text in ["", "abc", "()", "([]{})", "{[()()]}", "fn f(x: [u8; 2]) { g(x[0]) }"] {
+            assert!(is_balanced(text), "{:?} should be balanced", text);
+        }
+        // crossed
+        for text in ["([)]", "{(})", "[{]}"] {
+            assert!(!is_balanced(text), "{:?} is crossed", text);
+        }
+        // unmatched
+        for text in ["(", ")", "(()", "())", "{[}", "]["] {
+            assert!(!is_balanced(text), "{:?} is unmatched", text);
+        }
+    }
+
+    #[test]
+    fn hot_potato_leaves_one_name() {
+        let names = ["Bill", "David", "Susan", "Jane", "Kent", "Brad"];
+        assert_eq!(hot_potato(&names, 7), Some("Susan"));
+        // with k = 0 the first in line is out every time, so the last one wins
+        assert_eq!(hot_potato(&names, 0), Some("Brad"));
+        assert_eq!(hot_potato(&["solo"], 3), Some("solo"));
+        assert_eq!(hot_potato(&[], 3), None);
+    }
+
+    #[test]
+    fn dedup_keeps_the_first_copy_in_order() {
+        assert_eq!(dedup_preserving_order(&[3, 1, 3, 2, 1, 4]), [3, 1, 2, 4]);
+        assert_eq!(dedup_preserving_order(&["b", "a", "b"]), ["b", "a"]);
+        assert!(dedup_preserving_order::<i32>(&[]).is_empty());
+    }
+
+    #[test]
+    fn unique_by_a_case_insensitive_email() {
+        let users = vec![
+            ("pawan", "Pawan@Example.com"),
+            ("ravi", "ravi@example.com"),
+            ("pawan2", "pawan@example.COM"),
+            ("asha", "asha@example.com"),
+            ("ravi2", "RAVI@example.com"),
+        ];
+        let kept = unique_by(users, |(_, email)| email.to_lowercase());
+        let names: Vec<&str> = kept.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["pawan", "ravi", "asha"]);
+    }
+
+    #[test]
+    fn all_unique_input() {
+        let items = [5, 4, 3, 2, 1];
+        assert_eq!(dedup_preserving_order(&items), items);
+        assert_eq!(unique_by(items.to_vec(), |n| *n), items);
+        assert!(duplicates(&items).is_empty());
+    }
+
+    #[test]
+    fn all_identical_input() {
+        let items = ["x"; 4];
+        assert_eq!(dedup_preserving_order(&items), ["x"]);
+        assert_eq!(unique_by(items.to_vec(), |s| s.len()), ["x"]);
+        assert_eq!(duplicates(&items), [("x", 4)]);
+    }
+
+    #[test]
+    fn duplicates_come_in_first_seen_order() {
+        let items = ["b", "a", "c", "a", "b", "b"];
+        assert_eq!(duplicates(&items), [("b", 3), ("a", 2)]);
+        assert!(duplicates::<u8>(&[]).is_empty());
+    }
+
+    #[test]
+    fn grouping_nothing() {
+        let empty: Vec<i32> = Vec::new();
+        assert!(group_by(empty.clone(), |n| n % 2).is_empty());
+        assert!(group_by_ordered(empty.clone(), |n| n % 2).is_empty());
+        assert!(counts_by(empty, |n| n % 2).is_empty());
+    }
+
+    #[test]
+    fn a_single_group_keeps_the_item_order() {
+        let groups = group_by(vec![5, 3, 9, 1], |_| "all");
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups["all"], [5, 3, 9, 1]);
+        assert_eq!(group_by_ordered(vec![5, 3, 9, 1], |_| 0), [(0, vec![5, 3, 9, 1])]);
+        assert_eq!(counts_by(vec![5, 3, 9, 1], |_| ()).get(&()), Some(&4));
+    }
+
+    #[test]
+    fn keys_from_borrowed_and_owned_items() {
+        let words = vec![String::from("apple"), String::from("avocado"), String::from("banana")];
+        // the items are &String here, and the key borrows from them
+        let by_ref = group_by(&words, |word| &word[..1]);
+        assert_eq!(by_ref["a"], [&words[0], &words[1]]);
+        assert_eq!(by_ref["b"], [&words[2]]);
+        // the items are moved in, so the key has to be owned
+        let owned = group_by(words.clone(), |word| word.chars().next().unwrap());
+        assert_eq!(owned[&'a'], ["apple", "avocado"]);
+        let lengths = counts_by(&words, |word| word.len());
+        assert_eq!((lengths[&5], lengths[&6], lengths[&7]), (1, 1, 1));
+    }
+
+    #[test]
+    fn ordered_groups_follow_the_first_occurrence() {
+        // 200 keys, far too many for a HashMap to keep them in this order by luck
+        let items: Vec<u32> = (0..1000).map(|n| (n * 7919) % 200).collect();
+        let groups = group_by_ordered(items.clone(), |n| *n);
+        let keys: Vec<u32> = groups.iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, dedup_preserving_order(&items));
+        assert!(groups.iter().all(|(_, values)| values.len() == 5));
+        let letters = group_by_ordered(["cat", "bee", "cow", "ant", "bat"], |w| w.as_bytes()[0]);
+        assert_eq!(
+            letters,
+            [(b'c', vec!["cat", "cow"]), (b'b', vec!["bee", "bat"]), (b'a', vec!["ant"])]
+        );
+    }
+
+    // count everything, sort everything, keep the first k
+    fn top_k_by_sorting(items: &[i64], k: usize) -> Vec<(i64, usize)> {
+        let mut counts: Vec<(i64, usize)> = counts_by(items, |n| **n).into_iter().collect();
+        counts.sort_by(|(a, ca), (b, cb)| cb.cmp(ca).then(a.cmp(b)));
+        counts.truncate(k);
+        counts
+    }
+
+    #[test]
+    fn top_k_matches_a_full_sort_on_seeded_data() {
+        let mut rng = crate::rng::Rng::new(159);
+        for round in 0..20 {
+            let len = rng.range(0, 400) as usize;
+            let spread = rng.range(1, 60);
+            let items: Vec<i64> = (0..len).map(|_| rng.range(0, spread)).collect();
+            for k in [1, 3, 10, 100] {
+                let got = top_k_frequent(&items, k);
+                assert_eq!(got, top_k_by_sorting(&items, k), "round {} k {}", round, k);
+            }
+        }
+    }
+
+    #[test]
+    fn top_k_ties_go_to_the_smaller_item() {
+        let items = ["pear", "fig", "apple", "fig", "pear", "apple", "kiwi"];
+        assert_eq!(top_k_frequent(&items, 2), [("apple", 2), ("fig", 2)]);
+        assert_eq!(top_k_frequent(&[3, 1, 2], 2), [(1, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn top_k_with_k_zero_and_k_past_the_distinct_count() {
+        let items = [1, 1, 1, 2, 2, 3];
+        assert!(top_k_frequent(&items, 0).is_empty());
+        assert_eq!(top_k_frequent(&items, 1), [(1, 3)]);
+        assert_eq!(top_k_frequent(&items, 3), [(1, 3), (2, 2), (3, 1)]);
+        assert_eq!(top_k_frequent(&items, 50), [(1, 3), (2, 2), (3, 1)]);
+        assert!(top_k_frequent::<i32>(&[], 5).is_empty());
+    }
+
+    #[test]
+    fn reset_all_puts_every_item_back_to_its_default() {
+        use crate::point::Point;
+
+        let mut points = vec![Point::new(3, 4), Point::default(), Point::new(-1, 9)];
+        points[1].x = 5;
+        reset_all(&mut points);
+        assert_eq!(points, [Point { x: 0, y: 0 }; 3]);
+
+        let mut words = vec![String::from("a"), String::from("bc")];
+        reset_all(&mut words[1..]);
+        assert_eq!(words, ["a", ""]);
+
+        let mut none: [i32; 0] = [];
+        reset_all(&mut none);
+    }
+
+    #[test]
+    fn a_huge_k_returns_every_key() {
+        let items = [1, 1, 2, 3, 3, 3];
+        let all = [(3, 3), (1, 2), (2, 1)];
+        assert_eq!(top_k_frequent(&items, usize::MAX), all);
+        assert_eq!(top_k_frequent(&items, 1 << 40), all);
+        assert!(top_k_frequent::<i32>(&[], usize::MAX).is_empty());
+    }
+}
@@ -0,0 +1,57 @@
+// the small key=value config that error_chain reads, e.g.
+// "file=rust.txt\nmultiplier=3"
+
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+use crate::str_ext::StrExt;
+
+#[derive(Debug)]
+pub enum ConfigError {
+    MissingKey(&'static str),
+    BadNumber { key: &'static str, source: ParseIntError },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::MissingKey(key) => write!(f, "missing key `{}`", key),
+            ConfigError::BadNumber { key, .. } => write!(f, "`{}` is not a number", key),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::MissingKey(_) => None,
+            ConfigError::BadNumber { source, .. } => Some(source),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub file: String,
+    pub multiplier: u32,
+}
+
+// unknown keys and lines without `=` are skipped, spaces around the `=` are fine
+pub fn parse_config(text: &str) -> Result<Config, ConfigError> {
+    let mut file = None;
+    let mut multiplier = None;
+    for line in text.lines() {
+        match line.split_once_trimmed('=') {
+            Some(("file", value)) => file = Some(value.to_string()),
+            Some(("multiplier", value)) => multiplier = Some(value),
+            _ => {}
+        }
+    }
+    let file = file.ok_or(ConfigError::MissingKey("file"))?;
+    let multiplier = multiplier
+        .ok_or(ConfigError::MissingKey("multiplier"))?
+        .parse()
+        .map_err(|source| ConfigError::BadNumber { key: "multiplier", source })?;
+    Ok(Config { file, multiplier })
+}
@@ -0,0 +1,166 @@
+// Container: put things in, take them out. which one comes out next is up
+// to the type, and Item is an associated type because each container
+// holds exactly one kind of thing (a Stack<i32> is never a Container of &str)
+
+use crate::collections_ext::{Queue, Stack};
+use crate::ring_buffer::RingBuffer;
+
+pub trait Container {
+    type Item;
+
+    fn put(&mut self, item: Self::Item);
+    fn take(&mut self) -> Option<Self::Item>;
+    fn size(&self) -> usize;
+}
+
+// take gives back the newest item, last in first out
+impl<T> Container for Stack<T> {
+    type Item = T;
+
+    fn put(&mut self, item: T) {
+        self.push(item);
+    }
+
+    fn take(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+// take gives back the oldest item, first in first out
+impl<T> Container for Queue<T> {
+    type Item = T;
+
+    fn put(&mut self, item: T) {
+        self.enqueue(item);
+    }
+
+    fn take(&mut self) -> Option<T> {
+        self.dequeue()
+    }
+
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+// take gives back the oldest item like a Queue, but a put into a full
+// buffer overwrites the oldest, so size never goes past the capacity
+impl<T> Container for RingBuffer<T> {
+    type Item = T;
+
+    fn put(&mut self, item: T) {
+        self.push(item);
+    }
+
+    fn take(&mut self) -> Option<T> {
+        self.pop_oldest()
+    }
+
+    fn size(&self) -> usize {
+        self.len()
+    }
+}
+
+// everything in the order take hands it out, the container is left empty
+pub fn drain_into<C: Container>(c: &mut C) -> Vec<C::Item> {
+    let mut items = Vec::with_capacity(c.size());
+    while let Some(item) = c.take() {
+        items.push(item);
+    }
+    items
+}
+
+// moves every item across and returns how many were moved.
+// B only has to hold the same Item, it can be any other container
+pub fn transfer<A: Container, B: Container<Item = A::Item>>(from: &mut A, to: &mut B) -> usize {
+    let mut moved = 0;
+    while let Some(item) = from.take() {
+        to.put(item);
+        moved += 1;
+    }
+    moved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled<C: Container<Item = i32>>(mut c: C, items: &[i32]) -> C {
+        for &item in items {
+            c.put(item);
+        }
+        c
+    }
+
+    #[test]
+    fn stack_to_queue_reverses_and_queue_to_stack_does_not() {
+        let mut stack = filled(Stack::new(), &[1, 2, 3, 4]);
+        let mut queue = Queue::new();
+        assert_eq!(transfer(&mut stack, &mut queue), 4);
+        assert_eq!(stack.size(), 0);
+        assert_eq!(queue.size(), 4);
+        // the stack handed out its newest first, the queue keeps that order
+        assert_eq!(drain_into(&mut queue), [4, 3, 2, 1]);
+
+        let mut queue = filled(Queue::new(), &[1, 2, 3, 4]);
+        let mut stack = Stack::new();
+        assert_eq!(transfer(&mut queue, &mut stack), 4);
+        // the queue handed out its oldest first, so the stack now has 1 at the bottom
+        assert_eq!(drain_into(&mut stack), [4, 3, 2, 1]);
+
+        let mut queue = filled(Queue::new(), &[1, 2, 3, 4]);
+        let mut other = Queue::new();
+        transfer(&mut queue, &mut other);
+        assert_eq!(drain_into(&mut other), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn drain_into_follows_each_containers_take_order() {
+        assert_eq!(drain_into(&mut filled(Stack::new(), &[1, 2, 3])), [3, 2, 1]);
+        assert_eq!(drain_into(&mut filled(Queue::new(), &[1, 2, 3])), [1, 2, 3]);
+        let ring = RingBuffer::with_capacity(3).unwrap();
+        assert_eq!(drain_into(&mut filled(ring, &[1, 2, 3])), [1, 2, 3]);
+        // empty containers drain to nothing
+        assert!(drain_into(&mut Stack::<i32>::new()).is_empty());
+        assert!(drain_into(&mut Queue::<i32>::new()).is_empty());
+    }
+
+    #[test]
+    fn a_full_ring_buffer_keeps_only_the_newest() {
+        let mut ring = filled(RingBuffer::with_capacity(3).unwrap(), &[1, 2, 3, 4, 5]);
+        assert_eq!(ring.size(), 3);
+        assert_eq!(drain_into(&mut ring), [3, 4, 5]);
+
+        // moving five items into it only leaves room for the last three
+        let mut queue = filled(Queue::new(), &[1, 2, 3, 4, 5]);
+        let mut ring = RingBuffer::with_capacity(3).unwrap();
+        assert_eq!(transfer(&mut queue, &mut ring), 5);
+        assert_eq!(ring.size(), 3);
+        assert_eq!(drain_into(&mut ring), [3, 4, 5]);
+    }
+
+    #[test]
+    fn size_goes_up_with_put_and_down_with_take() {
+        let mut stack = Stack::new();
+        let mut queue = Queue::new();
+        let mut ring = RingBuffer::with_capacity(2).unwrap();
+        for n in 1..=2 {
+            stack.put(n);
+            queue.put(n);
+            ring.put(n);
+            assert_eq!([stack.size(), queue.size(), ring.size()], [n as usize; 3]);
+        }
+        assert_eq!((stack.take(), queue.take(), ring.take()), (Some(2), Some(1), Some(1)));
+        assert_eq!((stack.size(), queue.size(), ring.size()), (1, 1, 1));
+        stack.take();
+        queue.take();
+        ring.take();
+        assert_eq!((stack.take(), queue.take(), ring.take()), (None, None, None));
+        assert_eq!((stack.size(), queue.size(), ring.size()), (0, 0, 0));
+        assert_eq!(transfer(&mut stack, &mut queue), 0);
+    }
+}
@@ -0,0 +1,101 @@
+// three ways for many threads to bump one shared count:
+// a Mutex<u64>, a single AtomicU64, and a set of atomics summed at the end
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+pub fn concurrent_count_mutex(threads: usize, increments: u64) -> u64 {
+    let total = Mutex::new(0u64);
+    thread::scope(|s| {
+        for _ in 0..threads {
+            s.spawn(|| {
+                for _ in 0..increments {
+                    *total.lock().unwrap() += 1;
+                }
+            });
+        }
+    });
+    total.into_inner().unwrap()
+}
+
+pub fn concurrent_count_atomic(threads: usize, increments: u64) -> u64 {
+    let total = AtomicU64::new(0);
+    thread::scope(|s| {
+        for _ in 0..threads {
+            s.spawn(|| {
+                for _ in 0..increments {
+                    total.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    total.into_inner()
+}
+
+// each shard gets its own cache line, otherwise neighbouring shards
+// still bounce the same line between cores (false sharing)
+#[repr(align(64))]
+struct Shard(AtomicU64);
+
+// thread i only touches shard i % shards, so with enough shards
+// no two threads fight over the same counter
+pub fn concurrent_count_sharded(threads: usize, increments: u64, shards: usize) -> u64 {
+    assert!(shards > 0, "need at least one shard");
+    let counters: Vec<Shard> = (0..shards).map(|_| Shard(AtomicU64::new(0))).collect();
+    thread::scope(|s| {
+        for i in 0..threads {
+            let shard = &counters[i % shards].0;
+            s.spawn(move || {
+                for _ in 0..increments {
+                    shard.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    counters.into_iter().map(|shard| shard.0.into_inner()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SETUPS: [(usize, u64); 5] = [(1, 1), (1, 10_000), (2, 5_000), (4, 2_500), (16, 1_000)];
+
+    #[test]
+    fn mutex_counts_every_increment() {
+        for (threads, increments) in SETUPS {
+            assert_eq!(concurrent_count_mutex(threads, increments), threads as u64 * increments);
+        }
+    }
+
+    #[test]
+    fn atomic_counts_every_increment() {
+        for (threads, increments) in SETUPS {
+            assert_eq!(concurrent_count_atomic(threads, increments), threads as u64 * increments);
+        }
+    }
+
+    #[test]
+    fn sharded_counts_every_increment() {
+        for (threads, increments) in SETUPS {
+            for shards in [1, 3, threads] {
+                let total = concurrent_count_sharded(threads, increments, shards);
+                assert_eq!(total, threads as u64 * increments, "{} shards", shards);
+            }
+        }
+    }
+
+    #[test]
+    fn more_shards_than_threads() {
+        assert_eq!(concurrent_count_sharded(2, 1_000, 64), 2_000);
+        assert_eq!(concurrent_count_sharded(1, 7, 16), 7);
+    }
+
+    #[test]
+    fn no_threads_or_no_increments_count_zero() {
+        assert_eq!(concurrent_count_mutex(0, 100), 0);
+        assert_eq!(concurrent_count_atomic(8, 0), 0);
+        assert_eq!(concurrent_count_sharded(0, 100, 4), 0);
+    }
+}
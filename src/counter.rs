@@ -0,0 +1,195 @@
+// counts of things, like Python's collections.Counter. only keys with a
+// count above zero are stored, a missing key just counts as 0
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, BitAnd, BitOr, Sub};
+
+use crate::collections_ext::top_k_counts;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T: Eq + Hash> {
+    counts: HashMap<T, i64>,
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Counter<T> {
+        Counter { counts: HashMap::new() }
+    }
+
+    // not called add: with the Add impl below, `counter.add(x)` would
+    // resolve to the operator and fail to compile
+    pub fn add_one(&mut self, item: T) {
+        *self.counts.entry(item).or_insert(0) += 1;
+    }
+
+    pub fn count(&self, item: &T) -> i64 {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    // number of different items
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    // sum of all the counts
+    pub fn total(&self) -> i64 {
+        self.counts.values().sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&T, i64)> {
+        self.counts.iter().map(|(item, count)| (item, *count))
+    }
+
+    // keeps the keys whose count is still above zero
+    fn from_counts(counts: impl Iterator<Item = (T, i64)>) -> Counter<T> {
+        Counter { counts: counts.filter(|(_, count)| *count > 0).collect() }
+    }
+}
+
+impl<T: Eq + Hash + Ord + Clone> Counter<T> {
+    // the n biggest counts, equal counts in ascending order of the item
+    pub fn most_common(&self, n: usize) -> Vec<(T, i64)> {
+        top_k_counts(&self.counts, n)
+    }
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Counter<T> {
+        Counter::new()
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Counter<T> {
+        let mut counter = Counter::new();
+        for item in iter {
+            counter.add_one(item);
+        }
+        counter
+    }
+}
+
+// the operators work on references, `&a + &b`, and leave both sides alone
+
+// counts are added up
+impl<T: Eq + Hash + Clone> Add for &Counter<T> {
+    type Output = Counter<T>;
+
+    fn add(self, other: &Counter<T>) -> Counter<T> {
+        let mut out = self.clone();
+        for (item, count) in &other.counts {
+            *out.counts.entry(item.clone()).or_insert(0) += count;
+        }
+        out
+    }
+}
+
+// counts are subtracted, anything that drops to zero or below is removed
+impl<T: Eq + Hash + Clone> Sub for &Counter<T> {
+    type Output = Counter<T>;
+
+    fn sub(self, other: &Counter<T>) -> Counter<T> {
+        let left = self.iter().map(|(item, count)| (item.clone(), count - other.count(item)));
+        Counter::from_counts(left)
+    }
+}
+
+// intersection: the smaller count, for keys in both
+impl<T: Eq + Hash + Clone> BitAnd for &Counter<T> {
+    type Output = Counter<T>;
+
+    fn bitand(self, other: &Counter<T>) -> Counter<T> {
+        let both = self.iter().map(|(item, count)| (item.clone(), count.min(other.count(item))));
+        Counter::from_counts(both)
+    }
+}
+
+// union: the bigger count, for keys in either
+impl<T: Eq + Hash + Clone> BitOr for &Counter<T> {
+    type Output = Counter<T>;
+
+    fn bitor(self, other: &Counter<T>) -> Counter<T> {
+        let mut out = self.clone();
+        for (item, count) in &other.counts {
+            let mine = out.counts.entry(item.clone()).or_insert(0);
+            *mine = (*mine).max(*count);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counter(text: &str) -> Counter<char> {
+        text.chars().collect()
+    }
+
+    fn sorted(counter: &Counter<char>) -> Vec<(char, i64)> {
+        let mut pairs: Vec<(char, i64)> = counter.iter().map(|(c, n)| (*c, n)).collect();
+        pairs.sort();
+        pairs
+    }
+
+    #[test]
+    fn a_missing_key_counts_zero() {
+        let c = counter("aab");
+        assert_eq!(c.count(&'a'), 2);
+        assert_eq!(c.count(&'b'), 1);
+        assert_eq!(c.count(&'z'), 0);
+        assert_eq!(Counter::<char>::new().count(&'a'), 0);
+        assert_eq!((c.len(), c.total()), (2, 3));
+        let mut c = c;
+        c.add_one('z');
+        assert_eq!(c.count(&'z'), 1);
+    }
+
+    #[test]
+    fn add_sums_counts() {
+        assert_eq!(sorted(&(&counter("aab") + &counter("abc"))), [('a', 3), ('b', 2), ('c', 1)]);
+        // disjoint
+        assert_eq!(sorted(&(&counter("aa") + &counter("b"))), [('a', 2), ('b', 1)]);
+        assert_eq!(&counter("ab") + &Counter::new(), counter("ab"));
+    }
+
+    #[test]
+    fn sub_stops_at_zero_and_drops_the_key() {
+        let diff = &counter("aaabbc") - &counter("abbbd");
+        assert_eq!(sorted(&diff), [('a', 2), ('c', 1)]);
+        assert_eq!(diff.count(&'b'), 0);
+        assert_eq!(diff.len(), 2);
+        // disjoint leaves the left side as it was
+        assert_eq!(&counter("ab") - &counter("xy"), counter("ab"));
+        assert!((&counter("ab") - &counter("aabb")).is_empty());
+    }
+
+    #[test]
+    fn bitand_keeps_the_smaller_count_of_shared_keys() {
+        let both = &counter("aaabbc") & &counter("abbbd");
+        assert_eq!(sorted(&both), [('a', 1), ('b', 2)]);
+        assert!((&counter("ab") & &counter("xy")).is_empty());
+    }
+
+    #[test]
+    fn bitor_keeps_the_bigger_count_of_every_key() {
+        let either = &counter("aaabbc") | &counter("abbbd");
+        assert_eq!(sorted(&either), [('a', 3), ('b', 3), ('c', 1), ('d', 1)]);
+        assert_eq!(sorted(&(&counter("a") | &counter("b"))), [('a', 1), ('b', 1)]);
+    }
+
+    #[test]
+    fn most_common_breaks_ties_by_item() {
+        let c = counter("abracadabra");
+        assert_eq!(c.most_common(3), [('a', 5), ('b', 2), ('r', 2)]);
+        assert_eq!(c.most_common(0), []);
+        assert_eq!(c.most_common(10).len(), 5);
+        assert_eq!(c.most_common(usize::MAX), c.most_common(5));
+        assert_eq!(c.most_common(usize::MAX - 1).len(), 5);
+    }
+}
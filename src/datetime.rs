@@ -0,0 +1,30 @@
+// date and time helpers built on chrono, used by pkt_manage and friends
+
+pub mod args;
+pub mod business_days;
+pub mod calendar;
+pub mod countdown;
+pub mod date_math;
+pub mod events;
+pub mod humanize;
+pub mod offsets;
+pub mod parse;
+pub mod presets;
+pub mod schedule;
+pub mod timelog;
+pub mod timestamp;
+pub mod zones;
+
+use chrono::NaiveDate;
+
+// 28 to 31, or None when the month is not 1..=12 or the year is outside
+// chrono's range. December never needs next January, which chrono's last
+// year doesn't have
+pub fn days_in_month(year: i32, month: u32) -> Option<u32> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    if month == 12 {
+        return Some(31);
+    }
+    let next = NaiveDate::from_ymd_opt(year, month + 1, 1)?;
+    Some((next - first).num_days() as u32)
+}
@@ -0,0 +1,272 @@
+// parsing the time related command line values of pkt_manage
+
+use std::fmt::{self, Write};
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+
+use crate::str_ext::StrExt;
+
+use super::date_math::add_months;
+use super::parse::DateParseError;
+use super::timestamp::TimestampError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeArgError {
+    BadOffset(String),
+    BadDateTime(String),
+    BadDate(DateParseError),
+    BadYearMonth(String),
+    BadTimestamp(String),
+    BadPreset(String),
+    BadNumber { flag: String, value: String },
+    MonthsOutOfRange(i32),
+    Timestamp(TimestampError),
+    BadFormat { format: String, specifiers: Vec<String> },
+    MissingValue(String),
+    UnknownFlag(String),
+}
+
+impl fmt::Display for TimeArgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TimeArgError::BadOffset(s) => {
+                write!(f, "`{}` is not a UTC offset, try +05:30, -0800 or Z", s)
+            }
+            TimeArgError::BadDateTime(s) => {
+                write!(f, "`{}` is not an RFC 3339 date, try 2025-12-31T00:00:00Z", s)
+            }
+            TimeArgError::BadDate(err) => write!(f, "{}", err),
+            TimeArgError::BadYearMonth(s) => write!(f, "`{}` is not a month, try 2025-03", s),
+            TimeArgError::BadTimestamp(s) => write!(f, "`{}` is not a whole number of seconds", s),
+            TimeArgError::Timestamp(err) => write!(f, "{}", err),
+            TimeArgError::BadNumber { flag, value } => {
+                write!(f, "{} expects a whole number, got `{}`", flag, value)
+            }
+            TimeArgError::MonthsOutOfRange(months) => {
+                write!(f, "{:+} months from today is past the dates chrono can hold", months)
+            }
+            TimeArgError::BadPreset(s) => write!(
+                f,
+                "unknown preset `{}`, pick iso, rfc2822, date-only, time-only or friendly",
+                s
+            ),
+            TimeArgError::BadFormat { format, specifiers } => {
+                write!(f, "bad format `{}`", format)?;
+                if !specifiers.is_empty() {
+                    write!(f, ", unknown specifiers: {}", specifiers.join(", "))?;
+                }
+                Ok(())
+            }
+            TimeArgError::MissingValue(flag) => write!(f, "{} needs a value", flag),
+            TimeArgError::UnknownFlag(flag) => write!(f, "unknown flag {}", flag),
+        }
+    }
+}
+
+impl std::error::Error for TimeArgError {}
+
+impl From<DateParseError> for TimeArgError {
+    fn from(err: DateParseError) -> Self {
+        TimeArgError::BadDate(err)
+    }
+}
+
+// accepts Z, +05:30, -08:00, +0530, 0530 (no sign means east) and +05
+pub fn parse_offset(s: &str) -> Result<FixedOffset, TimeArgError> {
+    let bad = || TimeArgError::BadOffset(s.to_string());
+    let trimmed = s.trim();
+    if trimmed.eq_ignore_ascii_case("z") {
+        return Ok(FixedOffset::east_opt(0).unwrap());
+    }
+
+    let (sign, rest) = match trimmed.as_bytes().first() {
+        Some(b'+') => (1, &trimmed[1..]),
+        Some(b'-') => (-1, &trimmed[1..]),
+        _ => (1, trimmed),
+    };
+    // only ascii digits and ':' can be valid, and checking first means
+    // split_at below always lands on a char boundary
+    if !rest.bytes().all(|b| b.is_ascii_digit() || b == b':') {
+        return Err(bad());
+    }
+    let (hours, minutes) = match (rest.len(), rest.split_once(':')) {
+        (_, Some((h, m))) => (h, m),
+        (4, None) => rest.split_at(2),
+        (2, None) => (rest, "00"),
+        _ => return Err(bad()),
+    };
+    if hours.len() != 2 || minutes.len() != 2 {
+        return Err(bad());
+    }
+    if hours.contains(':') || minutes.contains(':') {
+        return Err(bad());
+    }
+    let hours: i32 = hours.parse().map_err(|_| bad())?;
+    let minutes: i32 = minutes.parse().map_err(|_| bad())?;
+    if hours > 23 || minutes > 59 {
+        return Err(bad());
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(bad)
+}
+
+// --add-months takes any i32, most of which land outside chrono's years
+pub fn months_from(today: NaiveDate, months: i32) -> Result<NaiveDate, TimeArgError> {
+    add_months(today, months).ok_or(TimeArgError::MonthsOutOfRange(months))
+}
+
+pub fn parse_rfc3339(s: &str) -> Result<DateTime<Utc>, TimeArgError> {
+    DateTime::parse_from_rfc3339(s.trim())
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| TimeArgError::BadDateTime(s.to_string()))
+}
+
+impl From<TimestampError> for TimeArgError {
+    fn from(err: TimestampError) -> Self {
+        TimeArgError::Timestamp(err)
+    }
+}
+
+// "2025-03" -> (2025, 3)
+pub fn parse_year_month(s: &str) -> Result<(i32, u32), TimeArgError> {
+    let bad = || TimeArgError::BadYearMonth(s.to_string());
+    let (year, month) = s.split_once_trimmed('-').ok_or_else(bad)?;
+    let year = year.parse().map_err(|_| bad())?;
+    let month = month.parse().map_err(|_| bad())?;
+    if !(1..=12).contains(&month) {
+        return Err(bad());
+    }
+    Ok((year, month))
+}
+
+// finds every %-specifier chrono doesn't understand, like %Q or a lone %
+fn bad_specifiers(format: &str) -> Vec<String> {
+    let mut bad = Vec::new();
+    let mut rest = format;
+    while let Some(pos) = rest.find('%') {
+        rest = &rest[pos..];
+        // a specifier is % plus one to three chars: %d, %-d, %:z, %.3f, %::z
+        let ends: Vec<usize> = rest
+            .char_indices()
+            .map(|(i, c)| i + c.len_utf8())
+            .skip(1)
+            .take(3)
+            .collect();
+        let valid = ends
+            .iter()
+            .find(|&&end| StrftimeItems::new(&rest[..end]).all(|item| item != Item::Error));
+        let end = match valid {
+            Some(&end) => end,
+            None => {
+                let end = ends.first().copied().unwrap_or(rest.len());
+                bad.push(rest[..end].to_string());
+                end
+            }
+        };
+        rest = &rest[end..];
+    }
+    bad
+}
+
+// checks the format string by formatting a fixed instant with it
+pub fn validate_format(format: &str) -> Result<(), TimeArgError> {
+    let specifiers = bad_specifiers(format);
+    let sample: DateTime<FixedOffset> = FixedOffset::east_opt(0)
+        .unwrap()
+        .with_ymd_and_hms(2025, 3, 1, 10, 15, 0)
+        .unwrap();
+    let mut out = String::new();
+    if specifiers.is_empty() && write!(out, "{}", sample.format(format)).is_ok() {
+        return Ok(());
+    }
+    Err(TimeArgError::BadFormat { format: format.to_string(), specifiers })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn east(seconds: i32) -> FixedOffset {
+        FixedOffset::east_opt(seconds).unwrap()
+    }
+
+    #[test]
+    fn offsets_in_every_accepted_shape() {
+        assert_eq!(parse_offset("+05:30"), Ok(east(5 * 3600 + 1800)));
+        assert_eq!(parse_offset("-08:00"), Ok(east(-8 * 3600)));
+        assert_eq!(parse_offset("Z"), Ok(east(0)));
+        assert_eq!(parse_offset("z"), Ok(east(0)));
+        assert_eq!(parse_offset("0530"), Ok(east(5 * 3600 + 1800)));
+        assert_eq!(parse_offset("-0800"), Ok(east(-8 * 3600)));
+        assert_eq!(parse_offset("+05"), Ok(east(5 * 3600)));
+        assert_eq!(parse_offset(" +23:59 "), Ok(east(23 * 3600 + 59 * 60)));
+    }
+
+    #[test]
+    fn garbage_offsets_are_rejected() {
+        let garbage =
+            ["", "+", "5:30", "+5:30", "+05:3", "+24:00", "+05:60", "+ab:cd", "UTC", "+053"];
+        for bad in garbage {
+            let expected = Err(TimeArgError::BadOffset(bad.to_string()));
+            assert_eq!(parse_offset(bad), expected, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn non_ascii_offsets_are_an_error_not_a_panic() {
+        // four bytes, but byte 2 is in the middle of the é
+        for bad in ["aé5", "+aé5", "0é5", "-é12", "０５３０", "+05:3０"] {
+            let expected = Err(TimeArgError::BadOffset(bad.to_string()));
+            assert_eq!(parse_offset(bad), expected, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn a_fixed_instant_formats_and_parses_back() {
+        let instant = Utc.with_ymd_and_hms(2025, 3, 1, 10, 15, 0).unwrap();
+        let local = instant.with_timezone(&parse_offset("+05:30").unwrap());
+        let format = "%Y-%m-%d %H:%M:%S %z";
+        validate_format(format).unwrap();
+        let text = local.format(format).to_string();
+        assert_eq!(text, "2025-03-01 15:45:00 +0530");
+        let back = DateTime::parse_from_str(&text, format).unwrap();
+        assert_eq!(back.with_timezone(&Utc), instant);
+    }
+
+    #[test]
+    fn bad_specifiers_are_named() {
+        assert_eq!(
+            validate_format("%Y %Q %d %"),
+            Err(TimeArgError::BadFormat {
+                format: String::from("%Y %Q %d %"),
+                specifiers: vec![String::from("%Q"), String::from("%")],
+            })
+        );
+        for good in ["%-d/%m", "%:z", "%.3f", "%::z", "no specifiers", "100%%"] {
+            assert_eq!(validate_format(good), Ok(()), "{}", good);
+        }
+    }
+
+    #[test]
+    fn rfc3339_and_year_month() {
+        let expected = Utc.with_ymd_and_hms(2025, 12, 31, 0, 0, 0).unwrap();
+        assert_eq!(parse_rfc3339("2025-12-31T05:30:00+05:30"), Ok(expected));
+        assert!(parse_rfc3339("2025-12-31").is_err());
+        assert_eq!(parse_year_month("2025-03"), Ok((2025, 3)));
+        assert!(parse_year_month("2025-13").is_err());
+        assert!(parse_year_month("march").is_err());
+    }
+
+    #[test]
+    fn add_months_past_chrono_s_range_is_a_usage_error() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        assert_eq!(months_from(today, 2), Ok(NaiveDate::from_ymd_opt(2025, 5, 14).unwrap()));
+        for months in [i32::MAX, i32::MIN, 4_000_000, -4_000_000] {
+            assert_eq!(months_from(today, months), Err(TimeArgError::MonthsOutOfRange(months)));
+        }
+        assert_eq!(
+            TimeArgError::MonthsOutOfRange(i32::MAX).to_string(),
+            "+2147483647 months from today is past the dates chrono can hold"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+// counting working days: Monday to Friday, minus any listed holidays
+
+use std::collections::BTreeSet;
+
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Holidays(pub BTreeSet<NaiveDate>);
+
+impl Holidays {
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.0.contains(&date)
+    }
+}
+
+fn next_day(date: NaiveDate) -> NaiveDate {
+    date.checked_add_days(Days::new(1)).unwrap()
+}
+
+fn prev_day(date: NaiveDate) -> NaiveDate {
+    date.checked_sub_days(Days::new(1)).unwrap()
+}
+
+pub fn add_business_days(start: NaiveDate, days: i64) -> NaiveDate {
+    add_business_days_with(start, days, &Holidays::default())
+}
+
+// a start that is not a business day first rolls forward to the next one,
+// so adding 0 days to a Saturday gives Monday, and -1 gives Friday
+pub fn add_business_days_with(start: NaiveDate, days: i64, holidays: &Holidays) -> NaiveDate {
+    let mut date = start;
+    while !holidays.is_business_day(date) {
+        date = next_day(date);
+    }
+    let mut left = days.unsigned_abs();
+    while left > 0 {
+        date = if days > 0 { next_day(date) } else { prev_day(date) };
+        if holidays.is_business_day(date) {
+            left -= 1;
+        }
+    }
+    date
+}
+
+pub fn business_days_between(a: NaiveDate, b: NaiveDate) -> i64 {
+    business_days_between_with(a, b, &Holidays::default())
+}
+
+// business days in a..b (a included, b not), negative when b is before a
+// so that add_business_days(a, business_days_between(a, b)) == b
+// whenever both are business days
+pub fn business_days_between_with(a: NaiveDate, b: NaiveDate, holidays: &Holidays) -> i64 {
+    let (from, to, sign) = if a <= b { (a, b, 1) } else { (b, a, -1) };
+    let count = from
+        .iter_days()
+        .take_while(|d| *d < to)
+        .filter(|d| holidays.is_business_day(*d))
+        .count() as i64;
+    sign * count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn holidays(dates: &[NaiveDate]) -> Holidays {
+        Holidays(dates.iter().copied().collect())
+    }
+
+    // 2025-03-03 is a Monday, 03-08 and 03-09 the weekend after it
+
+    #[test]
+    fn spans_crossing_several_weekends() {
+        assert_eq!(add_business_days(date(2025, 3, 3), 4), date(2025, 3, 7));
+        assert_eq!(add_business_days(date(2025, 3, 3), 5), date(2025, 3, 10));
+        assert_eq!(add_business_days(date(2025, 3, 7), 11), date(2025, 3, 24));
+        assert_eq!(business_days_between(date(2025, 3, 3), date(2025, 3, 24)), 15);
+    }
+
+    #[test]
+    fn negative_spans_go_backwards() {
+        assert_eq!(add_business_days(date(2025, 3, 10), -1), date(2025, 3, 7));
+        assert_eq!(add_business_days(date(2025, 3, 24), -15), date(2025, 3, 3));
+        assert_eq!(business_days_between(date(2025, 3, 24), date(2025, 3, 3)), -15);
+    }
+
+    #[test]
+    fn a_monday_holiday_is_skipped() {
+        let h = holidays(&[date(2025, 3, 10)]);
+        assert_eq!(add_business_days_with(date(2025, 3, 7), 1, &h), date(2025, 3, 11));
+        assert_eq!(add_business_days_with(date(2025, 3, 11), -1, &h), date(2025, 3, 7));
+        assert_eq!(business_days_between_with(date(2025, 3, 7), date(2025, 3, 11), &h), 1);
+        // a start on the holiday rolls forward like a weekend does
+        assert_eq!(add_business_days_with(date(2025, 3, 8), 0, &h), date(2025, 3, 11));
+    }
+
+    #[test]
+    fn zero_days_from_a_weekday_and_a_weekend() {
+        assert_eq!(add_business_days(date(2025, 3, 5), 0), date(2025, 3, 5));
+        assert_eq!(add_business_days(date(2025, 3, 8), 0), date(2025, 3, 10));
+        assert_eq!(add_business_days(date(2025, 3, 9), 0), date(2025, 3, 10));
+        assert_eq!(add_business_days(date(2025, 3, 8), -1), date(2025, 3, 7));
+        assert_eq!(business_days_between(date(2025, 3, 5), date(2025, 3, 5)), 0);
+        assert_eq!(business_days_between(date(2025, 3, 8), date(2025, 3, 10)), 0);
+    }
+
+    #[test]
+    fn between_undoes_add_for_business_days() {
+        let h = holidays(&[date(2025, 3, 17), date(2025, 4, 1)]);
+        let start = date(2025, 3, 3);
+        for days in -30..=30 {
+            let end = add_business_days_with(start, days, &h);
+            assert_eq!(business_days_between_with(start, end, &h), days, "{} days", days);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+// a month grid like the `cal` command prints
+
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate};
+
+use super::days_in_month;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalendarError {
+    BadMonth(u32),
+    BadYear(i32),
+}
+
+impl fmt::Display for CalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalendarError::BadMonth(m) => write!(f, "month {} must be between 1 and 12", m),
+            CalendarError::BadYear(y) => write!(f, "year {} is out of range", y),
+        }
+    }
+}
+
+impl std::error::Error for CalendarError {}
+
+const MONTH_NAMES: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June", "July", "August", "September",
+    "October", "November", "December",
+];
+
+// every day takes three columns: a separator and two digits
+// `today` is marked by turning the separators around it into [ and ]
+//
+//      March 2025
+//  Su Mo Tu We Th Fr Sa
+//                     1
+//   2  3  4[ 5] 6  7  8
+pub fn render_month(
+    year: i32,
+    month: u32,
+    today: Option<NaiveDate>,
+) -> Result<String, CalendarError> {
+    if !(1..=12).contains(&month) {
+        return Err(CalendarError::BadMonth(month));
+    }
+    let first = NaiveDate::from_ymd_opt(year, month, 1).ok_or(CalendarError::BadYear(year))?;
+    let days = days_in_month(year, month).ok_or(CalendarError::BadYear(year))?;
+    let today = today
+        .filter(|d| d.year() == year && d.month() == month)
+        .map(|d| d.day());
+
+    let mut out = String::new();
+    let title = format!("{} {}", MONTH_NAMES[month as usize - 1], year);
+    out.push_str(format!("{:^21}", title).trim_end());
+    out.push('\n');
+    out.push_str(" Su Mo Tu We Th Fr Sa\n");
+
+    // None for the blanks before the 1st
+    let leading = first.weekday().num_days_from_sunday() as usize;
+    let cells: Vec<Option<u32>> = std::iter::repeat_n(None, leading)
+        .chain((1..=days).map(Some))
+        .collect();
+
+    for week in cells.chunks(7) {
+        let mut line = String::new();
+        let mut after_today = false;
+        for cell in week {
+            let is_today = cell.is_some() && *cell == today;
+            line.push(if is_today { '[' } else if after_today { ']' } else { ' ' });
+            match cell {
+                Some(day) => line.push_str(&format!("{:>2}", day)),
+                None => line.push_str("  "),
+            }
+            after_today = is_today;
+        }
+        if after_today {
+            line.push(']');
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn a_month_starting_on_sunday() {
+        assert_eq!(
+            render_month(2025, 6, None).unwrap(),
+            "      June 2025\n\
+             \x20Su Mo Tu We Th Fr Sa\n\
+             \x20 1  2  3  4  5  6  7\n\
+             \x20 8  9 10 11 12 13 14\n\
+             \x2015 16 17 18 19 20 21\n\
+             \x2022 23 24 25 26 27 28\n\
+             \x2029 30\n"
+        );
+    }
+
+    #[test]
+    fn a_month_starting_on_saturday() {
+        assert_eq!(
+            render_month(2025, 3, None).unwrap(),
+            "     March 2025\n\
+             \x20Su Mo Tu We Th Fr Sa\n\
+             \x20                   1\n\
+             \x20 2  3  4  5  6  7  8\n\
+             \x20 9 10 11 12 13 14 15\n\
+             \x2016 17 18 19 20 21 22\n\
+             \x2023 24 25 26 27 28 29\n\
+             \x2030 31\n"
+        );
+    }
+
+    #[test]
+    fn february_in_a_leap_year() {
+        assert_eq!(
+            render_month(2024, 2, None).unwrap(),
+            "    February 2024\n\
+             \x20Su Mo Tu We Th Fr Sa\n\
+             \x20             1  2  3\n\
+             \x20 4  5  6  7  8  9 10\n\
+             \x2011 12 13 14 15 16 17\n\
+             \x2018 19 20 21 22 23 24\n\
+             \x2025 26 27 28 29\n"
+        );
+    }
+
+    #[test]
+    fn today_is_in_brackets() {
+        let grid = render_month(2025, 3, Some(date(2025, 3, 5))).unwrap();
+        assert_eq!(grid.lines().nth(3), Some("  2  3  4[ 5] 6  7  8"));
+        // at the end of a week the ] closes the line
+        let grid = render_month(2025, 3, Some(date(2025, 3, 8))).unwrap();
+        assert_eq!(grid.lines().nth(3), Some("  2  3  4  5  6  7[ 8]"));
+        // and at the start the [ takes the leading space
+        let grid = render_month(2025, 3, Some(date(2025, 3, 9))).unwrap();
+        assert_eq!(grid.lines().nth(4), Some("[ 9]10 11 12 13 14 15"));
+    }
+
+    #[test]
+    fn today_in_another_month_is_not_marked() {
+        let grid = render_month(2025, 3, Some(date(2025, 4, 5))).unwrap();
+        assert!(!grid.contains('['));
+        assert_eq!(grid, render_month(2025, 3, None).unwrap());
+    }
+
+    #[test]
+    fn bad_months_and_years_are_errors() {
+        assert_eq!(render_month(2025, 0, None), Err(CalendarError::BadMonth(0)));
+        assert_eq!(render_month(2025, 13, None), Err(CalendarError::BadMonth(13)));
+        assert_eq!(render_month(i32::MAX, 1, None), Err(CalendarError::BadYear(i32::MAX)));
+    }
+}
@@ -0,0 +1,118 @@
+// how long until (or since) a target instant, split into whole units
+
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Countdown {
+    pub days: i64,
+    pub hours: i64,
+    pub minutes: i64,
+    pub seconds: i64,
+    pub is_past: bool,
+}
+
+// whole seconds only, and all integer math, so nothing drifts
+pub fn countdown_to(target: DateTime<Utc>, now: DateTime<Utc>) -> Countdown {
+    let diff = (target - now).num_seconds();
+    let total = diff.unsigned_abs() as i64;
+    Countdown {
+        days: total / 86_400,
+        hours: total % 86_400 / 3600,
+        minutes: total % 3600 / 60,
+        seconds: total % 60,
+        is_past: diff < 0,
+    }
+}
+
+fn unit(count: i64, name: &str) -> String {
+    if count == 1 {
+        format!("1 {}", name)
+    } else {
+        format!("{} {}s", count, name)
+    }
+}
+
+// "3 days, 4 hours, 12 minutes", zero parts are left out
+// a target in the past reads "2 hours ago", the same instant reads "now"
+pub fn format_countdown(countdown: &Countdown) -> String {
+    let parts: Vec<String> = [
+        (countdown.days, "day"),
+        (countdown.hours, "hour"),
+        (countdown.minutes, "minute"),
+        (countdown.seconds, "second"),
+    ]
+    .iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, name)| unit(*count, name))
+    .collect();
+
+    if parts.is_empty() {
+        String::from("now")
+    } else if countdown.is_past {
+        format!("{} ago", parts.join(", "))
+    } else {
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeDelta, TimeZone};
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, s).unwrap()
+    }
+
+    fn countdown(days: i64, hours: i64, minutes: i64, seconds: i64, is_past: bool) -> Countdown {
+        Countdown { days, hours, minutes, seconds, is_past }
+    }
+
+    #[test]
+    fn exactly_now() {
+        let now = at(2025, 6, 1, 12, 0, 0);
+        let c = countdown_to(now, now);
+        assert_eq!(c, countdown(0, 0, 0, 0, false));
+        assert_eq!(format_countdown(&c), "now");
+    }
+
+    #[test]
+    fn under_a_minute() {
+        let now = at(2025, 6, 1, 12, 0, 0);
+        let c = countdown_to(now + TimeDelta::seconds(42), now);
+        assert_eq!(c, countdown(0, 0, 0, 42, false));
+        assert_eq!(format_countdown(&c), "42 seconds");
+        assert_eq!(format_countdown(&countdown_to(now + TimeDelta::seconds(1), now)), "1 second");
+        // fractions of a second are dropped
+        let c = countdown_to(now + TimeDelta::milliseconds(999), now);
+        assert_eq!(format_countdown(&c), "now");
+    }
+
+    #[test]
+    fn zero_parts_are_left_out() {
+        let now = at(2025, 6, 1, 12, 0, 0);
+        let c = countdown_to(at(2025, 6, 4, 16, 12, 0), now);
+        assert_eq!(c, countdown(3, 4, 12, 0, false));
+        assert_eq!(format_countdown(&c), "3 days, 4 hours, 12 minutes");
+        let c = countdown_to(at(2025, 6, 2, 12, 0, 1), now);
+        assert_eq!(format_countdown(&c), "1 day, 1 second");
+    }
+
+    #[test]
+    fn multi_year_spans_count_real_days() {
+        // 2024 is a leap year, so two years from 2024-01-01 is 731 days
+        let c = countdown_to(at(2026, 1, 1, 0, 0, 0), at(2024, 1, 1, 0, 0, 0));
+        assert_eq!(c, countdown(731, 0, 0, 0, false));
+        // ten years with the leap days of 2028 and 2032
+        let c = countdown_to(at(2035, 3, 1, 1, 2, 3), at(2025, 3, 1, 0, 0, 0));
+        assert_eq!(c, countdown(3652, 1, 2, 3, false));
+    }
+
+    #[test]
+    fn a_past_target_reads_ago() {
+        let now = at(2025, 6, 1, 12, 0, 0);
+        let c = countdown_to(at(2025, 6, 1, 9, 59, 30), now);
+        assert_eq!(c, countdown(0, 2, 0, 30, true));
+        assert_eq!(format_countdown(&c), "2 hours, 30 seconds ago");
+    }
+}
@@ -0,0 +1,133 @@
+// calendar arithmetic on NaiveDate
+// a day that doesn't exist in the target month is clamped to its last day:
+// Jan 31 + 1 month = Feb 28 (29 in leap years), Feb 29 + 1 year = Feb 28
+// a result past the years chrono can hold is None
+
+use chrono::{Datelike, Days, NaiveDate};
+
+use super::days_in_month;
+
+pub fn add_months(date: NaiveDate, months: i32) -> Option<NaiveDate> {
+    // count months from year 0 so negative deltas just work. in i64, because
+    // any i32 delta on top of a year * 12 still fits there
+    let total = date.year() as i64 * 12 + date.month0() as i64 + months as i64;
+    let year = i32::try_from(total.div_euclid(12)).ok()?;
+    let month = total.rem_euclid(12) as u32 + 1;
+    let day = date.day().min(days_in_month(year, month)?);
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+pub fn add_years(date: NaiveDate, years: i32) -> Option<NaiveDate> {
+    add_months(date, years.checked_mul(12)?)
+}
+
+// never fails: the last day of a month that has a first day exists too
+pub fn end_of_month(date: NaiveDate) -> NaiveDate {
+    match days_in_month(date.year(), date.month()) {
+        Some(last) => date.with_day(last).unwrap_or(date),
+        None => date,
+    }
+}
+
+// the Monday (or Sunday) on or before `date`
+pub fn start_of_week(date: NaiveDate, week_starts_monday: bool) -> NaiveDate {
+    let back = if week_starts_monday {
+        date.weekday().num_days_from_monday()
+    } else {
+        date.weekday().num_days_from_sunday()
+    };
+    date - Days::new(back as u64)
+}
+
+// 1 for January to March, ..., 4 for October to December
+pub fn quarter(date: NaiveDate) -> u8 {
+    (date.month0() / 3 + 1) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn jan_31_plus_a_month_is_the_end_of_february() {
+        assert_eq!(add_months(date(2025, 1, 31), 1), Some(date(2025, 2, 28)));
+        assert_eq!(add_months(date(2024, 1, 31), 1), Some(date(2024, 2, 29)));
+        assert_eq!(add_months(date(2025, 1, 31), 2), Some(date(2025, 3, 31)));
+        assert_eq!(add_months(date(2025, 3, 31), 1), Some(date(2025, 4, 30)));
+    }
+
+    #[test]
+    fn feb_29_plus_a_year_is_feb_28() {
+        assert_eq!(add_years(date(2024, 2, 29), 1), Some(date(2025, 2, 28)));
+        assert_eq!(add_years(date(2024, 2, 29), 4), Some(date(2028, 2, 29)));
+        assert_eq!(add_years(date(2024, 2, 29), -1), Some(date(2023, 2, 28)));
+    }
+
+    #[test]
+    fn crossing_the_year_boundary() {
+        assert_eq!(add_months(date(2025, 11, 15), 3), Some(date(2026, 2, 15)));
+        assert_eq!(add_months(date(2025, 12, 31), 1), Some(date(2026, 1, 31)));
+        assert_eq!(add_months(date(2025, 1, 15), -1), Some(date(2024, 12, 15)));
+        assert_eq!(add_months(date(2025, 6, 1), 24), Some(date(2027, 6, 1)));
+    }
+
+    #[test]
+    fn negative_deltas_mirror_positive_ones() {
+        assert_eq!(add_months(date(2025, 3, 31), -1), Some(date(2025, 2, 28)));
+        assert_eq!(add_months(date(2024, 3, 31), -1), Some(date(2024, 2, 29)));
+        assert_eq!(add_months(date(2025, 5, 20), -17), Some(date(2023, 12, 20)));
+        let there_and_back = add_months(date(2025, 5, 20), 17).and_then(|d| add_months(d, -17));
+        assert_eq!(there_and_back, Some(date(2025, 5, 20)));
+        assert_eq!(add_months(date(2025, 5, 20), 0), Some(date(2025, 5, 20)));
+        // years before 1 work too
+        assert_eq!(add_months(date(1, 1, 1), -1), Some(date(0, 12, 1)));
+    }
+
+    #[test]
+    fn end_of_month_and_quarter() {
+        assert_eq!(end_of_month(date(2024, 2, 10)), date(2024, 2, 29));
+        assert_eq!(end_of_month(date(2025, 2, 28)), date(2025, 2, 28));
+        assert_eq!(end_of_month(date(2025, 12, 1)), date(2025, 12, 31));
+        let quarters: Vec<u8> = (1..=12).map(|m| quarter(date(2025, m, 1))).collect();
+        assert_eq!(quarters, [1, 1, 1, 2, 2, 2, 3, 3, 3, 4, 4, 4]);
+    }
+
+    #[test]
+    fn start_of_week_for_both_conventions() {
+        // 2025-03-02 is a Sunday, 2025-03-03 a Monday
+        let sunday = date(2025, 3, 2);
+        let monday = date(2025, 3, 3);
+        assert_eq!(start_of_week(sunday, false), sunday);
+        assert_eq!(start_of_week(monday, true), monday);
+        assert_eq!(start_of_week(sunday, true), date(2025, 2, 24));
+        assert_eq!(start_of_week(monday, false), sunday);
+        assert_eq!(start_of_week(date(2025, 3, 8), true), monday);
+        assert_eq!(start_of_week(date(2025, 3, 8), false), sunday);
+    }
+
+    #[test]
+    fn going_past_chrono_s_range_is_none() {
+        let today = date(2025, 3, 14);
+        assert_eq!(add_months(today, i32::MAX), None);
+        assert_eq!(add_months(today, i32::MIN), None);
+        assert_eq!(add_years(today, i32::MAX), None);
+        assert_eq!(add_years(today, i32::MAX / 12 + 1), None);
+        assert_eq!(add_months(NaiveDate::MAX, 1), None);
+        assert_eq!(add_months(NaiveDate::MIN, -1), None);
+        // right up to the edge is fine
+        assert_eq!(add_months(NaiveDate::MAX, 0), Some(NaiveDate::MAX));
+        assert_eq!(add_months(date(NaiveDate::MAX.year(), 10, 31), 2), Some(NaiveDate::MAX));
+    }
+
+    #[test]
+    fn end_of_month_at_the_ends_of_chrono_s_range() {
+        assert_eq!(end_of_month(NaiveDate::MAX), NaiveDate::MAX);
+        assert_eq!(end_of_month(date(NaiveDate::MAX.year(), 12, 1)), NaiveDate::MAX);
+        let first = NaiveDate::MIN;
+        assert_eq!(end_of_month(first), date(first.year(), 1, 31));
+    }
+}
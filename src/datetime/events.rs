@@ -0,0 +1,241 @@
+// calendar events: sorting, clash detection and the gaps between them
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::intervals::{gaps, Interval};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub title: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventError {
+    // end at or before start, zero length events are not allowed either
+    EndNotAfterStart { title: String },
+}
+
+impl fmt::Display for EventError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EventError::EndNotAfterStart { title } => {
+                write!(f, "event `{}` has to end after it starts", title)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EventError {}
+
+impl Event {
+    pub fn new(title: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Event, EventError> {
+        if end <= start {
+            return Err(EventError::EndNotAfterStart { title: title.to_string() });
+        }
+        Ok(Event { title: title.to_string(), start, end })
+    }
+
+    pub fn length(&self) -> TimeDelta {
+        self.end - self.start
+    }
+
+    // half-open ranges, so one ending at 10:00 and one starting at 10:00 don't clash
+    pub fn overlaps(&self, other: &Event) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+}
+
+// by start, then end, then title
+impl Ord for Event {
+    fn cmp(&self, other: &Event) -> Ordering {
+        self.start
+            .cmp(&other.start)
+            .then(self.end.cmp(&other.end))
+            .then_with(|| self.title.cmp(&other.title))
+    }
+}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Event) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// every overlapping pair as indexes into `events`, smaller index first
+// sorts by start and sweeps, keeping only the events that are still running
+pub fn find_conflicts(events: &[Event]) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..events.len()).collect();
+    order.sort_by(|&a, &b| events[a].cmp(&events[b]));
+
+    let mut active: Vec<usize> = Vec::new();
+    let mut pairs = Vec::new();
+    for i in order {
+        let event = &events[i];
+        active.retain(|&j| events[j].end > event.start);
+        for &j in &active {
+            pairs.push((i.min(j), i.max(j)));
+        }
+        active.push(i);
+    }
+    pairs.sort();
+    pairs
+}
+
+// the gaps of at least `min_len` inside window_start..window_end, worked out
+// by intervals::gaps on millisecond timestamps (anything finer is dropped).
+// Event's fields are public, so one built by hand can end before it starts:
+// such an event covers no time and is skipped
+pub fn free_slots(
+    events: &[Event],
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+    min_len: TimeDelta,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let window = Interval::new(window_start.timestamp_millis(), window_end.timestamp_millis());
+    let Ok(window) = window else {
+        return Vec::new();
+    };
+    let busy: Vec<Interval> = events
+        .iter()
+        .filter_map(|e| Interval::new(e.start.timestamp_millis(), e.end.timestamp_millis()).ok())
+        .collect();
+    // a negative min_len lets every gap through
+    let min_len = u64::try_from(min_len.num_milliseconds()).unwrap_or(0);
+    gaps(&busy, window)
+        .into_iter()
+        .filter(|gap| gap.len() >= min_len)
+        .map(|gap| (from_millis(gap.start()), from_millis(gap.end())))
+        .collect()
+}
+
+fn from_millis(millis: i64) -> DateTime<Utc> {
+    // the millis came from DateTimes, so they are always in range
+    DateTime::from_timestamp_millis(millis).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 14, hour, minute, 0).unwrap()
+    }
+
+    fn event(title: &str, start: (u32, u32), end: (u32, u32)) -> Event {
+        Event::new(title, at(start.0, start.1), at(end.0, end.1)).unwrap()
+    }
+
+    #[test]
+    fn new_rejects_end_not_after_start() {
+        assert!(Event::new("zero", at(9, 0), at(9, 0)).is_err());
+        assert!(Event::new("backwards", at(10, 0), at(9, 0)).is_err());
+    }
+
+    #[test]
+    fn conflicts_are_overlapping_pairs() {
+        let events = [
+            event("a", (9, 0), (10, 0)),
+            event("b", (9, 30), (11, 0)),
+            event("c", (10, 0), (10, 30)),
+            event("d", (12, 0), (13, 0)),
+        ];
+        assert_eq!(find_conflicts(&events), vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn free_slots_between_events() {
+        let events = [event("a", (9, 0), (10, 0)), event("b", (10, 15), (11, 0))];
+        let slots = free_slots(&events, at(8, 0), at(12, 0), TimeDelta::minutes(30));
+        assert_eq!(slots, vec![(at(8, 0), at(9, 0)), (at(11, 0), at(12, 0))]);
+    }
+
+    // the fields are public, a backwards event must not make free_slots panic
+    #[test]
+    fn free_slots_skips_an_event_that_ends_before_it_starts() {
+        let backwards = Event { title: String::from("bad"), start: at(11, 0), end: at(10, 0) };
+        let events = [event("a", (9, 0), (10, 0)), backwards];
+        let slots = free_slots(&events, at(9, 0), at(12, 0), TimeDelta::zero());
+        assert_eq!(slots, vec![(at(10, 0), at(12, 0))]);
+    }
+
+    #[test]
+    fn free_slots_with_a_backwards_window_is_empty() {
+        assert!(free_slots(&[], at(12, 0), at(9, 0), TimeDelta::zero()).is_empty());
+    }
+
+    #[test]
+    fn touching_events_do_not_conflict() {
+        let a = event("a", (9, 0), (10, 0));
+        let b = event("b", (10, 0), (11, 0));
+        assert!(!a.overlaps(&b) && !b.overlaps(&a));
+        assert!(find_conflicts(&[a, b]).is_empty());
+    }
+
+    #[test]
+    fn nested_events_conflict_both_ways() {
+        let outer = event("outer", (9, 0), (12, 0));
+        let inner = event("inner", (10, 0), (10, 30));
+        assert!(outer.overlaps(&inner) && inner.overlaps(&outer));
+        // a later inner event listed first still gives (smaller, larger) indexes
+        assert_eq!(find_conflicts(&[inner, outer]), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn the_sweep_matches_checking_every_pair() {
+        let mut rng = crate::rng::Rng::new(138);
+        for _ in 0..50 {
+            let events: Vec<Event> = (0..rng.range(0, 30))
+                .map(|i| {
+                    let start = at(8, 0) + TimeDelta::minutes(rng.range(0, 600));
+                    let end = start + TimeDelta::minutes(rng.range(1, 120));
+                    Event::new(&format!("e{}", i), start, end).unwrap()
+                })
+                .collect();
+            let mut expected = Vec::new();
+            for i in 0..events.len() {
+                for j in i + 1..events.len() {
+                    if events[i].overlaps(&events[j]) {
+                        expected.push((i, j));
+                    }
+                }
+            }
+            assert_eq!(find_conflicts(&events), expected);
+        }
+    }
+
+    #[test]
+    fn free_slots_at_the_window_edges() {
+        // events sticking out of both ends of the window leave only the middle
+        let events = [event("early", (7, 0), (9, 0)), event("late", (11, 0), (13, 0))];
+        let slots = free_slots(&events, at(8, 0), at(12, 0), TimeDelta::zero());
+        assert_eq!(slots, vec![(at(9, 0), at(11, 0))]);
+        // events exactly on the edges leave nothing at the edges
+        let events = [event("a", (8, 0), (9, 0)), event("b", (11, 0), (12, 0))];
+        let slots = free_slots(&events, at(8, 0), at(12, 0), TimeDelta::zero());
+        assert_eq!(slots, vec![(at(9, 0), at(11, 0))]);
+        // nothing booked is one big slot
+        let slots = free_slots(&[], at(8, 0), at(12, 0), TimeDelta::hours(4));
+        assert_eq!(slots, vec![(at(8, 0), at(12, 0))]);
+        // and a slot shorter than min_len is dropped
+        assert!(free_slots(&[], at(8, 0), at(12, 0), TimeDelta::hours(5)).is_empty());
+    }
+
+    #[test]
+    fn events_sort_by_start_then_end_then_title() {
+        let mut events = [
+            event("b", (9, 0), (10, 0)),
+            event("a", (9, 0), (10, 0)),
+            event("c", (9, 0), (9, 30)),
+            event("d", (8, 0), (11, 0)),
+        ];
+        events.sort();
+        let titles: Vec<&str> = events.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, ["d", "c", "a", "b"]);
+    }
+}
@@ -0,0 +1,122 @@
+// "3 hours ago" / "in 2 days": one unit only, the largest that fits
+
+use chrono::{DateTime, Datelike, Months, Utc};
+
+fn plural(count: i64, unit: &str) -> String {
+    if count == 1 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+// whole calendar months from `earlier` to `later`
+// adding a month keeps the day of month, clamped to the last day when the
+// month is shorter: Jan 31 + 1 month is Feb 28 (Feb 29 in a leap year), so
+// Jan 31 -> Feb 29 2024 already counts as 1 month
+fn whole_months(earlier: DateTime<Utc>, later: DateTime<Utc>) -> u32 {
+    let guess = (later.year() - earlier.year()) * 12 + later.month() as i32
+        - earlier.month() as i32;
+    let mut months = guess.max(0) as u32;
+    while months > 0 && earlier.checked_add_months(Months::new(months)).is_none_or(|d| d > later)
+    {
+        months -= 1;
+    }
+    months
+}
+
+// describes `from` as seen from `to`: earlier is "ago", later is "in"
+// under 10 seconds either way is "just now"
+pub fn humanize(from: DateTime<Utc>, to: DateTime<Utc>) -> String {
+    let (earlier, later) = if from <= to { (from, to) } else { (to, from) };
+    let seconds = (later - earlier).num_seconds();
+
+    let amount = if seconds < 10 {
+        return String::from("just now");
+    } else if seconds < 60 {
+        plural(seconds, "second")
+    } else if seconds < 3600 {
+        plural(seconds / 60, "minute")
+    } else if seconds < 86_400 {
+        plural(seconds / 3600, "hour")
+    } else if seconds < 7 * 86_400 {
+        plural(seconds / 86_400, "day")
+    } else {
+        match whole_months(earlier, later) {
+            0 => plural(seconds / (7 * 86_400), "week"),
+            months @ 1..=11 => plural(months as i64, "month"),
+            months => plural(months as i64 / 12, "year"),
+        }
+    };
+
+    if from <= to {
+        format!("{} ago", amount)
+    } else {
+        format!("in {}", amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeDelta, TimeZone};
+
+    fn day(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 12, 0, 0).unwrap()
+    }
+
+    // `from` that many seconds before a fixed `to`
+    fn ago(seconds: i64) -> String {
+        let to = day(2025, 6, 15);
+        humanize(to - TimeDelta::seconds(seconds), to)
+    }
+
+    #[test]
+    fn boundaries_between_units() {
+        assert_eq!(ago(0), "just now");
+        assert_eq!(ago(9), "just now");
+        assert_eq!(ago(10), "10 seconds ago");
+        assert_eq!(ago(59), "59 seconds ago");
+        assert_eq!(ago(60), "1 minute ago");
+        assert_eq!(ago(59 * 60), "59 minutes ago");
+        assert_eq!(ago(60 * 60), "1 hour ago");
+        assert_eq!(ago(23 * 3600), "23 hours ago");
+        assert_eq!(ago(24 * 3600), "1 day ago");
+        assert_eq!(ago(6 * 86_400), "6 days ago");
+        assert_eq!(ago(7 * 86_400), "1 week ago");
+        assert_eq!(ago(14 * 86_400), "2 weeks ago");
+    }
+
+    #[test]
+    fn only_the_largest_unit_is_shown() {
+        assert_eq!(ago(3600 + 5 * 60), "1 hour ago");
+        assert_eq!(ago(2 * 86_400 + 23 * 3600), "2 days ago");
+    }
+
+    #[test]
+    fn the_future_reads_in() {
+        let now = day(2025, 6, 15);
+        assert_eq!(humanize(now + TimeDelta::seconds(9), now), "just now");
+        assert_eq!(humanize(now + TimeDelta::hours(3), now), "in 3 hours");
+        assert_eq!(humanize(day(2025, 8, 15), now), "in 2 months");
+    }
+
+    #[test]
+    fn months_follow_the_calendar() {
+        // Jan 31 + 1 month is clamped to the end of February
+        assert_eq!(humanize(day(2024, 1, 31), day(2024, 2, 29)), "1 month ago");
+        assert_eq!(humanize(day(2025, 1, 31), day(2025, 2, 28)), "1 month ago");
+        // a day short of that is still weeks
+        assert_eq!(humanize(day(2024, 1, 31), day(2024, 2, 28)), "4 weeks ago");
+        // seen from the other end of leap February
+        assert_eq!(humanize(day(2024, 3, 31), day(2024, 2, 29)), "in 1 month");
+        assert_eq!(humanize(day(2024, 3, 28), day(2024, 2, 29)), "in 4 weeks");
+    }
+
+    #[test]
+    fn years_after_twelve_months() {
+        assert_eq!(humanize(day(2024, 6, 15), day(2025, 6, 14)), "11 months ago");
+        assert_eq!(humanize(day(2024, 6, 15), day(2025, 6, 15)), "1 year ago");
+        assert_eq!(humanize(day(2020, 2, 29), day(2025, 3, 1)), "5 years ago");
+    }
+}
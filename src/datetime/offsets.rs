@@ -0,0 +1,122 @@
+// the local offset from UTC, and looking up offset changes in a table
+
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, Offset, TimeZone};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OffsetReport {
+    // minutes east of UTC, negative west of it
+    pub offset_minutes: i32,
+    // true when January and July have different offsets this year
+    pub is_dst_possible: bool,
+}
+
+fn local_offset_minutes<Tz: TimeZone>(dt: &DateTime<Tz>) -> i32 {
+    dt.offset().fix().local_minus_utc() / 60
+}
+
+pub fn offset_report(now_local: DateTime<Local>) -> OffsetReport {
+    let year = now_local.year();
+    let january = Local.with_ymd_and_hms(year, 1, 1, 12, 0, 0).earliest();
+    let july = Local.with_ymd_and_hms(year, 7, 1, 12, 0, 0).earliest();
+    let is_dst_possible = match (january, july) {
+        (Some(jan), Some(jul)) => local_offset_minutes(&jan) != local_offset_minutes(&jul),
+        _ => false,
+    };
+    OffsetReport { offset_minutes: local_offset_minutes(&now_local), is_dst_possible }
+}
+
+// the first entry in the table that takes effect after `after`
+// each entry is (when it starts, offset in minutes from then on)
+pub fn next_offset_change(
+    tz_offsets: &[(NaiveDateTime, i32)],
+    after: NaiveDateTime,
+) -> Option<(NaiveDateTime, i32)> {
+    tz_offsets
+        .iter()
+        .filter(|(at, _)| *at > after)
+        .min_by_key(|(at, _)| *at)
+        .copied()
+}
+
+// 330 -> "+05:30", -210 -> "-03:30", 0 -> "+00:00"
+pub fn minutes_to_hhmm(minutes: i32) -> String {
+    let sign = if minutes < 0 { '-' } else { '+' };
+    let abs = minutes.unsigned_abs();
+    format!("{}{:02}:{:02}", sign, abs / 60, abs % 60)
+}
+
+// a well known zone that uses this offset, only for display
+pub fn offset_nickname(minutes: i32) -> Option<&'static str> {
+    match minutes {
+        0 => Some("UTC"),
+        60 => Some("CET"),
+        120 => Some("EET"),
+        180 => Some("MSK"),
+        330 => Some("IST"),
+        345 => Some("NPT"),
+        480 => Some("CST"),
+        540 => Some("JST"),
+        600 => Some("AEST"),
+        -180 => Some("BRT"),
+        -210 => Some("NST"),
+        -240 => Some("EDT"),
+        -300 => Some("EST"),
+        -360 => Some("CST"),
+        -420 => Some("MST"),
+        -480 => Some("PST"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(y: i32, m: u32, d: u32, h: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap().and_hms_opt(h, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn offsets_as_hhmm() {
+        assert_eq!(minutes_to_hhmm(330), "+05:30");
+        assert_eq!(minutes_to_hhmm(345), "+05:45");
+        assert_eq!(minutes_to_hhmm(-210), "-03:30");
+        assert_eq!(minutes_to_hhmm(-480), "-08:00");
+        assert_eq!(minutes_to_hhmm(0), "+00:00");
+        assert_eq!(minutes_to_hhmm(-45), "-00:45");
+        assert_eq!(minutes_to_hhmm(840), "+14:00");
+    }
+
+    #[test]
+    fn nicknames_for_known_offsets_only() {
+        assert_eq!(offset_nickname(330), Some("IST"));
+        assert_eq!(offset_nickname(345), Some("NPT"));
+        assert_eq!(offset_nickname(-210), Some("NST"));
+        assert_eq!(offset_nickname(0), Some("UTC"));
+        assert_eq!(offset_nickname(17), None);
+    }
+
+    // a made up zone: standard time -300, summer time -240 from March to November
+    fn table() -> Vec<(NaiveDateTime, i32)> {
+        vec![(at(2025, 11, 2, 6), -300), (at(2025, 3, 9, 7), -240), (at(2026, 3, 8, 7), -240)]
+    }
+
+    #[test]
+    fn the_next_change_before_between_and_after_the_table() {
+        let table = table();
+        assert_eq!(next_offset_change(&table, at(2025, 1, 1, 0)), Some((at(2025, 3, 9, 7), -240)));
+        assert_eq!(next_offset_change(&table, at(2025, 6, 1, 0)), Some((at(2025, 11, 2, 6), -300)));
+        assert_eq!(next_offset_change(&table, at(2027, 1, 1, 0)), None);
+        // exactly on a change means that change already happened
+        assert_eq!(next_offset_change(&table, at(2025, 3, 9, 7)), Some((at(2025, 11, 2, 6), -300)));
+        assert_eq!(next_offset_change(&[], at(2025, 1, 1, 0)), None);
+    }
+
+    #[test]
+    fn the_report_uses_the_given_instant_offset() {
+        let now = Local::now();
+        let report = offset_report(now);
+        assert_eq!(report.offset_minutes, now.offset().fix().local_minus_utc() / 60);
+    }
+}
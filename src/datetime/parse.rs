@@ -0,0 +1,177 @@
+// parsing dates written in a few common ways, and the span between two of them
+
+use std::fmt;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateFormat {
+    Rfc3339,
+    DateTime,
+    Date,
+    DayMonthYear,
+}
+
+impl DateFormat {
+    // the order the formats are tried in
+    pub const ALL: [DateFormat; 4] = [
+        DateFormat::Rfc3339,
+        DateFormat::DateTime,
+        DateFormat::Date,
+        DateFormat::DayMonthYear,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DateFormat::Rfc3339 => "RFC 3339",
+            DateFormat::DateTime => "%Y-%m-%d %H:%M:%S",
+            DateFormat::Date => "%Y-%m-%d",
+            DateFormat::DayMonthYear => "%d/%m/%Y",
+        }
+    }
+
+    // everything without an offset is taken as UTC, plain dates as midnight
+    fn parse(self, s: &str) -> Option<DateTime<Utc>> {
+        match self {
+            DateFormat::Rfc3339 => DateTime::parse_from_rfc3339(s).ok().map(|dt| dt.to_utc()),
+            DateFormat::DateTime => NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|dt| dt.and_utc()),
+            DateFormat::Date => NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc()),
+            DateFormat::DayMonthYear => NaiveDate::parse_from_str(s, "%d/%m/%Y")
+                .ok()
+                .map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateParseError {
+    pub input: String,
+    pub tried: Vec<&'static str>,
+}
+
+impl fmt::Display for DateParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "could not parse `{}` as a date, tried: {}", self.input, self.tried.join(", "))
+    }
+}
+
+impl std::error::Error for DateParseError {}
+
+// like parse_datetime_flexible, but also says which format matched
+pub fn parse_datetime_with_format(s: &str) -> Result<(DateTime<Utc>, DateFormat), DateParseError> {
+    let s = s.trim();
+    for format in DateFormat::ALL {
+        if let Some(dt) = format.parse(s) {
+            return Ok((dt, format));
+        }
+    }
+    Err(DateParseError {
+        input: s.to_string(),
+        tried: DateFormat::ALL.iter().map(|f| f.label()).collect(),
+    })
+}
+
+pub fn parse_datetime_flexible(s: &str) -> Result<DateTime<Utc>, DateParseError> {
+    parse_datetime_with_format(s).map(|(dt, _)| dt)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignedSpan {
+    pub days: i64,
+    pub hours: i64,
+    pub minutes: i64,
+    // true when b is before a
+    pub negative: bool,
+}
+
+impl fmt::Display for SignedSpan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.negative { "-" } else { "" };
+        write!(f, "{}{}d {}h {}m", sign, self.days, self.hours, self.minutes)
+    }
+}
+
+// the time from a to b in whole minutes, leftover seconds are dropped
+pub fn span_between(a: DateTime<Utc>, b: DateTime<Utc>) -> SignedSpan {
+    let minutes = (b - a).num_minutes();
+    let total = minutes.unsigned_abs() as i64;
+    SignedSpan {
+        days: total / 1440,
+        hours: total % 1440 / 60,
+        minutes: total % 60,
+        negative: minutes < 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, s).unwrap()
+    }
+
+    #[test]
+    fn each_format_is_recognised() {
+        let cases = [
+            ("2025-03-01T10:15:00+05:30", at(2025, 3, 1, 4, 45, 0), DateFormat::Rfc3339),
+            ("2025-03-01T10:15:00Z", at(2025, 3, 1, 10, 15, 0), DateFormat::Rfc3339),
+            ("2025-03-01 10:15:00", at(2025, 3, 1, 10, 15, 0), DateFormat::DateTime),
+            ("2025-03-01", at(2025, 3, 1, 0, 0, 0), DateFormat::Date),
+            ("01/03/2025", at(2025, 3, 1, 0, 0, 0), DateFormat::DayMonthYear),
+            ("  2025-03-01  ", at(2025, 3, 1, 0, 0, 0), DateFormat::Date),
+        ];
+        for (input, expected, format) in cases {
+            assert_eq!(parse_datetime_with_format(input), Ok((expected, format)), "{}", input);
+            assert_eq!(parse_datetime_flexible(input), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn ambiguous_looking_dates_are_day_first() {
+        // 03/04 is the 3rd of April, never March 4th
+        assert_eq!(parse_datetime_flexible("03/04/2025"), Ok(at(2025, 4, 3, 0, 0, 0)));
+        // a month of 13 doesn't get swapped around to make it fit
+        assert!(parse_datetime_flexible("12/13/2025").is_err());
+        // nor does a date that doesn't exist
+        assert!(parse_datetime_flexible("2025-02-29").is_err());
+        assert_eq!(parse_datetime_flexible("29/02/2024"), Ok(at(2024, 2, 29, 0, 0, 0)));
+    }
+
+    #[test]
+    fn an_unparseable_string_lists_every_format_tried() {
+        let err = parse_datetime_flexible(" next tuesday ").unwrap_err();
+        assert_eq!(err.input, "next tuesday");
+        assert_eq!(err.tried, ["RFC 3339", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d", "%d/%m/%Y"]);
+        assert_eq!(
+            err.to_string(),
+            "could not parse `next tuesday` as a date, tried: \
+             RFC 3339, %Y-%m-%d %H:%M:%S, %Y-%m-%d, %d/%m/%Y"
+        );
+    }
+
+    #[test]
+    fn spans_forwards_and_backwards() {
+        let a = at(2025, 3, 1, 0, 0, 0);
+        let b = at(2025, 3, 3, 5, 7, 59);
+        let forward = span_between(a, b);
+        assert_eq!(forward, SignedSpan { days: 2, hours: 5, minutes: 7, negative: false });
+        assert_eq!(forward.to_string(), "2d 5h 7m");
+        let backward = span_between(b, a);
+        assert_eq!(backward, SignedSpan { negative: true, ..forward });
+        assert_eq!(backward.to_string(), "-2d 5h 7m");
+    }
+
+    #[test]
+    fn under_a_minute_is_an_empty_span() {
+        let a = at(2025, 3, 1, 0, 0, 0);
+        let zero = SignedSpan { days: 0, hours: 0, minutes: 0, negative: false };
+        assert_eq!(span_between(a, a), zero);
+        assert_eq!(span_between(a + chrono::TimeDelta::seconds(59), a), zero);
+    }
+}
@@ -0,0 +1,128 @@
+// ready-made output formats for pkt_manage --format-preset
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, SecondsFormat, TimeZone};
+
+use super::args::TimeArgError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatPreset {
+    // 2025-03-01T10:15:00Z
+    Iso,
+    // Sat, 1 Mar 2025 10:15:00 +0000
+    Rfc2822,
+    // 2025-03-01
+    DateOnly,
+    // 10:15:00
+    TimeOnly,
+    // Saturday, 1 March 2025, 10:15 AM
+    Friendly,
+}
+
+impl FromStr for FormatPreset {
+    type Err = TimeArgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "iso" => Ok(FormatPreset::Iso),
+            "rfc2822" => Ok(FormatPreset::Rfc2822),
+            "date-only" => Ok(FormatPreset::DateOnly),
+            "time-only" => Ok(FormatPreset::TimeOnly),
+            "friendly" => Ok(FormatPreset::Friendly),
+            _ => Err(TimeArgError::BadPreset(s.to_string())),
+        }
+    }
+}
+
+impl FormatPreset {
+    pub fn format<Tz: TimeZone>(&self, dt: DateTime<Tz>) -> String
+    where
+        Tz::Offset: Display,
+    {
+        match self {
+            FormatPreset::Iso => dt.to_rfc3339_opts(SecondsFormat::Secs, true),
+            FormatPreset::Rfc2822 => dt.to_rfc2822(),
+            FormatPreset::DateOnly => dt.format("%Y-%m-%d").to_string(),
+            FormatPreset::TimeOnly => dt.format("%H:%M:%S").to_string(),
+            FormatPreset::Friendly => dt.format("%A, %-d %B %Y, %-I:%M %p").to_string(),
+        }
+    }
+}
+
+// the ISO 8601 week, like 2025-W09
+// the ISO year can differ from the calendar year: Dec 29-31 may be in
+// week 1 of the next year, and Jan 1-3 in week 52 or 53 of the previous one
+pub fn iso_week_label<Tz: TimeZone>(dt: &DateTime<Tz>) -> String {
+    let week = dt.iso_week();
+    format!("{}-W{:02}", week.year(), week.week())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+
+    fn utc(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn late_december_can_be_week_1_of_next_year() {
+        assert_eq!(iso_week_label(&utc(2024, 12, 29)), "2024-W52");
+        assert_eq!(iso_week_label(&utc(2024, 12, 30)), "2025-W01");
+        assert_eq!(iso_week_label(&utc(2024, 12, 31)), "2025-W01");
+        assert_eq!(iso_week_label(&utc(2025, 12, 29)), "2026-W01");
+        assert_eq!(iso_week_label(&utc(2019, 12, 31)), "2020-W01");
+    }
+
+    #[test]
+    fn early_january_can_be_week_52_or_53_of_last_year() {
+        assert_eq!(iso_week_label(&utc(2021, 1, 1)), "2020-W53");
+        assert_eq!(iso_week_label(&utc(2021, 1, 3)), "2020-W53");
+        assert_eq!(iso_week_label(&utc(2021, 1, 4)), "2021-W01");
+        assert_eq!(iso_week_label(&utc(2022, 1, 1)), "2021-W52");
+        assert_eq!(iso_week_label(&utc(2022, 1, 2)), "2021-W52");
+        assert_eq!(iso_week_label(&utc(2025, 1, 1)), "2025-W01");
+    }
+
+    // Sunday 23:30 UTC is already Monday, the next week, at +05:30
+    #[test]
+    fn the_offset_can_change_the_week() {
+        let dt = Utc.with_ymd_and_hms(2025, 3, 2, 23, 30, 0).unwrap();
+        let india = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+        assert_eq!(iso_week_label(&dt), "2025-W09");
+        assert_eq!(iso_week_label(&dt.with_timezone(&india)), "2025-W10");
+    }
+
+    #[test]
+    fn every_preset_for_a_fixed_instant() {
+        let dt = Utc.with_ymd_and_hms(2025, 3, 1, 10, 15, 0).unwrap();
+        let expected = [
+            ("iso", "2025-03-01T10:15:00Z"),
+            ("rfc2822", "Sat, 1 Mar 2025 10:15:00 +0000"),
+            ("date-only", "2025-03-01"),
+            ("time-only", "10:15:00"),
+            ("friendly", "Saturday, 1 March 2025, 10:15 AM"),
+        ];
+        for (name, output) in expected {
+            let preset: FormatPreset = name.parse().unwrap();
+            assert_eq!(preset.format(dt), output, "{}", name);
+        }
+    }
+
+    #[test]
+    fn presets_keep_the_offset() {
+        let offset = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+        let dt = Utc.with_ymd_and_hms(2025, 3, 1, 10, 15, 0).unwrap().with_timezone(&offset);
+        assert_eq!(FormatPreset::Iso.format(dt), "2025-03-01T15:45:00+05:30");
+        assert_eq!(FormatPreset::Friendly.format(dt), "Saturday, 1 March 2025, 3:45 PM");
+    }
+
+    #[test]
+    fn unknown_preset_is_an_error() {
+        let err = "fancy".parse::<FormatPreset>().unwrap_err();
+        assert!(matches!(err, TimeArgError::BadPreset(p) if p == "fancy"));
+    }
+}
@@ -0,0 +1,258 @@
+// recurring schedules and their next occurrences (all in UTC)
+
+use std::fmt;
+
+use chrono::{DateTime, Datelike, Days, Duration, NaiveDate, TimeZone, Utc, Weekday};
+
+use super::days_in_month;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schedule {
+    Daily { hour: u32, minute: u32 },
+    Weekly { weekday: Weekday, hour: u32, minute: u32 },
+    // a day past the end of a month (31 in April) fires on its last day instead
+    Monthly { day: u32, hour: u32, minute: u32 },
+    // lined up with the Unix epoch, so every 15 minutes fires at :00, :15, :30, :45
+    EveryNMinutes(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    BadTime { hour: u32, minute: u32 },
+    BadDay(u32),
+    ZeroInterval,
+    // the next occurrence would be past the last instant chrono can hold
+    OutOfRange,
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScheduleError::BadTime { hour, minute } => {
+                write!(f, "{:02}:{:02} is not a time of day", hour, minute)
+            }
+            ScheduleError::BadDay(day) => write!(f, "day {} must be between 1 and 31", day),
+            ScheduleError::ZeroInterval => write!(f, "the interval must be at least one minute"),
+            ScheduleError::OutOfRange => write!(f, "the next occurrence is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for ScheduleError {}
+
+fn at(date: NaiveDate, hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&date.and_hms_opt(hour, minute, 0).unwrap())
+}
+
+impl Schedule {
+    fn validate(&self) -> Result<(), ScheduleError> {
+        let (hour, minute) = match *self {
+            Schedule::Daily { hour, minute } => (hour, minute),
+            Schedule::Weekly { hour, minute, .. } => (hour, minute),
+            Schedule::Monthly { day, hour, minute } => {
+                if !(1..=31).contains(&day) {
+                    return Err(ScheduleError::BadDay(day));
+                }
+                (hour, minute)
+            }
+            Schedule::EveryNMinutes(0) => return Err(ScheduleError::ZeroInterval),
+            Schedule::EveryNMinutes(_) => return Ok(()),
+        };
+        if hour > 23 || minute > 59 {
+            return Err(ScheduleError::BadTime { hour, minute });
+        }
+        Ok(())
+    }
+
+    // the first occurrence strictly after `now`, so being exactly on an
+    // occurrence gives the one after it
+    pub fn next_after(&self, now: DateTime<Utc>) -> Result<DateTime<Utc>, ScheduleError> {
+        self.validate()?;
+        let later = |t: DateTime<Utc>, days| {
+            t.checked_add_signed(Duration::days(days)).ok_or(ScheduleError::OutOfRange)
+        };
+        let today = now.date_naive();
+        let next = match *self {
+            Schedule::Daily { hour, minute } => {
+                let candidate = at(today, hour, minute);
+                if candidate > now { candidate } else { later(candidate, 1)? }
+            }
+            Schedule::Weekly { weekday, hour, minute } => {
+                let ahead = (7 + weekday.num_days_from_monday()
+                    - today.weekday().num_days_from_monday())
+                    % 7;
+                let day = today
+                    .checked_add_days(Days::new(ahead as u64))
+                    .ok_or(ScheduleError::OutOfRange)?;
+                let candidate = at(day, hour, minute);
+                if candidate > now { candidate } else { later(candidate, 7)? }
+            }
+            Schedule::Monthly { day, hour, minute } => {
+                let (mut year, mut month) = (today.year(), today.month());
+                loop {
+                    // None once the year has gone past chrono's last one
+                    let last = days_in_month(year, month).ok_or(ScheduleError::OutOfRange)?;
+                    let date = NaiveDate::from_ymd_opt(year, month, day.min(last))
+                        .ok_or(ScheduleError::OutOfRange)?;
+                    let candidate = at(date, hour, minute);
+                    if candidate > now {
+                        break candidate;
+                    }
+                    if month == 12 {
+                        year += 1;
+                        month = 1;
+                    } else {
+                        month += 1;
+                    }
+                }
+            }
+            Schedule::EveryNMinutes(n) => {
+                let step = n as i64 * 60;
+                let next = (now.timestamp().div_euclid(step) + 1) * step;
+                Utc.timestamp_opt(next, 0).single().ok_or(ScheduleError::OutOfRange)?
+            }
+        };
+        Ok(next)
+    }
+
+    // the next `count` occurrences, empty if the schedule is invalid
+    pub fn upcoming(&self, now: DateTime<Utc>, count: usize) -> Vec<DateTime<Utc>> {
+        let mut out = Vec::with_capacity(count);
+        let mut current = now;
+        while out.len() < count {
+            match self.next_after(current) {
+                Ok(next) => {
+                    out.push(next);
+                    current = next;
+                }
+                Err(_) => break,
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn daily_fires_today_or_tomorrow() {
+        let s = Schedule::Daily { hour: 9, minute: 30 };
+        assert_eq!(s.next_after(utc(2025, 3, 1, 8, 0)), Ok(utc(2025, 3, 1, 9, 30)));
+        assert_eq!(s.next_after(utc(2025, 3, 1, 10, 0)), Ok(utc(2025, 3, 2, 9, 30)));
+        // exactly on it gives the next one
+        assert_eq!(s.next_after(utc(2025, 3, 1, 9, 30)), Ok(utc(2025, 3, 2, 9, 30)));
+    }
+
+    #[test]
+    fn weekly_on_the_target_day_after_the_time_waits_a_week() {
+        // 2025-03-03 is a Monday
+        let s = Schedule::Weekly { weekday: Weekday::Mon, hour: 9, minute: 0 };
+        assert_eq!(s.next_after(utc(2025, 3, 3, 8, 59)), Ok(utc(2025, 3, 3, 9, 0)));
+        assert_eq!(s.next_after(utc(2025, 3, 3, 9, 0)), Ok(utc(2025, 3, 10, 9, 0)));
+        assert_eq!(s.next_after(utc(2025, 3, 3, 17, 0)), Ok(utc(2025, 3, 10, 9, 0)));
+        let friday = Schedule::Weekly { weekday: Weekday::Fri, hour: 18, minute: 0 };
+        assert_eq!(friday.next_after(utc(2025, 3, 3, 17, 0)), Ok(utc(2025, 3, 7, 18, 0)));
+    }
+
+    #[test]
+    fn monthly_day_31_clamps_to_the_last_day() {
+        let s = Schedule::Monthly { day: 31, hour: 0, minute: 0 };
+        let dates = s.upcoming(utc(2025, 1, 31, 12, 0), 4);
+        assert_eq!(
+            dates,
+            [
+                utc(2025, 2, 28, 0, 0),
+                utc(2025, 3, 31, 0, 0),
+                utc(2025, 4, 30, 0, 0),
+                utc(2025, 5, 31, 0, 0),
+            ]
+        );
+        let leap = Schedule::Monthly { day: 30, hour: 6, minute: 0 };
+        assert_eq!(leap.next_after(utc(2024, 2, 1, 0, 0)), Ok(utc(2024, 2, 29, 6, 0)));
+    }
+
+    #[test]
+    fn monthly_rolls_over_the_year() {
+        let s = Schedule::Monthly { day: 15, hour: 12, minute: 0 };
+        assert_eq!(s.next_after(utc(2025, 12, 20, 0, 0)), Ok(utc(2026, 1, 15, 12, 0)));
+    }
+
+    #[test]
+    fn every_n_minutes_lines_up_with_the_clock() {
+        let s = Schedule::EveryNMinutes(15);
+        assert_eq!(s.next_after(utc(2025, 3, 1, 10, 7)), Ok(utc(2025, 3, 1, 10, 15)));
+        assert_eq!(s.next_after(utc(2025, 3, 1, 10, 15)), Ok(utc(2025, 3, 1, 10, 30)));
+        assert_eq!(
+            s.upcoming(utc(2025, 3, 1, 23, 50), 2),
+            [utc(2025, 3, 2, 0, 0), utc(2025, 3, 2, 0, 15)]
+        );
+    }
+
+    #[test]
+    fn bad_schedules_are_rejected() {
+        let now = utc(2025, 3, 1, 0, 0);
+        assert_eq!(Schedule::EveryNMinutes(0).next_after(now), Err(ScheduleError::ZeroInterval));
+        assert!(Schedule::EveryNMinutes(0).upcoming(now, 3).is_empty());
+        assert_eq!(
+            Schedule::Daily { hour: 24, minute: 0 }.next_after(now),
+            Err(ScheduleError::BadTime { hour: 24, minute: 0 })
+        );
+        assert_eq!(
+            Schedule::Monthly { day: 0, hour: 0, minute: 0 }.next_after(now),
+            Err(ScheduleError::BadDay(0))
+        );
+        assert_eq!(
+            Schedule::Monthly { day: 32, hour: 0, minute: 0 }.next_after(now),
+            Err(ScheduleError::BadDay(32))
+        );
+    }
+
+    #[test]
+    fn upcoming_is_strictly_increasing() {
+        let s = Schedule::Daily { hour: 0, minute: 0 };
+        let dates = s.upcoming(utc(2025, 3, 1, 0, 0), 3);
+        assert_eq!(dates, [utc(2025, 3, 2, 0, 0), utc(2025, 3, 3, 0, 0), utc(2025, 3, 4, 0, 0)]);
+        assert!(s.upcoming(utc(2025, 3, 1, 0, 0), 0).is_empty());
+    }
+
+    #[test]
+    fn past_the_last_instant_is_out_of_range() {
+        let max = DateTime::<Utc>::MAX_UTC;
+        let schedules = [
+            Schedule::Daily { hour: 0, minute: 0 },
+            Schedule::Weekly { weekday: Weekday::Mon, hour: 0, minute: 0 },
+            Schedule::Weekly { weekday: max.weekday(), hour: 0, minute: 0 },
+            Schedule::Monthly { day: 31, hour: 0, minute: 0 },
+            Schedule::Monthly { day: 1, hour: 0, minute: 0 },
+            Schedule::EveryNMinutes(1),
+        ];
+        for s in schedules {
+            assert_eq!(s.next_after(max), Err(ScheduleError::OutOfRange), "{:?}", s);
+            assert!(s.upcoming(max, 3).is_empty());
+        }
+    }
+
+    #[test]
+    fn the_last_occurrences_before_the_end_still_work() {
+        let max = DateTime::<Utc>::MAX_UTC;
+        let last_day = max.date_naive();
+        let s = Schedule::Daily { hour: 12, minute: 0 };
+        let day_before = max - Duration::days(1);
+        assert_eq!(s.next_after(day_before), Ok(at(last_day, 12, 0)));
+        // one more and then it runs out
+        assert_eq!(s.upcoming(day_before - Duration::days(1), 5).len(), 2);
+
+        // december of chrono's last year is valid, only the january after isn't
+        assert_eq!(days_in_month(last_day.year(), 12), Some(31));
+        assert_eq!(days_in_month(last_day.year() + 1, 1), None);
+        let monthly = Schedule::Monthly { day: 31, hour: 6, minute: 0 };
+        let start = at(last_day.with_day(1).unwrap(), 0, 0);
+        assert_eq!(monthly.next_after(start), Ok(at(last_day, 6, 0)));
+    }
+}
@@ -0,0 +1,235 @@
+// an append-only log file with one timestamped line per message:
+//
+// 2025-03-01T10:15:00Z | message
+//
+// newlines inside a message are written as \n (and backslashes as \\)
+// so every entry stays on one line
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+pub struct TimeLog {
+    file: File,
+    now: Box<dyn Fn() -> DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Utc>,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub enum LogParseError {
+    Io(io::Error),
+    BadLine { line: usize, content: String },
+}
+
+impl fmt::Display for LogParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LogParseError::Io(err) => write!(f, "could not read the log: {}", err),
+            LogParseError::BadLine { line, content } => {
+                write!(f, "line {} is not a log entry: {:?}", line, content)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LogParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LogParseError::Io(err) => Some(err),
+            LogParseError::BadLine { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for LogParseError {
+    fn from(err: io::Error) -> Self {
+        LogParseError::Io(err)
+    }
+}
+
+impl TimeLog {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<TimeLog> {
+        TimeLog::open_with_clock(path, Utc::now)
+    }
+
+    // `now` is asked for the timestamp of every record, handy for fixed times
+    pub fn open_with_clock(
+        path: impl AsRef<Path>,
+        now: impl Fn() -> DateTime<Utc> + 'static,
+    ) -> io::Result<TimeLog> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(TimeLog { file, now: Box::new(now) })
+    }
+
+    pub fn record(&mut self, message: &str) -> io::Result<()> {
+        let timestamp = (self.now)().to_rfc3339_opts(SecondsFormat::Secs, true);
+        writeln!(self.file, "{} | {}", timestamp, escape(message))
+    }
+}
+
+fn escape(message: &str) -> String {
+    let mut out = String::with_capacity(message.len());
+    for c in message.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape(message: &str) -> String {
+    let mut out = String::with_capacity(message.len());
+    let mut chars = message.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+pub fn read_entries(path: impl AsRef<Path>) -> Result<Vec<LogEntry>, LogParseError> {
+    let text = std::fs::read_to_string(path)?;
+    let mut entries = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        let bad = || LogParseError::BadLine { line: index + 1, content: line.to_string() };
+        let (timestamp, message) = line.split_once(" | ").ok_or_else(bad)?;
+        let timestamp = DateTime::parse_from_rfc3339(timestamp).map_err(|_| bad())?;
+        entries.push(LogEntry { timestamp: timestamp.to_utc(), message: unescape(message) });
+    }
+    Ok(entries)
+}
+
+// entries with from <= timestamp < to
+pub fn entries_between(
+    entries: &[LogEntry],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<&LogEntry> {
+    entries.iter().filter(|e| e.timestamp >= from && e.timestamp < to).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::path::PathBuf;
+
+    use chrono::{TimeDelta, TimeZone};
+
+    // a fresh file per test, the tests share one process and one temp dir
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("timelog-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 1, 10, 15, 0).unwrap()
+    }
+
+    // a clock that moves one minute forward every time it is asked
+    fn ticking_clock() -> impl Fn() -> DateTime<Utc> {
+        let calls = Cell::new(0);
+        move || {
+            let now = start() + TimeDelta::minutes(calls.get());
+            calls.set(calls.get() + 1);
+            now
+        }
+    }
+
+    #[test]
+    fn entries_read_back_with_their_timestamps() {
+        let path = temp_path("read-back");
+        let mut log = TimeLog::open_with_clock(&path, ticking_clock()).unwrap();
+        for message in ["started", "working", "done"] {
+            log.record(message).unwrap();
+        }
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("2025-03-01T10:15:00Z | started\n"));
+        let entries = read_entries(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let messages: Vec<&str> = entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["started", "working", "done"]);
+        assert_eq!(entries[2].timestamp, start() + TimeDelta::minutes(2));
+    }
+
+    #[test]
+    fn reopening_appends() {
+        let path = temp_path("append");
+        TimeLog::open_with_clock(&path, start).unwrap().record("one").unwrap();
+        TimeLog::open_with_clock(&path, start).unwrap().record("two").unwrap();
+        let entries = read_entries(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+    }
+
+    #[test]
+    fn newlines_and_backslashes_round_trip() {
+        let path = temp_path("escape");
+        let tricky =
+            ["two\nlines", "windows\r\nline", "a \\n that was typed", "ends with \\", " | "];
+        let mut log = TimeLog::open_with_clock(&path, start).unwrap();
+        for message in tricky {
+            log.record(message).unwrap();
+        }
+        let text = std::fs::read_to_string(&path).unwrap();
+        let entries = read_entries(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(text.lines().count(), tricky.len());
+        let messages: Vec<&str> = entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, tricky);
+    }
+
+    #[test]
+    fn the_range_filter_is_half_open() {
+        let entries: Vec<LogEntry> = (0..5)
+            .map(|minute| LogEntry {
+                timestamp: start() + TimeDelta::minutes(minute),
+                message: minute.to_string(),
+            })
+            .collect();
+        let from = start() + TimeDelta::minutes(1);
+        let to = start() + TimeDelta::minutes(3);
+        let picked: Vec<&str> =
+            entries_between(&entries, from, to).iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(picked, ["1", "2"]);
+        assert!(entries_between(&entries, to, from).is_empty());
+    }
+
+    #[test]
+    fn bad_lines_are_reported_with_their_number() {
+        let path = temp_path("bad-line");
+        std::fs::write(&path, "2025-03-01T10:15:00Z | fine\n\nnot a log line\n").unwrap();
+        let result = read_entries(&path);
+        std::fs::remove_file(&path).unwrap();
+        match result {
+            Err(LogParseError::BadLine { line, content }) => {
+                assert_eq!((line, content.as_str()), (3, "not a log line"));
+            }
+            other => panic!("expected a bad line, got {:?}", other),
+        }
+        assert!(matches!(read_entries(temp_path("missing")), Err(LogParseError::Io(_))));
+    }
+}
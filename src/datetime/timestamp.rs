@@ -0,0 +1,187 @@
+// converting between DateTime<Utc> and Unix timestamps
+// negative values are before 1970 and work the same way
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampError {
+    // chrono only covers roughly the years -262143 to 262142
+    OutOfRange(i64),
+}
+
+impl fmt::Display for TimestampError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TimestampError::OutOfRange(value) => {
+                write!(f, "timestamp {} is outside the range chrono can represent", value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TimestampError {}
+
+pub fn to_unix(dt: DateTime<Utc>) -> i64 {
+    dt.timestamp()
+}
+
+pub fn from_unix(secs: i64) -> Result<DateTime<Utc>, TimestampError> {
+    DateTime::from_timestamp(secs, 0).ok_or(TimestampError::OutOfRange(secs))
+}
+
+pub fn to_unix_millis(dt: DateTime<Utc>) -> i64 {
+    dt.timestamp_millis()
+}
+
+pub fn from_unix_millis(millis: i64) -> Result<DateTime<Utc>, TimestampError> {
+    DateTime::from_timestamp_millis(millis).ok_or(TimestampError::OutOfRange(millis))
+}
+
+// days since 1970-01-01 to (year, month, day), Howard Hinnant's civil_from_days
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+// writes `value` as `buf.len()` zero padded digits
+fn put_digits(buf: &mut [u8], mut value: u32) {
+    for slot in buf.iter_mut().rev() {
+        *slot = b'0' + (value % 10) as u8;
+        value /= 10;
+    }
+}
+
+// the same bytes as dt.to_rfc3339_opts(SecondsFormat::Secs, true), e.g.
+// "2024-02-29T23:59:59Z", built by hand without chrono's format().
+// years 0 to 9999 are zero padded to 4 digits like chrono does, any other
+// year has no 4 digit form and gives None
+pub fn format_rfc3339_manual(dt: DateTime<Utc>) -> Option<String> {
+    let secs = dt.timestamp();
+    let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+    let of_day = secs.rem_euclid(86_400) as u32;
+    if !(0..=9999).contains(&year) {
+        return None;
+    }
+
+    let mut buf = *b"0000-00-00T00:00:00Z";
+    put_digits(&mut buf[0..4], year as u32);
+    put_digits(&mut buf[5..7], month);
+    put_digits(&mut buf[8..10], day);
+    put_digits(&mut buf[11..13], of_day / 3600);
+    put_digits(&mut buf[14..16], of_day / 60 % 60);
+    put_digits(&mut buf[17..19], of_day % 60);
+    // every byte is an ascii digit or separator
+    Some(String::from_utf8(buf.to_vec()).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use chrono::{SecondsFormat, TimeZone};
+
+    fn chrono_format(dt: DateTime<Utc>) -> String {
+        dt.to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+
+    #[test]
+    fn matches_chrono_on_seeded_instants() {
+        let mut rng = Rng::new(137);
+        // 0000-01-01 to 9999-12-31
+        let (low, high) = (-62_167_219_200, 253_402_300_799);
+        for _ in 0..100_000 {
+            let dt = from_unix(rng.range(low, high + 1)).unwrap();
+            assert_eq!(format_rfc3339_manual(dt), Some(chrono_format(dt)), "{}", dt.timestamp());
+        }
+    }
+
+    // every day of a leap year and its neighbours, single digit months and days included
+    #[test]
+    fn matches_chrono_day_by_day() {
+        let start = Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap().timestamp();
+        for day in 0..(3 * 366) {
+            let dt = from_unix(start + day * 86_400 + day * 37 % 86_400).unwrap();
+            assert_eq!(format_rfc3339_manual(dt), Some(chrono_format(dt)));
+        }
+    }
+
+    #[test]
+    fn boundaries() {
+        let cases = [
+            (Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(), "2025-01-01T00:00:00Z"),
+            (Utc.with_ymd_and_hms(2025, 12, 31, 23, 59, 59).unwrap(), "2025-12-31T23:59:59Z"),
+            (Utc.with_ymd_and_hms(2024, 2, 29, 12, 5, 9).unwrap(), "2024-02-29T12:05:09Z"),
+            (Utc.with_ymd_and_hms(2000, 2, 29, 0, 0, 0).unwrap(), "2000-02-29T00:00:00Z"),
+            (Utc.with_ymd_and_hms(1969, 12, 31, 23, 59, 59).unwrap(), "1969-12-31T23:59:59Z"),
+        ];
+        for (dt, expected) in cases {
+            assert_eq!(format_rfc3339_manual(dt).as_deref(), Some(expected));
+            assert_eq!(chrono_format(dt), expected);
+        }
+    }
+
+    #[test]
+    fn years_below_1000_are_zero_padded() {
+        for year in [0, 7, 42, 999] {
+            let dt = Utc.with_ymd_and_hms(year, 3, 4, 5, 6, 7).unwrap();
+            assert_eq!(format_rfc3339_manual(dt), Some(chrono_format(dt)));
+        }
+        let dt = Utc.with_ymd_and_hms(9, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(format_rfc3339_manual(dt).as_deref(), Some("0009-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn years_without_four_digits_are_none() {
+        let before = Utc.with_ymd_and_hms(-1, 12, 31, 23, 59, 59).unwrap();
+        let after = Utc.with_ymd_and_hms(10_000, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(format_rfc3339_manual(before), None);
+        assert_eq!(format_rfc3339_manual(after), None);
+    }
+
+    #[test]
+    fn seconds_round_trip_including_the_epoch_and_before() {
+        let instants = [
+            Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(1969, 7, 20, 20, 17, 40).unwrap(),
+            Utc.with_ymd_and_hms(1900, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2038, 1, 19, 3, 14, 8).unwrap(),
+        ];
+        for dt in instants {
+            assert_eq!(from_unix(to_unix(dt)), Ok(dt));
+        }
+        assert_eq!(to_unix(instants[0]), 0);
+        assert_eq!(to_unix(instants[1]), -14_182_940);
+        assert_eq!(to_unix(instants[3]), 1_735_689_600);
+    }
+
+    #[test]
+    fn millis_round_trip() {
+        let dt = Utc.with_ymd_and_hms(1969, 12, 31, 23, 59, 59).unwrap()
+            + chrono::TimeDelta::milliseconds(250);
+        assert_eq!(to_unix_millis(dt), -750);
+        assert_eq!(from_unix_millis(-750), Ok(dt));
+        assert_eq!(from_unix_millis(0).map(to_unix), Ok(0));
+    }
+
+    #[test]
+    fn out_of_range_values_are_errors() {
+        assert_eq!(from_unix(i64::MAX), Err(TimestampError::OutOfRange(i64::MAX)));
+        assert_eq!(from_unix(i64::MIN), Err(TimestampError::OutOfRange(i64::MIN)));
+        // i64::MAX milliseconds is still about 292 million years
+        assert_eq!(from_unix_millis(i64::MAX), Err(TimestampError::OutOfRange(i64::MAX)));
+        // the last second chrono can hold is fine
+        let max = DateTime::<Utc>::MAX_UTC;
+        assert_eq!(from_unix(to_unix(max)).map(to_unix), Ok(max.timestamp()));
+    }
+}
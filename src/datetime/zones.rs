@@ -0,0 +1,117 @@
+// one instant shown in several IANA time zones, using chrono-tz
+
+use std::fmt;
+
+use chrono::{DateTime, FixedOffset, Offset, Utc};
+use chrono_tz::{OffsetName, Tz};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneTime {
+    pub zone: String,
+    pub time: DateTime<FixedOffset>,
+    // IST, CET, EDT ... some zones only have a numeric one like +04
+    pub abbreviation: String,
+}
+
+impl ZoneTime {
+    pub fn offset_minutes(&self) -> i32 {
+        self.time.offset().local_minus_utc() / 60
+    }
+}
+
+// the zones that were found are still in `converted`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneError {
+    pub unknown: Vec<String>,
+    pub converted: Vec<ZoneTime>,
+}
+
+impl fmt::Display for ZoneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown time zone(s): {}", self.unknown.join(", "))
+    }
+}
+
+impl std::error::Error for ZoneError {}
+
+pub fn convert_to_zones(
+    instant: DateTime<Utc>,
+    zones: &[&str],
+) -> Result<Vec<ZoneTime>, ZoneError> {
+    let mut converted = Vec::new();
+    let mut unknown = Vec::new();
+    for name in zones {
+        let name = name.trim();
+        match name.parse::<Tz>() {
+            Ok(tz) => {
+                let local = instant.with_timezone(&tz);
+                let abbreviation = local.offset().abbreviation().unwrap_or("").to_string();
+                converted.push(ZoneTime {
+                    zone: name.to_string(),
+                    time: local.with_timezone(&local.offset().fix()),
+                    abbreviation,
+                });
+            }
+            Err(_) => unknown.push(name.to_string()),
+        }
+    }
+    if unknown.is_empty() {
+        Ok(converted)
+    } else {
+        Err(ZoneError { unknown, converted })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Timelike, TimeZone};
+
+    fn instant() -> DateTime<Utc> {
+        // winter, so neither New York nor Berlin is on summer time
+        Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap()
+    }
+
+    fn wall_clock(zone: &ZoneTime) -> (u32, u32) {
+        (zone.time.hour(), zone.time.minute())
+    }
+
+    #[test]
+    fn wall_clock_times_in_three_zones() {
+        let zones = ["Asia/Kolkata", "America/New_York", "Europe/Berlin", "Asia/Kathmandu"];
+        let converted = convert_to_zones(instant(), &zones).unwrap();
+        let clocks: Vec<(u32, u32)> = converted.iter().map(wall_clock).collect();
+        assert_eq!(clocks, [(17, 30), (7, 0), (13, 0), (17, 45)]);
+        let offsets: Vec<i32> = converted.iter().map(ZoneTime::offset_minutes).collect();
+        assert_eq!(offsets, [330, -300, 60, 345]);
+        assert_eq!(converted[0].abbreviation, "IST");
+        assert_eq!(converted[1].abbreviation, "EST");
+        assert_eq!(converted[2].abbreviation, "CET");
+    }
+
+    #[test]
+    fn every_converted_time_is_the_same_instant() {
+        let converted = convert_to_zones(instant(), &["Asia/Tokyo", " UTC ", "Pacific/Chatham"]);
+        for zone in converted.unwrap() {
+            assert_eq!(zone.time.to_utc(), instant());
+        }
+    }
+
+    #[test]
+    fn summer_time_changes_the_offset() {
+        let july = Utc.with_ymd_and_hms(2025, 7, 15, 12, 0, 0).unwrap();
+        let converted = convert_to_zones(july, &["America/New_York"]).unwrap();
+        assert_eq!(wall_clock(&converted[0]), (8, 0));
+        assert_eq!(converted[0].abbreviation, "EDT");
+    }
+
+    #[test]
+    fn misspelled_zones_are_listed_and_the_rest_still_convert() {
+        let err = convert_to_zones(instant(), &["Asia/Kolkatta", "Europe/Berlin", "Mars/Olympus"])
+            .unwrap_err();
+        assert_eq!(err.unknown, ["Asia/Kolkatta", "Mars/Olympus"]);
+        assert_eq!(err.converted.len(), 1);
+        assert_eq!(err.converted[0].zone, "Europe/Berlin");
+        assert_eq!(err.to_string(), "unknown time zone(s): Asia/Kolkatta, Mars/Olympus");
+    }
+}
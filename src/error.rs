@@ -0,0 +1,485 @@
+// one error type for the whole crate: every module's error converts into it,
+// so `?` works across module boundaries in any bin
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::process;
+
+use crate::config::ConfigError;
+use crate::exit_code::exit_code_for;
+use crate::line_reader::FileReadError;
+use crate::logger::{Level, Logger};
+use crate::report::{report, ReportMode};
+use crate::shape::ParseShapeError;
+use crate::user::UserError;
+
+#[derive(Debug)]
+pub enum AppError {
+    Io(io::Error),
+    FileRead(FileReadError),
+    ParseShape(ParseShapeError),
+    User(UserError),
+    Config(ConfigError),
+    // the command line was wrong, the message says how
+    Usage(String),
+    // anything with a .context(...) message on top
+    Context(ContextError),
+    // for one-off errors that don't deserve a type of their own
+    Msg(String),
+}
+
+// Display starts with the domain, the inner error is also reachable through source()
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AppError::Io(e) => write!(f, "io: {}", e),
+            AppError::FileRead(e) => write!(f, "file: {}", e),
+            AppError::ParseShape(e) => write!(f, "shape: {}", e),
+            AppError::User(e) => write!(f, "user: {}", e),
+            AppError::Config(e) => write!(f, "config: {}", e),
+            AppError::Usage(msg) => write!(f, "usage: {}", msg),
+            AppError::Context(e) => write!(f, "{}", e),
+            AppError::Msg(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl Error for AppError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AppError::Io(e) => Some(e),
+            AppError::FileRead(e) => Some(e),
+            AppError::ParseShape(e) => Some(e),
+            AppError::User(e) => Some(e),
+            AppError::Config(e) => Some(e),
+            AppError::Context(e) => Some(e),
+            AppError::Usage(_) | AppError::Msg(_) => None,
+        }
+    }
+}
+
+impl AppError {
+    // see exit_code for the whole table
+    pub fn exit_code(&self) -> i32 {
+        exit_code_for(self)
+    }
+}
+
+// "error: <err>" and then one indented "caused by:" line per source. the only
+// line left out is the direct source of an AppError, which its message has already
+pub fn report_error(err: &(dyn Error + 'static), writer: &mut dyn Write) {
+    // there is nowhere left to report a failed write to
+    let _ = writeln!(writer, "error: {}", err);
+    let mut parent = err;
+    let mut source = err.source();
+    while let Some(cause) = source {
+        // every AppError with a source writes it into its own Display, see above
+        if !parent.is::<AppError>() {
+            let _ = writeln!(writer, "  caused by: {}", cause);
+        }
+        parent = cause;
+        source = cause.source();
+    }
+}
+
+// for `fn main() { run_main(run) }`: runs `f`, reports any error on stderr
+// (see report::ReportMode for how much is shown) and exits with its exit_code
+pub fn run_main(f: fn() -> Result<(), AppError>) -> ! {
+    match f() {
+        Ok(()) => process::exit(0),
+        Err(err) => {
+            report(&err, ReportMode::from_env());
+            process::exit(err.exit_code())
+        }
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(e: io::Error) -> Self {
+        AppError::Io(e)
+    }
+}
+
+impl From<FileReadError> for AppError {
+    fn from(e: FileReadError) -> Self {
+        AppError::FileRead(e)
+    }
+}
+
+impl From<ParseShapeError> for AppError {
+    fn from(e: ParseShapeError) -> Self {
+        AppError::ParseShape(e)
+    }
+}
+
+impl From<UserError> for AppError {
+    fn from(e: UserError) -> Self {
+        AppError::User(e)
+    }
+}
+
+impl From<ConfigError> for AppError {
+    fn from(e: ConfigError) -> Self {
+        AppError::Config(e)
+    }
+}
+
+impl From<ContextError> for AppError {
+    fn from(e: ContextError) -> Self {
+        AppError::Context(e)
+    }
+}
+
+impl From<String> for AppError {
+    fn from(msg: String) -> Self {
+        AppError::Msg(msg)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(msg: &str) -> Self {
+        AppError::Msg(msg.to_string())
+    }
+}
+
+// a message saying what was being done, on top of the error that stopped it.
+// `{}` prints just the message, `{:#}` the whole chain joined with ": "
+#[derive(Debug)]
+pub struct ContextError {
+    message: String,
+    source: Box<dyn Error + Send + Sync + 'static>,
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if f.alternate() {
+            let mut cause: Option<&(dyn Error + 'static)> = Some(self.source.as_ref());
+            while let Some(err) = cause {
+                // a nested ContextError prints only its own message here
+                write!(f, ": {}", err)?;
+                cause = err.source();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Error for ContextError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+// read_to_string(path).context("reading the word list")?
+pub trait Contextual<T> {
+    fn context<C: fmt::Display>(self, ctx: C) -> Result<T, ContextError>;
+}
+
+// ContextError is an Error too, so contexts can be stacked
+impl<T, E: Error + Send + Sync + 'static> Contextual<T> for Result<T, E> {
+    fn context<C: fmt::Display>(self, ctx: C) -> Result<T, ContextError> {
+        self.map_err(|err| ContextError { message: ctx.to_string(), source: Box::new(err) })
+    }
+}
+
+// shortcuts for turning an Option into something a bin can use directly
+pub trait OptionExt<T> {
+    // None becomes AppError::Msg(msg)
+    fn ok_or_msg(self, msg: &str) -> Result<T, AppError>;
+    // like expect, but the panic says what was being done
+    fn expect_ctx(self, ctx: impl fmt::Display) -> T;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    fn ok_or_msg(self, msg: &str) -> Result<T, AppError> {
+        self.ok_or_else(|| AppError::Msg(msg.to_string()))
+    }
+
+    fn expect_ctx(self, ctx: impl fmt::Display) -> T {
+        match self {
+            Some(value) => value,
+            None => panic!("{}: got None", ctx),
+        }
+    }
+}
+
+// for errors that should be noted and then got past
+pub trait ResultExt<T, E> {
+    // the error goes to the logger at Error level, the value (if any) comes back
+    fn log_err(self, logger: &Logger) -> Option<T>;
+    // prints "warning: <err>" on stderr and carries on with T::default()
+    fn warn_default(self) -> T
+    where
+        T: Default;
+}
+
+impl<T, E: fmt::Display> ResultExt<T, E> for Result<T, E> {
+    fn log_err(self, logger: &Logger) -> Option<T> {
+        match self {
+            Ok(value) => Some(value),
+            Err(err) => {
+                logger.log(Level::Error, &err.to_string());
+                None
+            }
+        }
+    }
+
+    fn warn_default(self) -> T
+    where
+        T: Default,
+    {
+        self.unwrap_or_else(|err| {
+            eprintln!("warning: {}", err);
+            T::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use crate::config::parse_config;
+
+    fn not_found() -> io::Error {
+        io::Error::new(io::ErrorKind::NotFound, "no such file")
+    }
+
+    // `?` inside a function returning AppError, the way the bins use it
+    fn lift<E>(result: Result<(), E>) -> Result<(), AppError>
+    where
+        AppError: From<E>,
+    {
+        result?;
+        Ok(())
+    }
+
+    // every error from err down through source(), err itself first
+    fn chain<'a>(err: &'a (dyn Error + 'static)) -> Vec<&'a (dyn Error + 'static)> {
+        let mut out = vec![err];
+        let mut cause = err.source();
+        while let Some(err) = cause {
+            out.push(err);
+            cause = err.source();
+        }
+        out
+    }
+
+    #[test]
+    fn every_module_error_converts_with_the_question_mark() {
+        let io = lift(Err(not_found())).unwrap_err();
+        assert!(matches!(io, AppError::Io(ref e) if e.kind() == io::ErrorKind::NotFound));
+
+        let open = FileReadError::Open { path: PathBuf::from("a.txt"), source: not_found() };
+        assert!(matches!(lift(Err(open)).unwrap_err(), AppError::FileRead(_)));
+
+        let shape = lift(Err(ParseShapeError::Empty)).unwrap_err();
+        assert!(matches!(shape, AppError::ParseShape(ParseShapeError::Empty)));
+
+        let user = lift(Err(UserError::BadId(-1))).unwrap_err();
+        assert!(matches!(user, AppError::User(UserError::BadId(-1))));
+
+        let config = lift(parse_config("multiplier=2").map(|_| ())).unwrap_err();
+        assert!(matches!(config, AppError::Config(ConfigError::MissingKey("file"))));
+
+        let ctx = lift(Err(not_found()).context("loading")).unwrap_err();
+        assert!(matches!(ctx, AppError::Context(_)));
+
+        let owned = lift(Err("owned".to_string())).unwrap_err();
+        assert!(matches!(owned, AppError::Msg(ref m) if m == "owned"));
+        let borrowed = lift(Err("borrowed")).unwrap_err();
+        assert!(matches!(borrowed, AppError::Msg(ref m) if m == "borrowed"));
+    }
+
+    #[test]
+    fn display_starts_with_the_domain() {
+        let open = FileReadError::Open { path: PathBuf::from("a.txt"), source: not_found() };
+        let cases = [
+            (AppError::from(not_found()), "io: no such file"),
+            (AppError::from(open), "file: could not open a.txt"),
+            (AppError::from(ParseShapeError::Empty), "shape: no shape given"),
+            (AppError::from(UserError::EmptyName), "user: name can't be empty"),
+            (AppError::from(ConfigError::MissingKey("file")), "config: missing key `file`"),
+            (AppError::Usage("no file given".to_string()), "usage: no file given"),
+            (AppError::from("plain"), "plain"),
+        ];
+        for (err, expected) in cases {
+            assert_eq!(err.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn source_walks_down_to_the_original_io_error() {
+        let open = FileReadError::Open { path: PathBuf::from("words.txt"), source: not_found() };
+        let err = AppError::from(open);
+        let links = chain(&err);
+        assert_eq!(links.len(), 3);
+        assert!(links[1].is::<FileReadError>());
+        let io = links[2].downcast_ref::<io::Error>().expect("last link is the io error");
+        assert_eq!(io.kind(), io::ErrorKind::NotFound);
+
+        // a bad number in the config goes config -> ParseIntError
+        let err = AppError::from(parse_config("file=a\nmultiplier=x").unwrap_err());
+        let links = chain(&err);
+        assert_eq!(links.len(), 3);
+        assert!(links[2].is::<std::num::ParseIntError>());
+
+        // the ones without an inner error stop right away
+        assert!(AppError::from("msg").source().is_none());
+        assert!(AppError::Usage("u".to_string()).source().is_none());
+    }
+
+    // reading config: opening file: <the io error>, built the way a call site would
+    fn three_contexts() -> ContextError {
+        let opening: Result<(), io::Error> = Err(not_found());
+        let parsing = opening.context("opening file");
+        let loading = parsing.context("loading words.txt");
+        loading.context("reading config").unwrap_err()
+    }
+
+    #[test]
+    fn nested_contexts_print_short_and_full() {
+        let err = three_contexts();
+        assert_eq!(err.to_string(), "reading config");
+        assert_eq!(
+            format!("{:#}", err),
+            "reading config: loading words.txt: opening file: no such file"
+        );
+        // the AppError wrapper keeps the short form too
+        assert_eq!(AppError::from(three_contexts()).to_string(), "reading config");
+    }
+
+    #[test]
+    fn nested_contexts_have_a_source_depth_of_three() {
+        let err = three_contexts();
+        let links = chain(&err);
+        // the three contexts, each with a source, and the io error with none
+        assert_eq!(links.len() - 1, 3);
+        assert!(links[..3].iter().all(|e| e.is::<ContextError>()));
+        assert!(links[3].is::<io::Error>());
+        assert!(links[3].source().is_none());
+    }
+
+    #[test]
+    fn context_leaves_ok_values_alone() {
+        let ok: Result<i32, io::Error> = Ok(7);
+        assert_eq!(ok.context("never shown").unwrap(), 7);
+        let parsed = "x".parse::<i32>().context(format!("parsing {}", "x")).unwrap_err();
+        assert_eq!(format!("{:#}", parsed), "parsing x: invalid digit found in string");
+    }
+
+    fn report_lines(err: &(dyn Error + 'static)) -> Vec<String> {
+        let mut sink: Vec<u8> = Vec::new();
+        report_error(err, &mut sink);
+        String::from_utf8(sink).unwrap().lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn report_error_prints_one_caused_by_line_per_source() {
+        let open: Result<(), FileReadError> =
+            Err(FileReadError::Open { path: PathBuf::from("app.conf"), source: not_found() });
+        let err = AppError::from(open.context("reading config").unwrap_err());
+        assert_eq!(
+            report_lines(&err),
+            [
+                "error: reading config",
+                "  caused by: could not open app.conf",
+                "  caused by: no such file",
+            ]
+        );
+    }
+
+    #[test]
+    fn report_error_keeps_a_cause_that_only_looks_like_a_repeat() {
+        // the context message happens to end with the io error's text, but it is
+        // written by hand, so the io error is still its own line
+        let io: Result<(), io::Error> = Err(not_found());
+        let err = io.context("opening words.txt: no such file").unwrap_err();
+        assert_eq!(
+            report_lines(&err),
+            ["error: opening words.txt: no such file", "  caused by: no such file"]
+        );
+    }
+
+    #[test]
+    fn report_error_skips_a_message_already_in_the_one_above() {
+        // "config: `multiplier` is not a number" already has the ConfigError text in it
+        let err = AppError::from(parse_config("file=a\nmultiplier=x").unwrap_err());
+        assert_eq!(
+            report_lines(&err),
+            [
+                "error: config: `multiplier` is not a number",
+                "  caused by: invalid digit found in string",
+            ]
+        );
+        assert_eq!(report_lines(&AppError::from("just this")), ["error: just this"]);
+    }
+
+    #[test]
+    fn exit_codes_follow_the_error_domain() {
+        assert_eq!(AppError::Usage("no args".to_string()).exit_code(), 2);
+        assert_eq!(AppError::from(io::Error::other("pipe closed")).exit_code(), 74);
+        // a context on top doesn't hide the io error underneath
+        let wrapped: Result<(), io::Error> = Err(io::Error::other("pipe closed"));
+        assert_eq!(AppError::from(wrapped.context("writing").unwrap_err()).exit_code(), 74);
+        assert_eq!(AppError::from(UserError::EmptyName).exit_code(), 65);
+        assert_eq!(AppError::from("unclassified").exit_code(), 70);
+    }
+
+    // a log sink the test can read back after the logger has shut down
+    #[derive(Clone, Default)]
+    struct SharedSink(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl Write for SharedSink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn ok_or_msg_turns_none_into_a_msg() {
+        assert_eq!(Some(3).ok_or_msg("unused").unwrap(), 3);
+        let err = None::<i32>.ok_or_msg("no such command").unwrap_err();
+        assert!(matches!(err, AppError::Msg(ref m) if m == "no such command"));
+    }
+
+    #[test]
+    fn expect_ctx_returns_the_value() {
+        assert_eq!(Some("x").expect_ctx("looking up x"), "x");
+    }
+
+    #[test]
+    #[should_panic(expected = "looking up shape 7: got None")]
+    fn expect_ctx_panics_with_the_context() {
+        None::<i32>.expect_ctx(format!("looking up shape {}", 7));
+    }
+
+    #[test]
+    fn log_err_writes_the_error_to_the_logger() {
+        let sink = SharedSink::default();
+        let logger = Logger::new(Box::new(sink.clone()), Level::Info);
+        assert_eq!(Ok::<i32, String>(1).log_err(&logger), Some(1));
+        assert_eq!(Err::<i32, _>("disk on fire").log_err(&logger), None);
+        logger.shutdown().unwrap();
+
+        let text = String::from_utf8(sink.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with("ERROR "), "{}", lines[0]);
+        assert!(lines[0].ends_with(" disk on fire"), "{}", lines[0]);
+    }
+
+    #[test]
+    fn warn_default_gives_the_default_on_error() {
+        assert_eq!(Ok::<_, String>(vec![1, 2]).warn_default(), vec![1, 2]);
+        assert_eq!(Err::<Vec<i32>, _>("bad").warn_default(), Vec::<i32>::new());
+        assert_eq!("x".parse::<u32>().warn_default(), 0);
+    }
+}
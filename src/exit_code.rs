@@ -0,0 +1,185 @@
+// which exit code a failed run ends with, following sysexits.h
+
+use std::error::Error;
+use std::io::{self, ErrorKind};
+use std::process::{ExitCode, Termination};
+
+use crate::config::ConfigError;
+use crate::error::AppError;
+use crate::line_reader::FileReadError;
+use crate::report::{report, ReportMode};
+use crate::shape::ParseShapeError;
+use crate::user::UserError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppErrorKind {
+    // the command line was wrong
+    Usage,
+    // the input was there but made no sense
+    DataErr,
+    // a file that was asked for does not exist
+    NotFound,
+    // reading or writing failed some other way
+    Io,
+    // a bug, or an error nobody classified
+    Internal,
+}
+
+impl AppErrorKind {
+    pub fn code(self) -> i32 {
+        match self {
+            AppErrorKind::Usage => 2,
+            AppErrorKind::DataErr => 65,
+            AppErrorKind::NotFound => 66,
+            AppErrorKind::Internal => 70,
+            AppErrorKind::Io => 74,
+        }
+    }
+}
+
+pub fn classify(err: &io::Error) -> AppErrorKind {
+    match err.kind() {
+        ErrorKind::NotFound => AppErrorKind::NotFound,
+        // bytes that aren't utf-8 and the like
+        ErrorKind::InvalidData | ErrorKind::UnexpectedEof => AppErrorKind::DataErr,
+        _ => AppErrorKind::Io,
+    }
+}
+
+// the first error in the chain we know something about decides,
+// so a .context() on top doesn't hide an io error underneath
+fn kind_of(err: &(dyn Error + 'static)) -> Option<AppErrorKind> {
+    if let Some(app) = err.downcast_ref::<AppError>() {
+        match app {
+            AppError::Usage(_) => return Some(AppErrorKind::Usage),
+            AppError::Msg(_) => return Some(AppErrorKind::Internal),
+            _ => {}
+        }
+    } else if let Some(io) = err.downcast_ref::<io::Error>() {
+        return Some(classify(io));
+    } else if err.is::<ConfigError>() || err.is::<ParseShapeError>() || err.is::<UserError>() {
+        return Some(AppErrorKind::DataErr);
+    } else if let Some(FileReadError::Open { source, .. } | FileReadError::Read { source, .. }) =
+        err.downcast_ref::<FileReadError>()
+    {
+        return Some(classify(source));
+    }
+    None
+}
+
+pub fn kind_for(err: &AppError) -> AppErrorKind {
+    let mut cause: Option<&(dyn Error + 'static)> = Some(err);
+    while let Some(err) = cause {
+        if let Some(kind) = kind_of(err) {
+            return kind;
+        }
+        cause = err.source();
+    }
+    AppErrorKind::Internal
+}
+
+pub fn exit_code_for(err: &AppError) -> i32 {
+    kind_for(err).code()
+}
+
+// for `fn main() -> Exit { run().into() }`: an error is reported on stderr
+// the same way run_main does it, and the process ends with exit_code_for it
+pub struct Exit(pub Result<(), AppError>);
+
+impl From<Result<(), AppError>> for Exit {
+    fn from(result: Result<(), AppError>) -> Self {
+        Exit(result)
+    }
+}
+
+impl Termination for Exit {
+    fn report(self) -> ExitCode {
+        match self.0 {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                report(&err, ReportMode::from_env());
+                ExitCode::from(exit_code_for(&err) as u8)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use crate::error::Contextual;
+
+    fn io_error(kind: ErrorKind) -> io::Error {
+        io::Error::new(kind, "test")
+    }
+
+    fn open_error(kind: ErrorKind) -> FileReadError {
+        FileReadError::Open { path: PathBuf::from("missing.txt"), source: io_error(kind) }
+    }
+
+    #[test]
+    fn kinds_have_the_sysexits_codes() {
+        let table = [
+            (AppErrorKind::Usage, 2),
+            (AppErrorKind::DataErr, 65),
+            (AppErrorKind::NotFound, 66),
+            (AppErrorKind::Internal, 70),
+            (AppErrorKind::Io, 74),
+        ];
+        for (kind, code) in table {
+            assert_eq!(kind.code(), code, "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn io_errors_are_classified_by_kind() {
+        assert_eq!(classify(&io_error(ErrorKind::NotFound)), AppErrorKind::NotFound);
+        assert_eq!(classify(&io_error(ErrorKind::PermissionDenied)), AppErrorKind::Io);
+        assert_eq!(classify(&io_error(ErrorKind::Other)), AppErrorKind::Io);
+        assert_eq!(classify(&io_error(ErrorKind::InvalidData)), AppErrorKind::DataErr);
+        assert_eq!(classify(&io_error(ErrorKind::UnexpectedEof)), AppErrorKind::DataErr);
+    }
+
+    #[test]
+    fn every_app_error_variant_maps_to_a_code() {
+        let config = crate::config::parse_config("").unwrap_err();
+        let cases = [
+            (AppError::Usage("missing pattern".to_string()), 2),
+            (AppError::from(io_error(ErrorKind::NotFound)), 66),
+            (AppError::from(io_error(ErrorKind::PermissionDenied)), 74),
+            (AppError::from(open_error(ErrorKind::NotFound)), 66),
+            (AppError::from(open_error(ErrorKind::Other)), 74),
+            (AppError::from(ParseShapeError::Empty), 65),
+            (AppError::from(UserError::EmptyName), 65),
+            (AppError::from(config), 65),
+            (AppError::Msg("something odd".to_string()), 70),
+        ];
+        for (err, code) in cases {
+            assert_eq!(exit_code_for(&err), code, "{}", err);
+        }
+    }
+
+    #[test]
+    fn a_context_is_looked_through_and_unknown_errors_are_internal() {
+        let missing: Result<(), io::Error> = Err(io_error(ErrorKind::NotFound));
+        let err = AppError::from(missing.context("reading the word list").unwrap_err());
+        assert_eq!(kind_for(&err), AppErrorKind::NotFound);
+
+        // nothing in this chain is an error we know about
+        let odd: Result<(), std::fmt::Error> = Err(std::fmt::Error);
+        let err = AppError::from(odd.context("formatting").unwrap_err());
+        assert_eq!(kind_for(&err), AppErrorKind::Internal);
+        assert_eq!(exit_code_for(&err), 70);
+    }
+
+    #[test]
+    fn exit_reports_success_and_failure() {
+        // ExitCode can't be compared, so check through its Debug output
+        let ok = format!("{:?}", Exit(Ok(())).report());
+        assert_eq!(ok, format!("{:?}", ExitCode::SUCCESS));
+        let failed = Exit(Err(AppError::Usage("x".to_string()))).report();
+        assert_eq!(format!("{:?}", failed), format!("{:?}", ExitCode::from(2)));
+    }
+}
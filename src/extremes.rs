@@ -0,0 +1,176 @@
+// smallest and largest helpers that hand back references into the slice,
+// so nothing has to be cloned to find out which item won
+
+use std::cmp::Ordering;
+
+// the item with the smallest key, and the key itself so it isn't worked
+// out twice. the first of equal keys wins, like Iterator::min_by_key
+pub fn min_by_key_value<T, K: Ord, F: Fn(&T) -> K>(items: &[T], f: F) -> Option<(&T, K)> {
+    let mut iter = items.iter();
+    let first = iter.next()?;
+    let mut best = (first, f(first));
+    for item in iter {
+        let key = f(item);
+        if key < best.1 {
+            best = (item, key);
+        }
+    }
+    Some(best)
+}
+
+// the n largest items by cmp, largest first. the sort is stable,
+// so of equal items the one earlier in the slice comes first
+pub fn max_n_by<T, F: Fn(&T, &T) -> Ordering>(items: &[T], n: usize, cmp: F) -> Vec<&T> {
+    let mut refs: Vec<&T> = items.iter().collect();
+    refs.sort_by(|a, b| cmp(b, a));
+    refs.truncate(n);
+    refs
+}
+
+// smallest and largest in one pass. items are taken two at a time and only
+// the smaller is checked against the min and the larger against the max,
+// 3 comparisons per pair instead of 4.
+// ties go the same way as Iterator::min and max: the first smallest and the
+// last largest. a NaN is never smaller or larger, so it is only picked if it
+// starts the slice
+pub fn minmax<T: PartialOrd>(items: &[T]) -> Option<(&T, &T)> {
+    // one comparison puts a pair in order, a tie keeps them as they were
+    fn ordered<'a, T: PartialOrd>(a: &'a T, b: &'a T) -> (&'a T, &'a T) {
+        if b < a { (b, a) } else { (a, b) }
+    }
+
+    let first = items.first()?;
+    // an odd length starts from one item, an even one from the first pair
+    let (mut min, mut max, rest) = if items.len() % 2 == 1 {
+        (first, first, &items[1..])
+    } else {
+        let (small, large) = ordered(&items[0], &items[1]);
+        (small, large, &items[2..])
+    };
+    for pair in rest.chunks_exact(2) {
+        let (small, large) = ordered(&pair[0], &pair[1]);
+        if small < min {
+            min = small;
+        }
+        if large >= max {
+            max = large;
+        }
+    }
+    Some((min, max))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    use crate::rng::Rng;
+
+    // counts every comparison made through it
+    struct Counted<'a> {
+        value: i64,
+        count: &'a Cell<usize>,
+    }
+
+    impl PartialEq for Counted<'_> {
+        fn eq(&self, other: &Self) -> bool {
+            self.value == other.value
+        }
+    }
+
+    impl PartialOrd for Counted<'_> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            self.count.set(self.count.get() + 1);
+            self.value.partial_cmp(&other.value)
+        }
+    }
+
+    #[test]
+    fn min_by_key_value_returns_the_first_of_equal_keys() {
+        let words = ["pear", "fig", "kiwi", "yam", "banana"];
+        let (word, len) = min_by_key_value(&words, |w| w.len()).unwrap();
+        assert_eq!((*word, len), ("fig", 3));
+        assert!(std::ptr::eq(word, &words[1]));
+        assert_eq!(min_by_key_value(&[7], |n| -n), Some((&7, -7)));
+        assert_eq!(min_by_key_value(&[] as &[i32], |n| *n), None);
+    }
+
+    #[test]
+    fn min_by_key_value_works_out_each_key_once() {
+        let calls = Cell::new(0);
+        let items = [5, 3, 9, 1, 4];
+        let found = min_by_key_value(&items, |n| {
+            calls.set(calls.get() + 1);
+            *n
+        });
+        assert_eq!(found, Some((&1, 1)));
+        assert_eq!(calls.get(), items.len());
+    }
+
+    #[test]
+    fn max_n_by_keeps_slice_order_for_ties() {
+        let scores = [("a", 3), ("b", 5), ("c", 3), ("d", 5), ("e", 1)];
+        let top = max_n_by(&scores, 3, |x, y| x.1.cmp(&y.1));
+        let names: Vec<&str> = top.iter().map(|s| s.0).collect();
+        assert_eq!(names, ["b", "d", "a"]);
+    }
+
+    #[test]
+    fn max_n_by_with_zero_one_and_more_than_len() {
+        let items = [4, 8, 1];
+        assert!(max_n_by(&items, 0, Ord::cmp).is_empty());
+        assert_eq!(max_n_by(&items, 1, Ord::cmp), [&8]);
+        assert_eq!(max_n_by(&items, 10, Ord::cmp), [&8, &4, &1]);
+        assert_eq!(max_n_by(&[2], 5, Ord::cmp), [&2]);
+        assert!(max_n_by(&[] as &[i32], 3, Ord::cmp).is_empty());
+    }
+
+    #[test]
+    fn minmax_of_one_and_of_none() {
+        assert_eq!(minmax(&[] as &[i32]), None);
+        let one = [42];
+        let (min, max) = minmax(&one).unwrap();
+        assert!(std::ptr::eq(min, &one[0]) && std::ptr::eq(max, &one[0]));
+    }
+
+    #[test]
+    fn minmax_ties_match_iterator_min_and_max() {
+        let items = [2, 1, 5, 1, 5, 3];
+        let (min, max) = minmax(&items).unwrap();
+        // the first 1 and the last 5
+        assert!(std::ptr::eq(min, &items[1]));
+        assert!(std::ptr::eq(max, &items[4]));
+        let same = [7, 7, 7, 7];
+        let (min, max) = minmax(&same).unwrap();
+        assert!(std::ptr::eq(min, &same[0]) && std::ptr::eq(max, &same[3]));
+    }
+
+    #[test]
+    fn minmax_agrees_with_min_and_max_on_seeded_data() {
+        let mut rng = Rng::new(190);
+        for len in 1..200 {
+            // a small range, so there are plenty of ties
+            let items: Vec<i64> = (0..len).map(|_| rng.range(-20, 20)).collect();
+            let (min, max) = minmax(&items).unwrap();
+            assert!(std::ptr::eq(min, items.iter().min().unwrap()), "len {}", len);
+            assert!(std::ptr::eq(max, items.iter().max().unwrap()), "len {}", len);
+        }
+    }
+
+    #[test]
+    fn minmax_makes_about_three_comparisons_per_two_items() {
+        let count = Cell::new(0);
+        let mut rng = Rng::new(1900);
+        for len in [1usize, 2, 3, 10, 11, 1000, 1001] {
+            let items: Vec<Counted> =
+                (0..len).map(|_| Counted { value: rng.range(0, 1000), count: &count }).collect();
+            count.set(0);
+            let (min, max) = minmax(&items).unwrap();
+            let naive_min = items.iter().map(|c| c.value).min().unwrap();
+            let naive_max = items.iter().map(|c| c.value).max().unwrap();
+            assert_eq!((min.value, max.value), (naive_min, naive_max));
+            // 1 for the first pair, 3 for every pair after it
+            assert!(count.get() <= 3 * len / 2, "len {}: {} comparisons", len, count.get());
+        }
+    }
+}
@@ -0,0 +1,236 @@
+// a graph stored as adjacency lists: each node maps to the nodes its edges
+// lead to. neighbours keep the order their edges were added in, and nodes
+// the order they first appeared in, so every walk below is deterministic
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+pub struct Graph<N: Eq + Hash + Clone> {
+    edges: HashMap<N, Vec<N>>,
+    nodes: Vec<N>,
+}
+
+impl<N: Eq + Hash + Clone> Graph<N> {
+    pub fn new() -> Graph<N> {
+        Graph { edges: HashMap::new(), nodes: Vec::new() }
+    }
+
+    pub fn add_node(&mut self, node: N) {
+        if !self.edges.contains_key(&node) {
+            self.edges.insert(node.clone(), Vec::new());
+            self.nodes.push(node);
+        }
+    }
+
+    // an undirected edge is stored as one edge each way
+    pub fn add_edge(&mut self, a: N, b: N, directed: bool) {
+        self.add_node(a.clone());
+        self.add_node(b.clone());
+        self.edges.get_mut(&a).unwrap().push(b.clone());
+        if !directed {
+            self.edges.get_mut(&b).unwrap().push(a);
+        }
+    }
+
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    // empty for a node that isn't in the graph
+    pub fn neighbors(&self, node: &N) -> &[N] {
+        self.edges.get(node).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    // breadth first visit order, empty when start isn't in the graph
+    pub fn bfs(&self, start: &N) -> Vec<N> {
+        if !self.edges.contains_key(start) {
+            return Vec::new();
+        }
+        let mut seen = HashSet::from([start.clone()]);
+        let mut queue = VecDeque::from([start.clone()]);
+        let mut order = Vec::new();
+        while let Some(node) = queue.pop_front() {
+            for next in self.neighbors(&node) {
+                if seen.insert(next.clone()) {
+                    queue.push_back(next.clone());
+                }
+            }
+            order.push(node);
+        }
+        order
+    }
+
+    // fewest edges from `from` to `to`, both ends included. when several
+    // paths are as short, the one through the earlier added edges wins
+    pub fn shortest_path(&self, from: &N, to: &N) -> Option<Vec<N>> {
+        if !self.edges.contains_key(from) {
+            return None;
+        }
+        // each node points back to the node it was first reached from
+        let mut came_from: HashMap<N, Option<N>> = HashMap::from([(from.clone(), None)]);
+        let mut queue = VecDeque::from([from.clone()]);
+        while let Some(node) = queue.pop_front() {
+            if &node == to {
+                let mut path = vec![node];
+                while let Some(Some(prev)) = came_from.get(path.last().unwrap()) {
+                    path.push(prev.clone());
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for next in self.neighbors(&node) {
+                if !came_from.contains_key(next) {
+                    came_from.insert(next.clone(), Some(node.clone()));
+                    queue.push_back(next.clone());
+                }
+            }
+        }
+        None
+    }
+
+    // ignores edge direction, so a -> b counts as connecting a and b.
+    // an empty graph counts as connected
+    pub fn is_connected(&self) -> bool {
+        let Some(first) = self.nodes.first() else {
+            return true;
+        };
+        let mut both_ways: HashMap<&N, Vec<&N>> = HashMap::new();
+        for (from, tos) in &self.edges {
+            for to in tos {
+                both_ways.entry(from).or_default().push(to);
+                both_ways.entry(to).or_default().push(from);
+            }
+        }
+        let mut seen = HashSet::from([first]);
+        let mut stack = vec![first];
+        while let Some(node) = stack.pop() {
+            for next in both_ways.get(node).into_iter().flatten() {
+                if seen.insert(*next) {
+                    stack.push(next);
+                }
+            }
+        }
+        seen.len() == self.nodes.len()
+    }
+
+    // meant for directed graphs: an undirected edge is an edge both ways,
+    // which already makes a cycle of two
+    pub fn has_cycle(&self) -> bool {
+        // a node is on the path while its neighbours are still being walked
+        let mut on_path: HashSet<&N> = HashSet::new();
+        let mut done: HashSet<&N> = HashSet::new();
+        for start in &self.nodes {
+            if done.contains(start) {
+                continue;
+            }
+            // each entry is a node and how many of its neighbours were tried
+            let mut stack = vec![(start, 0)];
+            on_path.insert(start);
+            while let Some((node, tried)) = stack.pop() {
+                match self.neighbors(node).get(tried) {
+                    Some(next) => {
+                        stack.push((node, tried + 1));
+                        if on_path.contains(next) {
+                            return true;
+                        }
+                        if !done.contains(next) {
+                            on_path.insert(next);
+                            stack.push((next, 0));
+                        }
+                    }
+                    None => {
+                        on_path.remove(node);
+                        done.insert(node);
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+impl<N: Eq + Hash + Clone> Default for Graph<N> {
+    fn default() -> Graph<N> {
+        Graph::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&'static str, &'static str)], directed: bool) -> Graph<&'static str> {
+        let mut graph = Graph::new();
+        for &(a, b) in edges {
+            graph.add_edge(a, b, directed);
+        }
+        graph
+    }
+
+    #[test]
+    fn neighbors_and_bfs_keep_insertion_order() {
+        let g = graph(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d"), ("d", "e")], false);
+        assert_eq!(g.nodes(), ["a", "b", "c", "d", "e"]);
+        assert_eq!(g.neighbors(&"a"), ["b", "c"]);
+        assert_eq!(g.neighbors(&"d"), ["b", "c", "e"]);
+        assert_eq!(g.bfs(&"a"), ["a", "b", "c", "d", "e"]);
+        assert_eq!(g.bfs(&"e"), ["e", "d", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn a_disconnected_graph() {
+        let mut g = graph(&[("a", "b"), ("c", "d")], false);
+        assert!(!g.is_connected());
+        assert_eq!(g.bfs(&"a"), ["a", "b"]);
+        assert_eq!(g.shortest_path(&"a", &"d"), None);
+        g.add_node("lonely");
+        g.add_edge("b", "c", false);
+        assert!(!g.is_connected());
+        g.add_edge("lonely", "a", true);
+        // a one way edge still connects
+        assert!(g.is_connected());
+        assert!(Graph::<u8>::new().is_connected());
+    }
+
+    #[test]
+    fn directed_cycles() {
+        let mut g = graph(&[("a", "b"), ("b", "c"), ("a", "c")], true);
+        // a diamond is not a cycle
+        assert!(!g.has_cycle());
+        g.add_edge("c", "a", true);
+        assert!(g.has_cycle());
+        let self_loop = graph(&[("x", "x")], true);
+        assert!(self_loop.has_cycle());
+        // a cycle away from the first node is still found
+        let later = graph(&[("a", "b"), ("c", "d"), ("d", "e"), ("e", "c")], true);
+        assert!(later.has_cycle());
+        assert!(!Graph::<u8>::new().has_cycle());
+    }
+
+    #[test]
+    fn equal_length_paths_take_the_earlier_edge() {
+        // a-b-d and a-c-d are both two edges long
+        let g = graph(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d")], false);
+        assert_eq!(g.shortest_path(&"a", &"d"), Some(vec!["a", "b", "d"]));
+        let g = graph(&[("a", "c"), ("a", "b"), ("b", "d"), ("c", "d")], false);
+        assert_eq!(g.shortest_path(&"a", &"d"), Some(vec!["a", "c", "d"]));
+        assert_eq!(g.shortest_path(&"a", &"a"), Some(vec!["a"]));
+    }
+
+    #[test]
+    fn shortest_path_follows_direction() {
+        let g = graph(&[("a", "b"), ("b", "c"), ("c", "d"), ("a", "d")], true);
+        assert_eq!(g.shortest_path(&"a", &"d"), Some(vec!["a", "d"]));
+        assert_eq!(g.shortest_path(&"d", &"a"), None);
+        assert_eq!(g.shortest_path(&"b", &"d"), Some(vec!["b", "c", "d"]));
+    }
+
+    #[test]
+    fn a_missing_start_node() {
+        let g = graph(&[("a", "b")], false);
+        assert!(g.bfs(&"zzz").is_empty());
+        assert_eq!(g.shortest_path(&"zzz", &"a"), None);
+        assert_eq!(g.shortest_path(&"a", &"zzz"), None);
+        assert!(g.neighbors(&"zzz").is_empty());
+    }
+}
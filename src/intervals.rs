@@ -0,0 +1,188 @@
+// integer ranges from start to end: merging, coverage, point lookups and gaps.
+// lengths and gaps treat them as half-open, [start, end): len is end - start
+// and a gap starts exactly where a busy interval ends. contains_point is the
+// exception and counts both ends as inside. lengths are u64, so even
+// [i64::MIN, i64::MAX] has one
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Interval {
+    start: i64,
+    end: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntervalError {
+    EndBeforeStart { start: i64, end: i64 },
+}
+
+impl fmt::Display for IntervalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IntervalError::EndBeforeStart { start, end } => {
+                write!(f, "interval ends at {} before it starts at {}", end, start)
+            }
+        }
+    }
+}
+
+impl std::error::Error for IntervalError {}
+
+impl Interval {
+    // start == end is fine, that's a single point
+    pub fn new(start: i64, end: i64) -> Result<Interval, IntervalError> {
+        if end < start {
+            return Err(IntervalError::EndBeforeStart { start, end });
+        }
+        Ok(Interval { start, end })
+    }
+
+    pub fn start(&self) -> i64 {
+        self.start
+    }
+
+    pub fn end(&self) -> i64 {
+        self.end
+    }
+
+    // a single point has length 0
+    pub fn len(&self) -> u64 {
+        self.end.abs_diff(self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// sorted and non-overlapping. intervals that only touch, like [1, 3] and
+// [3, 5], are merged too
+pub fn merge(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.sort();
+    let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        match merged.last_mut() {
+            Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+// the length covered by at least one interval, overlaps count once. the
+// merged intervals don't overlap, so the sum is never more than u64::MAX
+pub fn total_covered(intervals: &[Interval]) -> u64 {
+    merge(intervals.to_vec()).iter().map(Interval::len).sum()
+}
+
+// ends count as inside
+pub fn contains_point(intervals: &[Interval], p: i64) -> bool {
+    let merged = merge(intervals.to_vec());
+    // the merged intervals are sorted, find the last one starting at or before p
+    let after = merged.partition_point(|interval| interval.start <= p);
+    after > 0 && p <= merged[after - 1].end
+}
+
+// the stretches of `window` that no interval covers. intervals that run
+// past the window are cut off at its edges
+pub fn gaps(intervals: &[Interval], window: Interval) -> Vec<Interval> {
+    let mut gaps = Vec::new();
+    let mut cursor = window.start;
+    for interval in merge(intervals.to_vec()) {
+        if interval.end < window.start {
+            continue;
+        }
+        if interval.start > window.end {
+            break;
+        }
+        if interval.start > cursor {
+            gaps.push(Interval { start: cursor, end: interval.start });
+        }
+        cursor = cursor.max(interval.end);
+    }
+    if cursor < window.end {
+        gaps.push(Interval { start: cursor, end: window.end });
+    }
+    gaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iv(start: i64, end: i64) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn end_before_start_is_an_error() {
+        assert_eq!(Interval::new(5, 4), Err(IntervalError::EndBeforeStart { start: 5, end: 4 }));
+        assert!(iv(3, 3).is_empty());
+    }
+
+    #[test]
+    fn merge_unsorted_overlapping() {
+        let merged = merge(vec![iv(8, 10), iv(1, 4), iv(2, 6), iv(15, 18), iv(9, 12)]);
+        assert_eq!(merged, vec![iv(1, 6), iv(8, 12), iv(15, 18)]);
+        assert_eq!(merge(Vec::new()), Vec::new());
+    }
+
+    #[test]
+    fn touching_endpoints_merge() {
+        assert_eq!(merge(vec![iv(3, 5), iv(1, 3)]), vec![iv(1, 5)]);
+        assert_eq!(merge(vec![iv(1, 2), iv(3, 4)]), vec![iv(1, 2), iv(3, 4)]);
+    }
+
+    #[test]
+    fn single_points() {
+        assert_eq!(merge(vec![iv(4, 4), iv(4, 4)]), vec![iv(4, 4)]);
+        assert_eq!(merge(vec![iv(1, 4), iv(4, 4)]), vec![iv(1, 4)]);
+        assert_eq!(total_covered(&[iv(4, 4)]), 0);
+        assert!(contains_point(&[iv(4, 4)], 4));
+    }
+
+    #[test]
+    fn covered_counts_overlaps_once() {
+        assert_eq!(total_covered(&[iv(0, 10), iv(5, 15), iv(20, 21)]), 16);
+        assert_eq!(total_covered(&[]), 0);
+    }
+
+    #[test]
+    fn the_widest_interval_has_a_length() {
+        let all = iv(i64::MIN, i64::MAX);
+        assert_eq!(all.len(), u64::MAX);
+        assert!(!all.is_empty());
+        assert_eq!(iv(-5, 5).len(), 10);
+        let halves = [iv(i64::MIN, 0), iv(0, i64::MAX), iv(-10, 10)];
+        assert_eq!(total_covered(&halves), u64::MAX);
+        assert_eq!(gaps(&[iv(-1, 1)], all), [iv(i64::MIN, -1), iv(1, i64::MAX)]);
+    }
+
+    #[test]
+    fn points_on_boundaries() {
+        let busy = [iv(10, 20), iv(1, 5)];
+        let expected =
+            [(0, false), (1, true), (5, true), (6, false), (10, true), (20, true), (21, false)];
+        for (p, inside) in expected {
+            assert_eq!(contains_point(&busy, p), inside, "{}", p);
+        }
+        assert!(!contains_point(&[], 0));
+    }
+
+    #[test]
+    fn gaps_inside_the_window() {
+        let busy = [iv(2, 4), iv(6, 7)];
+        assert_eq!(gaps(&busy, iv(0, 10)), vec![iv(0, 2), iv(4, 6), iv(7, 10)]);
+        assert_eq!(gaps(&[], iv(0, 10)), vec![iv(0, 10)]);
+    }
+
+    #[test]
+    fn gaps_with_intervals_past_the_window() {
+        let busy = [iv(-5, 2), iv(8, 30), iv(40, 50)];
+        assert_eq!(gaps(&busy, iv(0, 10)), vec![iv(2, 8)]);
+        assert_eq!(gaps(&[iv(-5, 20)], iv(0, 10)), Vec::new());
+        // entirely before or after the window
+        assert_eq!(gaps(&[iv(-9, -1), iv(11, 12)], iv(0, 10)), vec![iv(0, 10)]);
+    }
+}
@@ -0,0 +1,141 @@
+// running code that might panic without letting the panic end the program,
+// and getting the panic message back as a plain String
+
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe, UnwindSafe};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicInfo {
+    pub message: String,
+}
+
+impl fmt::Display for PanicInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "panicked: {}", self.message)
+    }
+}
+
+impl std::error::Error for PanicInfo {}
+
+// panic!("literal") carries a &str, panic!("{}", x) a String,
+// panic_any can carry anything at all
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        String::from("non-string panic payload")
+    }
+}
+
+// the panic hook is global, so while it is swapped out a panic on any other
+// thread is quiet too. fine for a checker that runs one thing at a time
+fn with_silent_hook<R>(f: impl FnOnce() -> R) -> R {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    panic::set_hook(previous);
+    result
+}
+
+// the usual "thread 'main' panicked at ..." line is not printed,
+// whatever hook was set before is back afterwards
+pub fn run_isolated<T>(f: impl FnOnce() -> T + UnwindSafe) -> Result<T, PanicInfo> {
+    with_silent_hook(|| panic::catch_unwind(f))
+        .map_err(|payload| PanicInfo { message: panic_message(payload.as_ref()) })
+}
+
+// one result per job, in order, a panicking job doesn't stop the rest
+pub fn run_all_isolated<T>(jobs: Vec<Box<dyn FnOnce() -> T>>) -> Vec<Result<T, PanicInfo>> {
+    with_silent_hook(|| {
+        jobs.into_iter()
+            .map(|job| {
+                panic::catch_unwind(AssertUnwindSafe(job))
+                    .map_err(|payload| PanicInfo { message: panic_message(payload.as_ref()) })
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::sync::Mutex;
+
+    // the hook is global, so the tests that swap it take turns
+    static HOOK: Mutex<()> = Mutex::new(());
+
+    thread_local! {
+        static HOOK_RAN: Cell<bool> = const { Cell::new(false) };
+    }
+
+    fn lock() -> std::sync::MutexGuard<'static, ()> {
+        HOOK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    #[test]
+    fn string_and_formatted_panics_give_their_message() {
+        let _guard = lock();
+        assert_eq!(run_isolated(|| 5), Ok(5));
+        let literal = run_isolated(|| -> i32 { panic!("plain message") });
+        assert_eq!(literal.unwrap_err().message, "plain message");
+        let n = 42;
+        let formatted = run_isolated(move || -> i32 { panic!("job {} failed", n) });
+        let info = formatted.unwrap_err();
+        assert_eq!(info.message, "job 42 failed");
+        assert_eq!(info.to_string(), "panicked: job 42 failed");
+    }
+
+    #[test]
+    fn a_non_string_payload_gets_the_fallback_message() {
+        let _guard = lock();
+        let info = run_isolated(|| panic::panic_any(42)).unwrap_err();
+        assert_eq!(info.message, "non-string panic payload");
+        assert_eq!(panic_message(&String::from("direct")), "direct");
+    }
+
+    #[test]
+    fn run_all_isolated_keeps_going_after_a_panic() {
+        let _guard = lock();
+        let jobs: Vec<Box<dyn FnOnce() -> i32>> = vec![
+            Box::new(|| 1),
+            Box::new(|| panic!("second")),
+            Box::new(|| 3),
+            Box::new(|| panic::panic_any(4u8)),
+        ];
+        let results = run_all_isolated(jobs);
+        let expected = [
+            Ok(1),
+            Err(PanicInfo { message: "second".to_string() }),
+            Ok(3),
+            Err(PanicInfo { message: "non-string panic payload".to_string() }),
+        ];
+        assert_eq!(results, expected);
+        assert!(run_all_isolated::<i32>(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn the_previous_hook_is_back_afterwards() {
+        let _guard = lock();
+        let original = panic::take_hook();
+        // other tests' threads may panic too, so only this thread's panics count
+        panic::set_hook(Box::new(|_| HOOK_RAN.with(|ran| ran.set(true))));
+
+        assert!(run_isolated(|| panic!("quiet")).is_err());
+        // the silent hook ran instead of ours
+        let ran_during = HOOK_RAN.with(|ran| ran.get());
+        let _ = run_all_isolated::<()>(vec![Box::new(|| panic!("quiet too"))]);
+        let ran_during_all = HOOK_RAN.with(|ran| ran.get());
+        // and ours is back for a panic outside of isolation
+        let _ = panic::catch_unwind(|| panic!("loud"));
+        let ran_after = HOOK_RAN.with(|ran| ran.get());
+
+        panic::set_hook(original);
+        assert!(!ran_during);
+        assert!(!ran_during_all);
+        assert!(ran_after);
+    }
+}
@@ -0,0 +1,139 @@
+// two extra iterator adapters, available on every iterator once IterExt is in scope
+
+pub trait IterExt: Iterator + Sized {
+    // Vecs of `size` items, the last one may be shorter.
+    // panics when size is 0, like slice::chunks
+    fn batched(self, size: usize) -> Batched<Self> {
+        assert!(size > 0, "batch size must be at least 1");
+        Batched { inner: self, size }
+    }
+
+    // every item with the one after it: a, b, c gives (a, b) and (b, c)
+    fn pairs(self) -> Pairs<Self>
+    where
+        Self::Item: Clone,
+    {
+        Pairs { inner: self, prev: None }
+    }
+}
+
+impl<I: Iterator> IterExt for I {}
+
+pub struct Batched<I> {
+    inner: I,
+    size: usize,
+}
+
+impl<I: Iterator> Iterator for Batched<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        let batch: Vec<I::Item> = self.inner.by_ref().take(self.size).collect();
+        if batch.is_empty() { None } else { Some(batch) }
+    }
+
+    // n items make n / size batches, rounded up
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.inner.size_hint();
+        (low.div_ceil(self.size), high.map(|h| h.div_ceil(self.size)))
+    }
+}
+
+pub struct Pairs<I: Iterator> {
+    inner: I,
+    // the item that starts the next pair, None before the first call
+    prev: Option<I::Item>,
+}
+
+impl<I: Iterator> Iterator for Pairs<I>
+where
+    I::Item: Clone,
+{
+    type Item = (I::Item, I::Item);
+
+    fn next(&mut self) -> Option<(I::Item, I::Item)> {
+        let prev = match self.prev.take() {
+            Some(prev) => prev,
+            None => self.inner.next()?,
+        };
+        let next = self.inner.next()?;
+        self.prev = Some(next.clone());
+        Some((prev, next))
+    }
+
+    // one pair fewer than the items left, counting the one held back
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.inner.size_hint();
+        match self.prev {
+            Some(_) => (low, high),
+            None => (low.saturating_sub(1), high.map(|h| h.saturating_sub(1))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batches_of_an_exact_multiple() {
+        let batches: Vec<Vec<i32>> = (1..=6).batched(3).collect();
+        assert_eq!(batches, [vec![1, 2, 3], vec![4, 5, 6]]);
+        let singles: Vec<Vec<i32>> = (1..=3).batched(1).collect();
+        assert_eq!(singles, [vec![1], vec![2], vec![3]]);
+    }
+
+    #[test]
+    fn the_last_batch_can_be_short() {
+        let batches: Vec<Vec<i32>> = (1..=7).batched(3).collect();
+        assert_eq!(batches, [vec![1, 2, 3], vec![4, 5, 6], vec![7]]);
+        let one: Vec<Vec<i32>> = (1..=2).batched(10).collect();
+        assert_eq!(one, [vec![1, 2]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "batch size must be at least 1")]
+    fn a_batch_size_of_zero_panics() {
+        let _ = (1..=3).batched(0);
+    }
+
+    #[test]
+    fn empty_iterators_give_nothing() {
+        assert_eq!(std::iter::empty::<i32>().batched(4).next(), None);
+        assert_eq!(std::iter::empty::<i32>().pairs().next(), None);
+    }
+
+    #[test]
+    fn pairs_of_zero_one_two_and_more_items() {
+        assert_eq!(Vec::<i32>::new().into_iter().pairs().count(), 0);
+        assert_eq!([1].into_iter().pairs().count(), 0);
+        assert_eq!([1, 2].into_iter().pairs().collect::<Vec<_>>(), [(1, 2)]);
+        let words: Vec<(&str, &str)> = ["a", "b", "c", "d"].into_iter().pairs().collect();
+        assert_eq!(words, [("a", "b"), ("b", "c"), ("c", "d")]);
+    }
+
+    #[test]
+    fn size_hints_are_exact_for_exact_inputs() {
+        for len in 0..12usize {
+            for size in 1..5 {
+                let mut batched = (0..len).batched(size);
+                let mut left = len.div_ceil(size);
+                assert_eq!(batched.size_hint(), (left, Some(left)));
+                while batched.next().is_some() {
+                    left -= 1;
+                    let hint = batched.size_hint();
+                    assert_eq!(hint, (left, Some(left)), "len {} size {}", len, size);
+                }
+            }
+            let mut pairs = (0..len).pairs();
+            let mut left = len.saturating_sub(1);
+            assert_eq!(pairs.size_hint(), (left, Some(left)));
+            while pairs.next().is_some() {
+                left -= 1;
+                assert_eq!(pairs.size_hint(), (left, Some(left)), "len {}", len);
+            }
+        }
+        // a filter only knows its upper bound
+        assert_eq!((0..10).filter(|n| n % 2 == 0).batched(3).size_hint(), (0, Some(4)));
+    }
+}
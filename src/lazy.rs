@@ -0,0 +1,137 @@
+// a value that is worked out the first time someone asks for it,
+// safe to put in a static and to race for from many threads
+
+use std::sync::{Mutex, OnceLock};
+
+pub struct Lazy<T> {
+    cell: OnceLock<T>,
+    init: fn() -> T,
+    // only the fallible path needs this, so two failing inits don't run at once
+    try_lock: Mutex<()>,
+}
+
+impl<T> Lazy<T> {
+    // const, so it works for statics
+    pub const fn new(init: fn() -> T) -> Lazy<T> {
+        Lazy { cell: OnceLock::new(), init, try_lock: Mutex::new(()) }
+    }
+
+    // the first caller runs init, everyone racing with it waits and gets the
+    // same value, init never runs a second time
+    pub fn get(&self) -> &T {
+        self.cell.get_or_init(self.init)
+    }
+
+    // the value if something already set it
+    pub fn try_get(&self) -> Option<&T> {
+        self.cell.get()
+    }
+
+    // like get but with a different initializer that can fail. an Err leaves
+    // the Lazy empty, so the next call tries again
+    pub fn get_or_try_init<E>(&self, f: impl FnOnce() -> Result<T, E>) -> Result<&T, E> {
+        if let Some(value) = self.cell.get() {
+            return Ok(value);
+        }
+        let _guard = self.try_lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        // someone may have finished while we waited for the lock
+        if let Some(value) = self.cell.get() {
+            return Ok(value);
+        }
+        let value = f()?;
+        // a get() racing with us may still win, then our value is dropped
+        Ok(self.cell.get_or_init(|| value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Barrier;
+    use std::thread;
+    use std::time::Duration;
+
+    // each test has its own counter, init is a plain fn and can't capture one
+    static RACE_INITS: AtomicUsize = AtomicUsize::new(0);
+
+    fn slow_init() -> usize {
+        RACE_INITS.fetch_add(1, Ordering::SeqCst);
+        // long enough for the other threads to pile up behind it
+        thread::sleep(Duration::from_millis(20));
+        42
+    }
+
+    #[test]
+    fn sixteen_racing_threads_run_init_once() {
+        static VALUE: Lazy<usize> = Lazy::new(slow_init);
+        let barrier = Barrier::new(16);
+        let seen: Vec<usize> = thread::scope(|s| {
+            let handles: Vec<_> = (0..16)
+                .map(|_| {
+                    s.spawn(|| {
+                        barrier.wait();
+                        *VALUE.get()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert_eq!(seen, [42; 16]);
+        assert_eq!(RACE_INITS.load(Ordering::SeqCst), 1);
+        assert_eq!(VALUE.try_get(), Some(&42));
+    }
+
+    static UNUSED_INITS: AtomicUsize = AtomicUsize::new(0);
+
+    fn unused_init() -> String {
+        UNUSED_INITS.fetch_add(1, Ordering::SeqCst);
+        String::from("from new")
+    }
+
+    #[test]
+    fn a_failed_init_can_be_retried() {
+        let lazy = Lazy::new(unused_init);
+        assert_eq!(lazy.try_get(), None);
+        let first: Result<&String, &str> = lazy.get_or_try_init(|| Err("not yet"));
+        assert_eq!(first, Err("not yet"));
+        assert_eq!(lazy.try_get(), None);
+        let second: Result<&String, &str> = lazy.get_or_try_init(|| Ok(String::from("second")));
+        assert_eq!(second.map(String::as_str), Ok("second"));
+        // set now, neither initializer runs again
+        let third: Result<&String, &str> = lazy.get_or_try_init(|| panic!("must not run"));
+        assert_eq!(third.map(String::as_str), Ok("second"));
+        assert_eq!(lazy.get(), "second");
+        assert_eq!(UNUSED_INITS.load(Ordering::SeqCst), 0);
+    }
+
+    static PLAIN_INITS: AtomicUsize = AtomicUsize::new(0);
+
+    fn counted_init() -> u8 {
+        PLAIN_INITS.fetch_add(1, Ordering::SeqCst);
+        7
+    }
+
+    #[test]
+    fn racing_fallible_inits_succeed_once() {
+        let lazy = Lazy::new(counted_init);
+        let tries = AtomicUsize::new(0);
+        let barrier = Barrier::new(16);
+        thread::scope(|s| {
+            for _ in 0..16 {
+                s.spawn(|| {
+                    barrier.wait();
+                    let value = lazy.get_or_try_init(|| {
+                        tries.fetch_add(1, Ordering::SeqCst);
+                        thread::sleep(Duration::from_millis(5));
+                        Ok::<u8, ()>(9)
+                    });
+                    assert_eq!(value, Ok(&9));
+                });
+            }
+        });
+        assert_eq!(tries.load(Ordering::SeqCst), 1);
+        assert_eq!(*lazy.get(), 9);
+        assert_eq!(PLAIN_INITS.load(Ordering::SeqCst), 0);
+    }
+}
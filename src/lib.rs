@@ -0,0 +1,57 @@
+// code shared by more than one bin lives here, the bins use it as rust_tut::...
+
+pub mod area;
+pub mod bounded_pipeline;
+pub mod bst;
+pub mod cache;
+pub mod cancel;
+pub mod collections_ext;
+pub mod config;
+pub mod container;
+pub mod contention;
+pub mod counter;
+pub mod datetime;
+pub mod error;
+pub mod exit_code;
+pub mod extremes;
+pub mod graph;
+pub mod intervals;
+pub mod isolate;
+pub mod iter_ext;
+pub mod lazy;
+pub mod line_reader;
+pub mod linked_list;
+pub mod locking;
+pub mod logger;
+pub mod lru;
+pub mod matrix;
+pub mod multimap;
+pub mod pairs;
+pub mod parallel;
+pub mod phased;
+pub mod point;
+pub mod rate_limiter;
+pub mod rect;
+pub mod report;
+pub mod retry;
+pub mod ring_buffer;
+pub mod rng;
+pub mod row;
+pub mod rw_cache;
+pub mod sample_data;
+pub mod scheduler;
+pub mod set_ops;
+pub mod shape;
+pub mod shape_visitors;
+pub mod sorting;
+pub mod sparse;
+pub mod stopwatch;
+pub mod str_ext;
+pub mod style;
+pub mod summary;
+pub mod text;
+pub mod thread_pool;
+pub mod ticker;
+pub mod trie;
+pub mod user;
+pub mod validate;
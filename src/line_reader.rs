@@ -0,0 +1,131 @@
+// reading a file on a background thread and handing over one line at a time,
+// so the other end can start working before the whole file is in
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver};
+use std::thread::{self, JoinHandle};
+
+#[derive(Debug)]
+pub enum FileReadError {
+    Open { path: PathBuf, source: io::Error },
+    // `line` is the 1-based line that could not be read
+    Read { path: PathBuf, line: usize, source: io::Error },
+}
+
+impl fmt::Display for FileReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FileReadError::Open { path, .. } => write!(f, "could not open {}", path.display()),
+            FileReadError::Read { path, line, .. } => {
+                write!(f, "could not read line {} of {}", line, path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for FileReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FileReadError::Open { source, .. } | FileReadError::Read { source, .. } => Some(source),
+        }
+    }
+}
+
+// the stream ends when the reader drops its sender: after the last line,
+// or right after the one Err if opening or reading fails.
+// if the receiver goes away first the reader just stops
+pub fn spawn_line_reader(
+    path: PathBuf,
+) -> (Receiver<Result<String, FileReadError>>, JoinHandle<()>) {
+    let (sender, receiver) = channel();
+    let handle = thread::spawn(move || {
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(source) => {
+                let _ = sender.send(Err(FileReadError::Open { path, source }));
+                return;
+            }
+        };
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let item = line.map_err(|source| FileReadError::Read {
+                path: path.clone(),
+                line: i + 1,
+                source,
+            });
+            let failed = item.is_err();
+            if sender.send(item).is_err() || failed {
+                return;
+            }
+        }
+    });
+    (receiver, handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+        let name = format!("line_reader_{}_{}", std::process::id(), name);
+        let path = std::env::temp_dir().join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn every_line_arrives_in_order() {
+        let text: String = (1..=500).map(|n| format!("line {}\n", n)).collect();
+        let path = temp_file("order", text.as_bytes());
+        let (lines, handle) = spawn_line_reader(path.clone());
+        let got: Vec<String> = lines.iter().map(Result::unwrap).collect();
+        handle.join().unwrap();
+        let expected: Vec<String> = (1..=500).map(|n| format!("line {}", n)).collect();
+        assert_eq!(got, expected);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn a_missing_file_gives_one_err_then_closes() {
+        let path = std::env::temp_dir().join("line_reader_there_is_no_such_file.txt");
+        let (lines, handle) = spawn_line_reader(path.clone());
+        let items: Vec<_> = lines.iter().collect();
+        handle.join().unwrap();
+        assert_eq!(items.len(), 1);
+        match &items[0] {
+            Err(err @ FileReadError::Open { .. }) => {
+                assert_eq!(err.to_string(), format!("could not open {}", path.display()));
+            }
+            other => panic!("expected an Open error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn a_bad_line_ends_the_stream_with_its_number() {
+        // line 3 is not UTF-8
+        let path = temp_file("bad_utf8", b"one\ntwo\n\xff\xfe\nfour\n");
+        let (lines, handle) = spawn_line_reader(path.clone());
+        let items: Vec<_> = lines.iter().collect();
+        handle.join().unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].as_deref().ok(), Some("one"));
+        assert_eq!(items[1].as_deref().ok(), Some("two"));
+        assert!(matches!(items[2], Err(FileReadError::Read { line: 3, .. })));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn dropping_the_receiver_lets_the_reader_stop() {
+        let text = "x\n".repeat(100_000);
+        let path = temp_file("early_drop", text.as_bytes());
+        let (lines, handle) = spawn_line_reader(path.clone());
+        assert_eq!(lines.recv().unwrap().unwrap(), "x");
+        drop(lines);
+        // the next send fails and the thread returns instead of hanging
+        handle.join().unwrap();
+        fs::remove_file(path).unwrap();
+    }
+}
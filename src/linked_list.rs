@@ -0,0 +1,248 @@
+// a singly linked list in the "too many lists" style: every node owns the
+// next one through an Option<Box<Node>>
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    next: Link<T>,
+}
+
+pub struct LinkedList<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+impl<T> LinkedList<T> {
+    pub fn new() -> LinkedList<T> {
+        LinkedList { head: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let next = self.head.take();
+        self.head = Some(Box::new(Node { value, next }));
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let node = self.head.take()?;
+        self.head = node.next;
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.as_mut().map(|node| &mut node.value)
+    }
+
+    // turns every next pointer around, no node is copied or reallocated
+    pub fn reverse(&mut self) {
+        let mut reversed: Link<T> = None;
+        let mut rest = self.head.take();
+        while let Some(mut node) = rest {
+            rest = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+        self.head = reversed;
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref() }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { next: self.head.as_deref_mut() }
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> LinkedList<T> {
+        LinkedList::new()
+    }
+}
+
+// the default drop would recurse once per node and overflow the stack
+// on a long list, so unlink the nodes one at a time instead
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        let mut link = self.head.take();
+        while let Some(mut node) = link {
+            link = node.next.take();
+        }
+    }
+}
+
+pub struct IntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.next?;
+        self.next = node.next.as_deref();
+        Some(&node.value)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        // take() so the iterator gives up its borrow of this node
+        let node = self.next.take()?;
+        self.next = node.next.as_deref_mut();
+        Some(&mut node.value)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list_of(values: &[i32]) -> LinkedList<i32> {
+        let mut list = LinkedList::new();
+        for &value in values.iter().rev() {
+            list.push_front(value);
+        }
+        list
+    }
+
+    #[test]
+    fn push_pop_and_peek() {
+        let mut list = LinkedList::new();
+        assert!(list.is_empty());
+        assert_eq!(list.peek(), None);
+        list.push_front(1);
+        list.push_front(2);
+        assert_eq!(list.peek(), Some(&2));
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+    }
+
+    #[test]
+    fn peek_mut_changes_the_head() {
+        let mut list = list_of(&[1, 2]);
+        if let Some(head) = list.peek_mut() {
+            *head *= 10;
+        }
+        assert_eq!(list.peek(), Some(&10));
+        assert_eq!(list.pop_front(), Some(10));
+        assert_eq!(LinkedList::<i32>::new().peek_mut(), None);
+    }
+
+    #[test]
+    fn all_three_iterators() {
+        let mut list = list_of(&[1, 2, 3]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+        let mut sum = 0;
+        for value in &list {
+            sum += value;
+        }
+        assert_eq!(sum, 6);
+        for value in list.iter_mut() {
+            *value += 1;
+        }
+        for value in &mut list {
+            *value *= 2;
+        }
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), [4, 6, 8]);
+        assert_eq!(LinkedList::<i32>::new().iter().next(), None);
+    }
+
+    #[test]
+    fn into_iter_can_stop_early() {
+        let mut iter = list_of(&[1, 2, 3]).into_iter();
+        assert_eq!(iter.next(), Some(1));
+        // the two left are dropped with the iterator
+        drop(iter);
+    }
+
+    #[test]
+    fn reverse_empty_single_and_long() {
+        let mut empty = LinkedList::<i32>::new();
+        empty.reverse();
+        assert!(empty.is_empty());
+
+        let mut single = list_of(&[7]);
+        single.reverse();
+        assert_eq!(single.iter().copied().collect::<Vec<_>>(), [7]);
+
+        let values: Vec<i32> = (0..1000).collect();
+        let mut long = list_of(&values);
+        long.reverse();
+        assert_eq!(long.len(), 1000);
+        assert!(long.iter().copied().eq(values.iter().copied().rev()));
+        long.reverse();
+        assert!(long.iter().copied().eq(values.iter().copied()));
+    }
+
+    #[test]
+    fn dropping_a_100_000_node_list_does_not_overflow() {
+        let mut list = LinkedList::new();
+        for value in 0..100_000 {
+            list.push_front(value);
+        }
+        assert_eq!(list.len(), 100_000);
+        drop(list);
+    }
+}
@@ -0,0 +1,163 @@
+// taking a lock without waiting forever, and the classic two-lock deadlock
+
+use std::fmt;
+use std::sync::{Barrier, Mutex, MutexGuard, TryLockError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::rng::Rng;
+
+// polls try_lock every `poll` until it works or `timeout` has passed.
+// a poisoned lock is still handed out, the data is usually fine
+pub fn try_lock_timeout<T>(
+    m: &Mutex<T>,
+    timeout: Duration,
+    poll: Duration,
+) -> Option<MutexGuard<'_, T>> {
+    let start = Instant::now();
+    loop {
+        match m.try_lock() {
+            Ok(guard) => return Some(guard),
+            Err(TryLockError::Poisoned(err)) => return Some(err.into_inner()),
+            Err(TryLockError::WouldBlock) => {
+                if start.elapsed() >= timeout {
+                    return None;
+                }
+                thread::sleep(poll);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadlockOutcome {
+    // both threads got both locks, after this many back offs in total
+    Completed { retries: u32 },
+    DeadlockDetected,
+}
+
+impl fmt::Display for DeadlockOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeadlockOutcome::Completed { retries } => {
+                write!(f, "completed after {} retries", retries)
+            }
+            DeadlockOutcome::DeadlockDetected => write!(f, "deadlock detected"),
+        }
+    }
+}
+
+const LOCK_TIMEOUT: Duration = Duration::from_millis(50);
+const POLL: Duration = Duration::from_millis(1);
+
+// thread one takes a then b, thread two takes b then a. a barrier makes sure
+// both hold their first lock before reaching for the second, so the first
+// try always collides. without `resolve` that collision is reported; with it
+// each thread lets go, sleeps a random bit and tries again
+pub fn deadlock_demo(resolve: bool) -> DeadlockOutcome {
+    let a = Mutex::new(0u32);
+    let b = Mutex::new(0u32);
+    let barrier = Barrier::new(2);
+
+    let results: Vec<Option<u32>> = thread::scope(|s| {
+        let handles = [(&a, &b, 1), (&b, &a, 2)].map(|(first, second, seed)| {
+            let barrier = &barrier;
+            s.spawn(move || {
+                let mut rng = Rng::new(seed);
+                let mut retries = 0;
+                loop {
+                    let mut held = first.lock().unwrap();
+                    if retries == 0 {
+                        barrier.wait();
+                    }
+                    if let Some(mut other) = try_lock_timeout(second, LOCK_TIMEOUT, POLL) {
+                        *held += 1;
+                        *other += 1;
+                        return Some(retries);
+                    }
+                    if !resolve {
+                        return None;
+                    }
+                    drop(held);
+                    retries += 1;
+                    thread::sleep(Duration::from_millis(rng.range(1, 20) as u64));
+                }
+            })
+        });
+        handles.map(|h| h.join().unwrap()).into_iter().collect()
+    });
+
+    match results.iter().copied().sum::<Option<u32>>() {
+        Some(retries) => DeadlockOutcome::Completed { retries },
+        None => DeadlockOutcome::DeadlockDetected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn an_uncontended_lock_is_taken_straight_away() {
+        let m = Mutex::new(5);
+        let start = Instant::now();
+        let guard = try_lock_timeout(&m, Duration::from_secs(5), Duration::from_secs(1));
+        assert_eq!(guard.as_deref(), Some(&5));
+        // the first try_lock works, so there was no poll sleep
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+
+    #[test]
+    fn a_lock_held_past_the_timeout_gives_none() {
+        let m = Mutex::new(0);
+        let (locked, wait_for_lock) = channel();
+        let (release, wait_for_release) = channel::<()>();
+        thread::scope(|s| {
+            let m = &m;
+            s.spawn(move || {
+                let _guard = m.lock().unwrap();
+                locked.send(()).unwrap();
+                let _ = wait_for_release.recv();
+            });
+            wait_for_lock.recv().unwrap();
+            let start = Instant::now();
+            let timeout = Duration::from_millis(30);
+            assert!(try_lock_timeout(m, timeout, Duration::from_millis(2)).is_none());
+            assert!(start.elapsed() >= timeout);
+            release.send(()).unwrap();
+        });
+        // free again once the other thread is done
+        assert!(try_lock_timeout(&m, Duration::ZERO, POLL).is_some());
+    }
+
+    #[test]
+    fn a_poisoned_lock_is_still_handed_out() {
+        let m = Mutex::new(1);
+        let _ = thread::scope(|s| {
+            s.spawn(|| {
+                let _guard = m.lock().unwrap();
+                panic!("poison it");
+            })
+            .join()
+        });
+        assert!(m.is_poisoned());
+        assert_eq!(try_lock_timeout(&m, Duration::ZERO, POLL).as_deref(), Some(&1));
+    }
+
+    #[test]
+    fn the_resolving_demo_completes_in_time() {
+        let start = Instant::now();
+        let outcome = deadlock_demo(true);
+        assert!(matches!(outcome, DeadlockOutcome::Completed { retries } if retries >= 1));
+        assert!(start.elapsed() < Duration::from_secs(10), "took {:?}", start.elapsed());
+    }
+
+    #[test]
+    fn without_resolving_the_deadlock_is_reported() {
+        assert_eq!(deadlock_demo(false), DeadlockOutcome::DeadlockDetected);
+        assert_eq!(DeadlockOutcome::DeadlockDetected.to_string(), "deadlock detected");
+        let done = DeadlockOutcome::Completed { retries: 2 };
+        assert_eq!(done.to_string(), "completed after 2 retries");
+    }
+}
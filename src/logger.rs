@@ -0,0 +1,284 @@
+// a logger that hands every line to one writer thread over a channel,
+// so logging from many threads never interleaves half lines and never
+// waits on a slow sink
+
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::mpsc::{channel, Sender};
+use std::thread::{self, JoinHandle};
+
+use chrono::{SecondsFormat, Utc};
+
+// most severe first, a minimum level of Info lets Error, Warn and Info through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Level {
+    fn from_u8(value: u8) -> Level {
+        match value {
+            0 => Level::Error,
+            1 => Level::Warn,
+            2 => Level::Info,
+            _ => Level::Debug,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let label = match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+struct Record {
+    level: Level,
+    // taken when log was called, not when the line got written
+    timestamp: String,
+    message: String,
+}
+
+pub struct Logger {
+    sender: Option<Sender<Record>>,
+    writer: Option<JoinHandle<io::Result<()>>>,
+    min_level: AtomicU8,
+}
+
+impl Logger {
+    // lines look like "INFO 2025-01-01T10:00:00.000Z the message"
+    pub fn new(mut sink: Box<dyn Write + Send>, min_level: Level) -> Logger {
+        let (sender, receiver) = channel::<Record>();
+        let writer = thread::spawn(move || {
+            // keeps draining after a write error so senders never notice,
+            // the first error is what shutdown reports
+            let mut result = Ok(());
+            for record in receiver {
+                let written =
+                    writeln!(sink, "{} {} {}", record.level, record.timestamp, record.message);
+                if result.is_ok() {
+                    result = written;
+                }
+            }
+            result.and(sink.flush())
+        });
+        Logger {
+            sender: Some(sender),
+            writer: Some(writer),
+            min_level: AtomicU8::new(min_level as u8),
+        }
+    }
+
+    pub fn stderr(min_level: Level) -> Logger {
+        Logger::new(Box::new(io::stderr()), min_level)
+    }
+
+    pub fn set_level(&self, level: Level) {
+        self.min_level.store(level as u8, Ordering::Relaxed);
+    }
+
+    pub fn level(&self) -> Level {
+        Level::from_u8(self.min_level.load(Ordering::Relaxed))
+    }
+
+    // anything less severe than the minimum level is dropped right here
+    pub fn log(&self, level: Level, msg: &str) {
+        if level > self.level() {
+            return;
+        }
+        let record = Record {
+            level,
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            message: msg.to_string(),
+        };
+        if let Some(sender) = &self.sender {
+            // the writer only stops once the sender is gone, so this can't fail
+            let _ = sender.send(record);
+        }
+    }
+
+    // every message logged before this call is written and flushed first
+    pub fn shutdown(mut self) -> io::Result<()> {
+        self.finish()
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        // dropping the sender ends the writer's loop once the queue is empty
+        self.sender.take();
+        match self.writer.take() {
+            Some(writer) => {
+                writer.join().unwrap_or_else(|_| Err(io::Error::other("log writer panicked")))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+// a logger that is simply dropped still writes out everything queued
+impl Drop for Logger {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+// log_info!(logger, "loaded {} files", n)
+#[macro_export]
+macro_rules! log_info {
+    ($logger:expr, $($arg:tt)*) => {
+        $logger.log($crate::logger::Level::Info, &format!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! log_error {
+    ($logger:expr, $($arg:tt)*) => {
+        $logger.log($crate::logger::Level::Error, &format!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    // a Vec<u8> the test can still read after the logger took the sink
+    #[derive(Clone, Default)]
+    struct SharedSink(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedSink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SharedSink {
+        fn lines(&self) -> Vec<String> {
+            let bytes = self.0.lock().unwrap().clone();
+            String::from_utf8(bytes).unwrap().lines().map(String::from).collect()
+        }
+    }
+
+    // "LEVEL timestamp message" split into its three parts
+    fn parse(line: &str) -> (String, String, String) {
+        let mut parts = line.splitn(3, ' ');
+        let level = parts.next().unwrap().to_string();
+        let timestamp = parts.next().expect("no timestamp").to_string();
+        let message = parts.next().expect("no message").to_string();
+        (level, timestamp, message)
+    }
+
+    #[test]
+    fn a_thousand_messages_from_four_threads_all_arrive() {
+        let sink = SharedSink::default();
+        let logger = Logger::new(Box::new(sink.clone()), Level::Debug);
+        thread::scope(|s| {
+            for t in 0..4 {
+                let logger = &logger;
+                s.spawn(move || {
+                    for n in 0..250 {
+                        log_info!(logger, "thread {} message {}", t, n);
+                    }
+                });
+            }
+        });
+        logger.shutdown().unwrap();
+
+        let lines = sink.lines();
+        assert_eq!(lines.len(), 1000);
+        let mut seen = Vec::new();
+        for line in &lines {
+            let (level, timestamp, message) = parse(line);
+            assert_eq!(level, "INFO");
+            assert!(chrono::DateTime::parse_from_rfc3339(&timestamp).is_ok(), "{}", line);
+            assert!(message.starts_with("thread "), "{}", line);
+            seen.push(message);
+        }
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), 1000);
+    }
+
+    #[test]
+    fn messages_below_the_minimum_level_are_dropped() {
+        let sink = SharedSink::default();
+        let logger = Logger::new(Box::new(sink.clone()), Level::Warn);
+        logger.log(Level::Debug, "debug");
+        logger.log(Level::Info, "info");
+        logger.log(Level::Warn, "warn");
+        log_error!(logger, "error {}", 1);
+        logger.set_level(Level::Debug);
+        assert_eq!(logger.level(), Level::Debug);
+        logger.log(Level::Debug, "debug after");
+        logger.set_level(Level::Error);
+        logger.log(Level::Warn, "warn after");
+        logger.shutdown().unwrap();
+
+        let got: Vec<(String, String)> = sink
+            .lines()
+            .iter()
+            .map(|line| {
+                let (level, _, message) = parse(line);
+                (level, message)
+            })
+            .collect();
+        let expected = [("WARN", "warn"), ("ERROR", "error 1"), ("DEBUG", "debug after")];
+        assert_eq!(got, expected.map(|(l, m)| (l.to_string(), m.to_string())));
+    }
+
+    #[test]
+    fn dropping_the_logger_still_writes_everything() {
+        let sink = SharedSink::default();
+        {
+            let logger = Logger::new(Box::new(sink.clone()), Level::Info);
+            for n in 0..100 {
+                logger.log(Level::Error, &format!("n {}", n));
+            }
+        }
+        assert_eq!(sink.lines().len(), 100);
+    }
+
+    struct BrokenSink;
+
+    impl Write for BrokenSink {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn shutdown_reports_a_write_error() {
+        let logger = Logger::new(Box::new(BrokenSink), Level::Info);
+        logger.log(Level::Info, "lost");
+        logger.log(Level::Info, "also lost");
+        let err = logger.shutdown().unwrap_err();
+        assert_eq!(err.to_string(), "disk full");
+    }
+
+    #[test]
+    fn level_order_and_labels() {
+        assert!(Level::Error < Level::Warn && Level::Info < Level::Debug);
+        let labels = [Level::Error, Level::Warn, Level::Info, Level::Debug].map(|l| l.to_string());
+        assert_eq!(labels, ["ERROR", "WARN", "INFO", "DEBUG"]);
+        for level in [Level::Error, Level::Warn, Level::Info, Level::Debug] {
+            assert_eq!(Level::from_u8(level as u8), level);
+        }
+    }
+}
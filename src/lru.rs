@@ -0,0 +1,164 @@
+// a least recently used cache: once it is full, putting a new key
+// throws out the key that was used longest ago
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LruError {
+    ZeroCapacity,
+}
+
+impl fmt::Display for LruError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LruError::ZeroCapacity => write!(f, "an LRU cache needs room for at least one entry"),
+        }
+    }
+}
+
+impl std::error::Error for LruError {}
+
+pub struct LruCache<K: Hash + Eq + Clone, V> {
+    capacity: usize,
+    values: HashMap<K, V>,
+    // least recently used at the front, most recent at the back
+    order: VecDeque<K>,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Result<LruCache<K, V>, LruError> {
+        if capacity == 0 {
+            return Err(LruError::ZeroCapacity);
+        }
+        Ok(LruCache { capacity, values: HashMap::new(), order: VecDeque::new() })
+    }
+
+    // moving a key to the back is a linear scan, fine for small caches
+    fn promote(&mut self, key: &K) {
+        if let Some(pos) = self.order.iter().position(|k| k == key) {
+            let key = self.order.remove(pos).unwrap();
+            self.order.push_back(key);
+        }
+    }
+
+    // a hit makes the key the most recently used
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        if self.values.contains_key(key) {
+            self.promote(key);
+        }
+        self.values.get(key)
+    }
+
+    // returns the value that had to be evicted to make room, if any.
+    // putting a key that is already there replaces its value and never evicts
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if self.values.contains_key(&key) {
+            self.promote(&key);
+            self.values.insert(key, value);
+            return None;
+        }
+        let mut evicted = None;
+        if self.values.len() == self.capacity {
+            let oldest = self.order.pop_front().unwrap();
+            evicted = self.values.remove(&oldest);
+        }
+        self.order.push_back(key.clone());
+        self.values.insert(key, value);
+        evicted
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.order.clear();
+    }
+
+    // does not count as a use
+    pub fn contains(&self, key: &K) -> bool {
+        self.values.contains_key(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_capacity_is_rejected() {
+        assert_eq!(LruCache::<u32, u32>::new(0).err(), Some(LruError::ZeroCapacity));
+        assert_eq!(
+            LruError::ZeroCapacity.to_string(),
+            "an LRU cache needs room for at least one entry"
+        );
+        assert_eq!(LruCache::<u32, u32>::new(1).unwrap().capacity(), 1);
+    }
+
+    #[test]
+    fn evicts_the_least_recently_used_after_gets_and_puts() {
+        let mut cache = LruCache::new(3).unwrap();
+        assert_eq!(cache.put("a", 1), None);
+        assert_eq!(cache.put("b", 2), None);
+        assert_eq!(cache.put("c", 3), None);
+        // a is used again, so b is now the oldest
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.put("d", 4), Some(2));
+        assert!(!cache.contains(&"b"));
+        // c is the oldest now, then a
+        assert_eq!(cache.put("e", 5), Some(3));
+        assert_eq!(cache.get(&"d"), Some(&4));
+        assert_eq!(cache.put("f", 6), Some(1));
+        assert_eq!(cache.len(), 3);
+        for key in ["d", "e", "f"] {
+            assert!(cache.contains(&key), "{} should still be there", key);
+        }
+    }
+
+    #[test]
+    fn updating_a_key_replaces_it_without_evicting() {
+        let mut cache = LruCache::new(2).unwrap();
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.put("a", 10), None);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&"a"), Some(&10));
+        // the update made a the most recent, so b goes first
+        assert_eq!(cache.put("c", 3), Some(2));
+        assert_eq!(cache.get(&"a"), Some(&10));
+    }
+
+    #[test]
+    fn a_miss_and_contains_do_not_change_the_order() {
+        let mut cache = LruCache::new(2).unwrap();
+        cache.put(1, "one");
+        cache.put(2, "two");
+        assert_eq!(cache.get(&3), None);
+        assert!(cache.contains(&1));
+        assert_eq!(cache.put(3, "three"), Some("one"));
+    }
+
+    #[test]
+    fn a_capacity_of_one_and_clear() {
+        let mut cache = LruCache::new(1).unwrap();
+        assert!(cache.is_empty());
+        assert_eq!(cache.put('x', 1), None);
+        assert_eq!(cache.put('y', 2), Some(1));
+        assert_eq!(cache.get(&'x'), None);
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.put('z', 3), None);
+        assert_eq!(cache.len(), 1);
+    }
+}
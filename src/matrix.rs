@@ -0,0 +1,236 @@
+// a matrix whose size is only known at runtime. the values live in one flat
+// Vec, row after row, and (row, col) is found at row * cols + col.
+// a Vec<Vec<f64>> would put every row in its own allocation somewhere on the
+// heap, the flat layout keeps neighbouring values next to each other in memory
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynMatrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixError {
+    // row `row` has `got` values when the first row has `expected`
+    Ragged { row: usize, expected: usize, got: usize },
+    OutOfBounds { row: usize, col: usize, rows: usize, cols: usize },
+    // the (rows, cols) of both sides
+    DimensionMismatch { op: &'static str, left: (usize, usize), right: (usize, usize) },
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MatrixError::Ragged { row, expected, got } => {
+                write!(f, "row {} has {} values, expected {}", row, got, expected)
+            }
+            MatrixError::OutOfBounds { row, col, rows, cols } => {
+                write!(f, "({}, {}) is outside a {}x{} matrix", row, col, rows, cols)
+            }
+            MatrixError::DimensionMismatch { op, left, right } => write!(
+                f,
+                "can't {} a {}x{} and a {}x{} matrix",
+                op, left.0, left.1, right.0, right.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MatrixError {}
+
+impl DynMatrix {
+    pub fn zeros(rows: usize, cols: usize) -> DynMatrix {
+        DynMatrix { rows, cols, data: vec![0.0; rows * cols] }
+    }
+
+    pub fn identity(n: usize) -> DynMatrix {
+        let mut out = DynMatrix::zeros(n, n);
+        for i in 0..n {
+            out.data[i * n + i] = 1.0;
+        }
+        out
+    }
+
+    // every row must be as long as the first one
+    pub fn from_rows(rows: Vec<Vec<f64>>) -> Result<DynMatrix, MatrixError> {
+        let cols = rows.first().map_or(0, Vec::len);
+        let mut data = Vec::with_capacity(rows.len() * cols);
+        for (i, row) in rows.iter().enumerate() {
+            if row.len() != cols {
+                return Err(MatrixError::Ragged { row: i, expected: cols, got: row.len() });
+            }
+            data.extend_from_slice(row);
+        }
+        Ok(DynMatrix { rows: rows.len(), cols, data })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn index(&self, row: usize, col: usize) -> Result<usize, MatrixError> {
+        if row < self.rows && col < self.cols {
+            Ok(row * self.cols + col)
+        } else {
+            Err(MatrixError::OutOfBounds { row, col, rows: self.rows, cols: self.cols })
+        }
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Result<f64, MatrixError> {
+        Ok(self.data[self.index(row, col)?])
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: f64) -> Result<(), MatrixError> {
+        let i = self.index(row, col)?;
+        self.data[i] = value;
+        Ok(())
+    }
+
+    fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn add(&self, other: &DynMatrix) -> Result<DynMatrix, MatrixError> {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::DimensionMismatch {
+                op: "add",
+                left: self.shape(),
+                right: other.shape(),
+            });
+        }
+        let data = self.data.iter().zip(&other.data).map(|(a, b)| a + b).collect();
+        Ok(DynMatrix { rows: self.rows, cols: self.cols, data })
+    }
+
+    // (r x n) * (n x c), the loops go i, k, j so the inner one walks both
+    // `other` and `out` along a row, one value after the next
+    pub fn mul(&self, other: &DynMatrix) -> Result<DynMatrix, MatrixError> {
+        if self.cols != other.rows {
+            return Err(MatrixError::DimensionMismatch {
+                op: "multiply",
+                left: self.shape(),
+                right: other.shape(),
+            });
+        }
+        let mut out = DynMatrix::zeros(self.rows, other.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self.data[i * self.cols + k];
+                let other_row = &other.data[k * other.cols..(k + 1) * other.cols];
+                let out_row = &mut out.data[i * other.cols..(i + 1) * other.cols];
+                for (o, b) in out_row.iter_mut().zip(other_row) {
+                    *o += a * b;
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn transpose(&self) -> DynMatrix {
+        let mut out = DynMatrix::zeros(self.cols, self.rows);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                out.data[j * self.rows + i] = self.data[i * self.cols + j];
+            }
+        }
+        out
+    }
+}
+
+// every column is as wide as its widest value, numbers line up on the right
+impl fmt::Display for DynMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cells: Vec<String> = self.data.iter().map(|v| v.to_string()).collect();
+        let widths: Vec<usize> = (0..self.cols)
+            .map(|j| (0..self.rows).map(|i| cells[i * self.cols + j].len()).max().unwrap_or(0))
+            .collect();
+        for i in 0..self.rows {
+            let row: Vec<String> = (0..self.cols)
+                .map(|j| format!("{:>width$}", cells[i * self.cols + j], width = widths[j]))
+                .collect();
+            writeln!(f, "[{}]", row.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn m(rows: &[&[f64]]) -> DynMatrix {
+        DynMatrix::from_rows(rows.iter().map(|row| row.to_vec()).collect()).unwrap()
+    }
+
+    #[test]
+    fn a_known_2x3_times_3x2_product() {
+        let a = m(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]]);
+        let b = m(&[&[7.0, 8.0], &[9.0, 10.0], &[11.0, 12.0]]);
+        let product = a.mul(&b).unwrap();
+        assert_eq!(product, m(&[&[58.0, 64.0], &[139.0, 154.0]]));
+        // the other way round is 3x3
+        let other = b.mul(&a).unwrap();
+        assert_eq!((other.rows(), other.cols()), (3, 3));
+        assert_eq!(other.get(0, 0), Ok(39.0));
+        assert_eq!(a.mul(&DynMatrix::identity(3)), Ok(a.clone()));
+    }
+
+    #[test]
+    fn dimension_mismatch_errors() {
+        let a = DynMatrix::zeros(2, 3);
+        let b = DynMatrix::zeros(2, 3);
+        assert_eq!(
+            a.mul(&b),
+            Err(MatrixError::DimensionMismatch { op: "multiply", left: (2, 3), right: (2, 3) })
+        );
+        let c = DynMatrix::zeros(3, 2);
+        let err = a.add(&c).unwrap_err();
+        assert_eq!(err, MatrixError::DimensionMismatch { op: "add", left: (2, 3), right: (3, 2) });
+        assert_eq!(err.to_string(), "can't add a 2x3 and a 3x2 matrix");
+        assert_eq!(a.add(&b), Ok(DynMatrix::zeros(2, 3)));
+    }
+
+    #[test]
+    fn transpose_round_trips() {
+        let a = m(&[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]]);
+        let t = a.transpose();
+        assert_eq!(t, m(&[&[1.0, 4.0], &[2.0, 5.0], &[3.0, 6.0]]));
+        assert_eq!(t.transpose(), a);
+        assert_eq!(DynMatrix::identity(4).transpose(), DynMatrix::identity(4));
+    }
+
+    #[test]
+    fn ragged_rows_are_rejected() {
+        let rows = vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0]];
+        let err = DynMatrix::from_rows(rows).unwrap_err();
+        assert_eq!(err, MatrixError::Ragged { row: 2, expected: 2, got: 1 });
+        assert_eq!(err.to_string(), "row 2 has 1 values, expected 2");
+        let err = DynMatrix::from_rows(vec![vec![1.0], vec![2.0, 3.0]]).unwrap_err();
+        assert_eq!(err, MatrixError::Ragged { row: 1, expected: 1, got: 2 });
+        let empty = DynMatrix::from_rows(Vec::new()).unwrap();
+        assert_eq!((empty.rows(), empty.cols()), (0, 0));
+    }
+
+    #[test]
+    fn get_and_set_check_bounds() {
+        let mut a = DynMatrix::zeros(2, 3);
+        assert_eq!(a.set(1, 2, 9.0), Ok(()));
+        assert_eq!(a.get(1, 2), Ok(9.0));
+        let out = MatrixError::OutOfBounds { row: 2, col: 0, rows: 2, cols: 3 };
+        assert_eq!(a.get(2, 0), Err(out));
+        assert_eq!(a.set(0, 3, 1.0).unwrap_err().to_string(), "(0, 3) is outside a 2x3 matrix");
+    }
+
+    #[test]
+    fn display_lines_up_columns() {
+        let a = m(&[&[1.0, 200.0], &[-3.5, 4.0]]);
+        assert_eq!(a.to_string(), "[   1 200]\n[-3.5   4]\n");
+    }
+}
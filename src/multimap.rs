@@ -0,0 +1,145 @@
+// a HashMap where a key can hold several values, kept in insertion order
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiMap<K: Eq + Hash, V> {
+    // a key is only here while it has at least one value
+    map: HashMap<K, Vec<V>>,
+}
+
+impl<K: Eq + Hash, V> MultiMap<K, V> {
+    pub fn new() -> MultiMap<K, V> {
+        MultiMap { map: HashMap::new() }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.map.entry(key).or_default().push(value);
+    }
+
+    // an empty slice for a key that isn't there
+    pub fn get(&self, key: &K) -> &[V] {
+        self.map.get(key).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    // removes the first matching value, and the key once it has none left
+    pub fn remove_value(&mut self, key: &K, value: &V) -> bool
+    where
+        V: PartialEq,
+    {
+        let Some(values) = self.map.get_mut(key) else {
+            return false;
+        };
+        let Some(pos) = values.iter().position(|v| v == value) else {
+            return false;
+        };
+        values.remove(pos);
+        if values.is_empty() {
+            self.map.remove(key);
+        }
+        true
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    // number of keys
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    // in no particular order, like the keys of a HashMap
+    pub fn keys_with_multiple(&self) -> Vec<&K> {
+        self.map.iter().filter(|(_, values)| values.len() > 1).map(|(k, _)| k).collect()
+    }
+
+    // every (key, value) pair, a key's values come out in insertion order
+    pub fn flat_iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.map.iter().flat_map(|(k, values)| values.iter().map(move |v| (k, v)))
+    }
+}
+
+impl<K: Eq + Hash, V> Default for MultiMap<K, V> {
+    fn default() -> MultiMap<K, V> {
+        MultiMap::new()
+    }
+}
+
+impl<K: Eq + Hash, V> From<Vec<(K, V)>> for MultiMap<K, V> {
+    fn from(pairs: Vec<(K, V)>) -> MultiMap<K, V> {
+        let mut map = MultiMap::new();
+        for (k, v) in pairs {
+            map.insert(k, v);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_missing_key_is_an_empty_slice() {
+        let mut map: MultiMap<&str, i32> = MultiMap::new();
+        assert!(map.get(&"nobody").is_empty());
+        map.insert("a", 1);
+        assert_eq!(map.get(&"a"), [1]);
+        assert!(map.get(&"b").is_empty());
+        assert!(!map.contains_key(&"b"));
+    }
+
+    #[test]
+    fn removing_the_last_value_removes_the_key() {
+        let mut map = MultiMap::from(vec![("a", 1), ("a", 2), ("b", 3)]);
+        assert!(map.remove_value(&"a", &1));
+        assert_eq!(map.get(&"a"), [2]);
+        assert!(!map.remove_value(&"a", &1));
+        assert!(map.remove_value(&"a", &2));
+        assert!(!map.contains_key(&"a"));
+        assert_eq!(map.len(), 1);
+        assert!(!map.remove_value(&"zzz", &1));
+        assert!(map.remove_value(&"b", &3));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn only_the_first_equal_value_is_removed() {
+        let mut map = MultiMap::from(vec![("k", 7), ("k", 8), ("k", 7)]);
+        assert!(map.remove_value(&"k", &7));
+        assert_eq!(map.get(&"k"), [8, 7]);
+    }
+
+    #[test]
+    fn flat_iter_yields_every_pair() {
+        let map = MultiMap::from(vec![("a", 1), ("b", 2), ("a", 3), ("c", 4), ("a", 5)]);
+        assert_eq!(map.flat_iter().count(), 5);
+        let mut pairs: Vec<(&str, i32)> = map.flat_iter().map(|(k, v)| (*k, *v)).collect();
+        pairs.sort();
+        assert_eq!(pairs, [("a", 1), ("a", 3), ("a", 5), ("b", 2), ("c", 4)]);
+        assert_eq!(MultiMap::<u8, u8>::new().flat_iter().count(), 0);
+    }
+
+    #[test]
+    fn from_pairs_keeps_each_keys_order() {
+        let map = MultiMap::from(vec![("x", 3), ("y", 1), ("x", 1), ("x", 2), ("y", 0)]);
+        assert_eq!(map.get(&"x"), [3, 1, 2]);
+        assert_eq!(map.get(&"y"), [1, 0]);
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn keys_with_multiple_values() {
+        let map = MultiMap::from(vec![("a", 1), ("b", 2), ("a", 3), ("c", 4), ("c", 5)]);
+        let mut keys = map.keys_with_multiple();
+        keys.sort();
+        assert_eq!(keys, [&"a", &"c"]);
+        assert!(MultiMap::from(vec![(1, 1), (2, 2)]).keys_with_multiple().is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+// small generic helpers for pairs, and MinMax for tracking both ends of a stream
+
+// (a, b) -> (b, a)
+pub fn swap<T>((a, b): (T, T)) -> (T, T) {
+    (b, a)
+}
+
+// f runs on both halves, U can be a different type from T
+pub fn map_pair<T, U>((a, b): (T, T), f: impl Fn(T) -> U) -> (U, U) {
+    (f(a), f(b))
+}
+
+// stops at the end of the shorter slice, like Iterator::zip
+pub fn zip_with<A, B, C>(xs: &[A], ys: &[B], f: impl Fn(&A, &B) -> C) -> Vec<C> {
+    xs.iter().zip(ys).map(|(x, y)| f(x, y)).collect()
+}
+
+// the smallest and largest value seen so far, one value at a time, without
+// keeping the rest. a single value is both ends at once, so it is held once
+// and T doesn't have to be Clone
+#[derive(Debug, Clone, PartialEq)]
+pub enum MinMax<T: PartialOrd> {
+    Empty,
+    One(T),
+    Two { min: T, max: T },
+}
+
+impl<T: PartialOrd> MinMax<T> {
+    pub fn new() -> MinMax<T> {
+        MinMax::Empty
+    }
+
+    // ties go like Iterator::min and max: the first smallest and the last largest.
+    // a value that compares as neither (a NaN) is dropped, unless it came first
+    pub fn observe(&mut self, value: T) {
+        *self = match std::mem::replace(self, MinMax::Empty) {
+            MinMax::Empty => MinMax::One(value),
+            MinMax::One(only) => {
+                if value < only {
+                    MinMax::Two { min: value, max: only }
+                } else if value >= only {
+                    MinMax::Two { min: only, max: value }
+                } else {
+                    MinMax::One(only)
+                }
+            }
+            MinMax::Two { min, max } => {
+                if value < min {
+                    MinMax::Two { min: value, max }
+                } else if value >= max {
+                    MinMax::Two { min, max: value }
+                } else {
+                    MinMax::Two { min, max }
+                }
+            }
+        };
+    }
+
+    // (min, max), None until something was observed
+    pub fn get(&self) -> Option<(&T, &T)> {
+        match self {
+            MinMax::Empty => None,
+            MinMax::One(only) => Some((only, only)),
+            MinMax::Two { min, max } => Some((min, max)),
+        }
+    }
+}
+
+impl<T: PartialOrd> Default for MinMax<T> {
+    fn default() -> MinMax<T> {
+        MinMax::new()
+    }
+}
+
+impl<T: PartialOrd> Extend<T> for MinMax<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.observe(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_and_map_pair() {
+        assert_eq!(swap((1, 2)), (2, 1));
+        assert_eq!(swap(("a".to_string(), "b".to_string())), ("b".to_string(), "a".to_string()));
+        // i32 in, String out
+        assert_eq!(map_pair((3, 40), |n| format!("#{}", n)), ("#3".to_string(), "#40".to_string()));
+        assert_eq!(map_pair(("ab", "cde"), str::len), (2, 3));
+    }
+
+    #[test]
+    fn zip_with_stops_at_the_shorter_slice() {
+        let sums = zip_with(&[1, 2, 3], &[10, 20], |a, b| a + b);
+        assert_eq!(sums, [11, 22]);
+        let labels = zip_with(&["x"], &[1.5, 2.5, 3.5], |name, v| format!("{}={}", name, v));
+        assert_eq!(labels, ["x=1.5"]);
+        assert!(zip_with(&[] as &[i32], &[1], |a, b| a * b).is_empty());
+    }
+
+    #[test]
+    fn min_max_of_nothing_and_of_one_value() {
+        let empty: MinMax<i32> = MinMax::default();
+        assert_eq!(empty.get(), None);
+        let mut one = MinMax::new();
+        one.observe(String::from("only"));
+        assert_eq!(one.get(), Some((&"only".to_string(), &"only".to_string())));
+    }
+
+    #[test]
+    fn min_max_over_descending_and_mixed_input() {
+        let mut falling = MinMax::new();
+        falling.extend((1..=100).rev());
+        assert_eq!(falling.get(), Some((&1, &100)));
+
+        let mut words = MinMax::new();
+        words.extend(["pear", "apple", "zucchini", "fig"].map(String::from));
+        let (min, max) = words.get().unwrap();
+        assert_eq!((min.as_str(), max.as_str()), ("apple", "zucchini"));
+    }
+
+    #[test]
+    fn min_max_ties_and_nan() {
+        // (value, position) only compares the value, so the position shows which one won
+        #[derive(Debug, PartialEq)]
+        struct Tagged(i32, usize);
+        impl PartialOrd for Tagged {
+            fn partial_cmp(&self, other: &Tagged) -> Option<std::cmp::Ordering> {
+                self.0.partial_cmp(&other.0)
+            }
+        }
+        let mut tied = MinMax::new();
+        tied.extend([3, 1, 3, 1].iter().enumerate().map(|(i, &v)| Tagged(v, i)));
+        let (min, max) = tied.get().unwrap();
+        assert_eq!((min.1, max.1), (1, 2));
+
+        let mut floats = MinMax::new();
+        floats.extend([2.0, f64::NAN, -1.0, f64::NAN, 5.0]);
+        assert_eq!(floats.get(), Some((&-1.0, &5.0)));
+        let mut second = MinMax::new();
+        second.extend([2.0, f64::NAN]);
+        assert_eq!(second.get(), Some((&2.0, &2.0)));
+    }
+}
@@ -0,0 +1,108 @@
+// data parallel helpers on slices built on thread::scope,
+// the slice is cut into contiguous chunks and each chunk gets its own thread
+
+use std::num::NonZeroUsize;
+use std::thread;
+
+// 0 threads means "as many as the machine has"
+pub(crate) fn thread_count(threads: usize) -> usize {
+    if threads > 0 {
+        return threads;
+    }
+    thread::available_parallelism().map(NonZeroUsize::get).unwrap_or(1)
+}
+
+// length of each chunk so that at most `threads` chunks come out
+fn chunk_size(len: usize, threads: usize) -> usize {
+    len.div_ceil(thread_count(threads)).max(1)
+}
+
+pub fn parallel_sum(data: &[i64], threads: usize) -> i64 {
+    if data.is_empty() {
+        return 0;
+    }
+    let size = chunk_size(data.len(), threads);
+    thread::scope(|s| {
+        let handles: Vec<_> = data
+            .chunks(size)
+            .map(|chunk| s.spawn(move || chunk.iter().sum::<i64>()))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).sum()
+    })
+}
+
+// the chunks are joined in the order they were spawned,
+// so the output lines up with the input
+pub fn parallel_map<T: Sync, U: Send>(
+    data: &[T],
+    threads: usize,
+    f: impl Fn(&T) -> U + Sync,
+) -> Vec<U> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+    let size = chunk_size(data.len(), threads);
+    let f = &f;
+    thread::scope(|s| {
+        let handles: Vec<_> = data
+            .chunks(size)
+            .map(|chunk| s.spawn(move || chunk.iter().map(f).collect::<Vec<U>>()))
+            .collect();
+        let mut out = Vec::with_capacity(data.len());
+        for handle in handles {
+            out.extend(handle.join().unwrap());
+        }
+        out
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn empty_slices() {
+        assert_eq!(parallel_sum(&[], 4), 0);
+        assert_eq!(parallel_sum(&[], 0), 0);
+        assert!(parallel_map(&[] as &[i64], 4, |n| n * 2).is_empty());
+    }
+
+    #[test]
+    fn fewer_items_than_threads() {
+        let data = [5, -2, 7];
+        assert_eq!(parallel_sum(&data, 16), 10);
+        assert_eq!(parallel_map(&data, 16, |n| n * 10), [50, -20, 70]);
+        assert_eq!(parallel_sum(&[42], 8), 42);
+    }
+
+    #[test]
+    fn a_large_seeded_dataset_matches_the_sequential_result() {
+        let mut rng = Rng::new(166);
+        let data: Vec<i64> = (0..100_003).map(|_| rng.range(-1_000_000, 1_000_000)).collect();
+        let expected: i64 = data.iter().sum();
+        for threads in [0, 1, 2, 3, 7, 64] {
+            assert_eq!(parallel_sum(&data, threads), expected, "{} threads", threads);
+        }
+        let squares: Vec<i64> = data.iter().map(|n| n * n).collect();
+        assert_eq!(parallel_map(&data, 5, |n| n * n), squares);
+    }
+
+    #[test]
+    fn map_output_keeps_the_input_order() {
+        let data: Vec<usize> = (0..1000).collect();
+        for threads in [0, 1, 3, 8, 1000, 2000] {
+            let out = parallel_map(&data, threads, |n| n.to_string());
+            let back: Vec<usize> = out.iter().map(|s| s.parse().unwrap()).collect();
+            assert_eq!(back, data, "{} threads", threads);
+        }
+    }
+
+    #[test]
+    fn zero_threads_means_at_least_one() {
+        assert!(thread_count(0) >= 1);
+        assert_eq!(thread_count(3), 3);
+        assert_eq!(chunk_size(10, 3), 4);
+        assert_eq!(chunk_size(0, 3), 1);
+    }
+}
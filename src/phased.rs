@@ -0,0 +1,93 @@
+// workers stepping through phases in lockstep: nobody starts phase k + 1
+// until every worker has finished phase k, because phase k + 1 needs the
+// sum of everything phase k produced
+
+use std::sync::{Barrier, Mutex};
+use std::thread;
+
+// what worker `w` of `workers` makes in a phase whose input is `previous_sum`.
+// the previous sum is 0 for the first phase
+pub fn phase_value(previous_sum: u64, workers: usize, w: usize) -> u64 {
+    previous_sum / workers as u64 + w as u64 + 1
+}
+
+// row w holds worker w's value for each phase
+pub fn phased_compute(workers: usize, phases: usize) -> Vec<Vec<u64>> {
+    // one running total per phase, a phase only ever adds to its own slot
+    let sums = Mutex::new(vec![0u64; phases]);
+    let barrier = Barrier::new(workers);
+
+    thread::scope(|s| {
+        let handles: Vec<_> = (0..workers)
+            .map(|w| {
+                let sums = &sums;
+                let barrier = &barrier;
+                s.spawn(move || {
+                    let mut values = Vec::with_capacity(phases);
+                    for phase in 0..phases {
+                        let previous_sum =
+                            if phase == 0 { 0 } else { sums.lock().unwrap()[phase - 1] };
+                        let value = phase_value(previous_sum, workers, w);
+                        sums.lock().unwrap()[phase] += value;
+                        values.push(value);
+                        // after this every worker's value for `phase` is in the sum
+                        barrier.wait();
+                    }
+                    values
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // every value must come from the full sum of the phase before it, which
+    // is only possible if no worker ran ahead of the barrier
+    fn assert_phases_follow_each_other(matrix: &[Vec<u64>], workers: usize, phases: usize) {
+        assert_eq!(matrix.len(), workers);
+        let mut previous_sum = 0;
+        for phase in 0..phases {
+            for (w, row) in matrix.iter().enumerate() {
+                assert_eq!(row.len(), phases);
+                let expected = phase_value(previous_sum, workers, w);
+                assert_eq!(row[phase], expected, "worker {} phase {}", w, phase);
+            }
+            previous_sum = matrix.iter().map(|row| row[phase]).sum();
+        }
+    }
+
+    #[test]
+    fn one_worker() {
+        let matrix = phased_compute(1, 5);
+        // each phase is the last one plus 1
+        assert_eq!(matrix, [vec![1, 2, 3, 4, 5]]);
+        assert_phases_follow_each_other(&matrix, 1, 5);
+    }
+
+    #[test]
+    fn four_workers() {
+        let matrix = phased_compute(4, 3);
+        // phase 0 is 1..=4 (sum 10), phase 1 is 10 / 4 + 1..=4 (sum 18),
+        // phase 2 is 18 / 4 + 1..=4
+        assert_eq!(matrix, [vec![1, 3, 5], vec![2, 4, 6], vec![3, 5, 7], vec![4, 6, 8]]);
+        assert_phases_follow_each_other(&matrix, 4, 3);
+    }
+
+    #[test]
+    fn zero_phases_and_zero_workers() {
+        assert_eq!(phased_compute(3, 0), [Vec::<u64>::new(), Vec::new(), Vec::new()]);
+        assert!(phased_compute(0, 5).is_empty());
+    }
+
+    #[test]
+    fn eight_workers_twenty_phases_finish() {
+        let matrix = phased_compute(8, 20);
+        assert_phases_follow_each_other(&matrix, 8, 20);
+        // the same numbers every run, scheduling can't change them
+        assert_eq!(phased_compute(8, 20), matrix);
+    }
+}
@@ -0,0 +1,236 @@
+// a token bucket: up to `capacity` tokens, refilled at a steady rate,
+// every call that goes through spends one.
+// the bucket is only topped up when someone looks at it
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub type Clock = Arc<dyn Fn() -> Instant + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateLimitError {
+    ZeroCapacity,
+    BadRate(f64),
+}
+
+impl fmt::Display for RateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RateLimitError::ZeroCapacity => {
+                write!(f, "a rate limiter needs room for at least one token")
+            }
+            RateLimitError::BadRate(rate) => {
+                write!(f, "the refill rate must be a positive number, got {}", rate)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RateLimitError {}
+
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    clock: Clock,
+    state: Mutex<BucketState>,
+}
+
+impl RateLimiter {
+    // starts full, so a burst of `capacity` calls goes straight through
+    pub fn new(capacity: u32, refill_per_sec: f64) -> Result<RateLimiter, RateLimitError> {
+        RateLimiter::with_clock(capacity, refill_per_sec, Arc::new(Instant::now))
+    }
+
+    // the clock can be swapped out, e.g. for one that only moves when told to
+    pub fn with_clock(
+        capacity: u32,
+        refill_per_sec: f64,
+        clock: Clock,
+    ) -> Result<RateLimiter, RateLimitError> {
+        if capacity == 0 {
+            return Err(RateLimitError::ZeroCapacity);
+        }
+        if !refill_per_sec.is_finite() || refill_per_sec <= 0.0 {
+            return Err(RateLimitError::BadRate(refill_per_sec));
+        }
+        let state = BucketState { tokens: capacity as f64, last_refill: clock() };
+        let state = Mutex::new(state);
+        Ok(RateLimiter { capacity: capacity as f64, refill_per_sec, clock, state })
+    }
+
+    fn refill(&self, state: &mut BucketState) {
+        let now = (self.clock)();
+        // a clock that went backwards adds nothing
+        let elapsed = now.saturating_duration_since(state.last_refill).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        state.last_refill = now;
+    }
+
+    pub fn try_acquire(&self) -> bool {
+        self.acquire_or_wait().is_none()
+    }
+
+    // None when a token was taken, otherwise how long until the next one is there
+    fn acquire_or_wait(&self) -> Option<Duration> {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state);
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            None
+        } else {
+            // a tiny rate can make the wait too long for a Duration
+            let wait = (1.0 - state.tokens) / self.refill_per_sec;
+            Some(Duration::try_from_secs_f64(wait).unwrap_or(Duration::MAX))
+        }
+    }
+
+    // the lock is not held while sleeping, so other threads can still check in
+    pub fn acquire_blocking(&self) {
+        while let Some(wait) = self.acquire_or_wait() {
+            thread::sleep(wait);
+        }
+    }
+
+    // fractional, a half refilled token shows up as 0.5
+    pub fn available(&self) -> f64 {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state);
+        state.tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a clock that only moves when the test says so
+    struct FakeClock {
+        now: Arc<Mutex<Instant>>,
+    }
+
+    impl FakeClock {
+        fn new() -> FakeClock {
+            FakeClock { now: Arc::new(Mutex::new(Instant::now())) }
+        }
+
+        fn clock(&self) -> Clock {
+            let now = Arc::clone(&self.now);
+            Arc::new(move || *now.lock().unwrap())
+        }
+
+        fn advance(&self, by: Duration) {
+            *self.now.lock().unwrap() += by;
+        }
+    }
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn zero_capacity_and_bad_rates_are_rejected() {
+        assert_eq!(RateLimiter::new(0, 1.0).err(), Some(RateLimitError::ZeroCapacity));
+        for rate in [0.0, -1.0, f64::INFINITY] {
+            assert_eq!(RateLimiter::new(1, rate).err(), Some(RateLimitError::BadRate(rate)));
+        }
+        let nan = RateLimiter::new(1, f64::NAN);
+        assert!(matches!(nan, Err(RateLimitError::BadRate(r)) if r.is_nan()));
+        assert_eq!(
+            RateLimitError::ZeroCapacity.to_string(),
+            "a rate limiter needs room for at least one token"
+        );
+    }
+
+    #[test]
+    fn a_burst_then_nothing_left() {
+        let fake = FakeClock::new();
+        let limiter = RateLimiter::with_clock(3, 1.0, fake.clock()).unwrap();
+        assert_eq!(limiter.available(), 3.0);
+        assert!(limiter.try_acquire());
+        assert!(limiter.try_acquire());
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+        assert_eq!(limiter.available(), 0.0);
+    }
+
+    #[test]
+    fn exact_token_counts_as_time_moves() {
+        let fake = FakeClock::new();
+        let limiter = RateLimiter::with_clock(5, 4.0, fake.clock()).unwrap();
+        for _ in 0..5 {
+            assert!(limiter.try_acquire());
+        }
+        fake.advance(ms(250));
+        assert_eq!(limiter.available(), 1.0);
+        fake.advance(ms(125));
+        assert_eq!(limiter.available(), 1.5);
+        assert!(limiter.try_acquire());
+        assert_eq!(limiter.available(), 0.5);
+        assert!(!limiter.try_acquire());
+        // never more than the capacity, however long it was
+        fake.advance(Duration::from_secs(60));
+        assert_eq!(limiter.available(), 5.0);
+    }
+
+    #[test]
+    fn a_clock_going_backwards_adds_nothing() {
+        let now = Arc::new(Mutex::new(Instant::now() + Duration::from_secs(10)));
+        let shared = Arc::clone(&now);
+        let clock: Clock = Arc::new(move || *shared.lock().unwrap());
+        let limiter = RateLimiter::with_clock(2, 1.0, clock).unwrap();
+        assert!(limiter.try_acquire() && limiter.try_acquire());
+        *now.lock().unwrap() -= Duration::from_secs(5);
+        assert_eq!(limiter.available(), 0.0);
+    }
+
+    #[test]
+    fn threads_never_get_more_than_capacity_plus_refill() {
+        let fake = FakeClock::new();
+        let limiter = Arc::new(RateLimiter::with_clock(10, 100.0, fake.clock()).unwrap());
+        let granted = thread::scope(|s| {
+            let workers: Vec<_> = (0..8)
+                .map(|_| {
+                    let limiter = Arc::clone(&limiter);
+                    s.spawn(move || (0..200).filter(|_| limiter.try_acquire()).count())
+                })
+                .collect();
+            // 20 steps of 10 ms at 100 per second is 20 more tokens
+            for _ in 0..20 {
+                fake.advance(ms(10));
+                thread::yield_now();
+            }
+            workers.into_iter().map(|w| w.join().unwrap()).sum::<usize>()
+        });
+        assert!(granted <= 10 + 20, "{} tokens handed out", granted);
+        // the first burst always goes through, and the bucket never overflows
+        assert!(granted >= 10);
+        assert!(limiter.available() <= 10.0);
+    }
+
+    #[test]
+    fn acquire_blocking_waits_for_the_next_token() {
+        let limiter = RateLimiter::new(1, 50.0).unwrap();
+        let start = Instant::now();
+        limiter.acquire_blocking();
+        limiter.acquire_blocking();
+        limiter.acquire_blocking();
+        // two refills of 20 ms each
+        assert!(start.elapsed() >= ms(35), "{:?}", start.elapsed());
+    }
+
+    #[test]
+    fn a_tiny_rate_does_not_overflow_the_wait() {
+        let fake = FakeClock::new();
+        let limiter = RateLimiter::with_clock(1, 1e-300, fake.clock()).unwrap();
+        assert!(limiter.try_acquire());
+        assert_eq!(limiter.acquire_or_wait(), Some(Duration::MAX));
+    }
+}
@@ -0,0 +1,246 @@
+// the Rect from impl_struct.rs, whole number sides so the area is exact
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul};
+
+use crate::shape::{Shape, ShapeKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub len: u32,
+    pub breadth: u32,
+}
+
+impl Rect {
+    pub fn new(len: u32, breadth: u32) -> Rect {
+        Rect { len, breadth }
+    }
+
+    pub fn area(&self) -> u32 {
+        self.len * self.breadth
+    }
+
+    pub fn peri(&self) -> u32 {
+        2 * (self.len + self.breadth)
+    }
+}
+
+// 1x1 rather than 0x0: a rect with no area isn't much of a rect, and scaling
+// it or adding it to another would never change anything
+impl Default for Rect {
+    fn default() -> Rect {
+        Rect::new(1, 1)
+    }
+}
+
+// both rects are sizes with a corner pinned at the origin, so the smallest
+// rect that covers them both takes the bigger len and the bigger breadth
+impl Add for Rect {
+    type Output = Rect;
+
+    fn add(self, other: Rect) -> Rect {
+        Rect::new(self.len.max(other.len), self.breadth.max(other.breadth))
+    }
+}
+
+// scales both sides, a side that no longer fits in a u32 is a bug, not a wrap
+impl Mul<u32> for Rect {
+    type Output = Rect;
+
+    fn mul(self, factor: u32) -> Rect {
+        let scale = |side: u32, name: &str| {
+            side.checked_mul(factor).unwrap_or_else(|| {
+                panic!("{} {} times {} overflows a u32", name, side, factor)
+            })
+        };
+        Rect::new(scale(self.len, "len"), scale(self.breadth, "breadth"))
+    }
+}
+
+// a <= b when a fits inside b (same corner, no rotating). a 2x5 and a 5x2
+// don't fit in each other, so neither is smaller and partial_cmp is None.
+// that is why containment can't be Ord: Ord needs every pair to have an answer
+impl PartialOrd for Rect {
+    fn partial_cmp(&self, other: &Rect) -> Option<Ordering> {
+        let len = self.len.cmp(&other.len);
+        let breadth = self.breadth.cmp(&other.breadth);
+        match (len, breadth) {
+            (Ordering::Equal, b) => Some(b),
+            (l, Ordering::Equal) => Some(l),
+            (l, b) if l == b => Some(l),
+            _ => None,
+        }
+    }
+}
+
+// why a Shape couldn't become a Rect, `which` is "length" or "breadth"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShapeConvertError {
+    WrongVariant(ShapeKind),
+    // NaN counts as fractional, it isn't a whole number either
+    Fractional { which: &'static str, value: f64 },
+    Negative { which: &'static str, value: f64 },
+    TooLarge { which: &'static str, value: f64 },
+}
+
+impl fmt::Display for ShapeConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShapeConvertError::WrongVariant(kind) => write!(f, "a {:?} is not a rect", kind),
+            ShapeConvertError::Fractional { which, value } => {
+                write!(f, "{} {} is not a whole number", which, value)
+            }
+            ShapeConvertError::Negative { which, value } => {
+                write!(f, "{} {} is negative", which, value)
+            }
+            ShapeConvertError::TooLarge { which, value } => {
+                write!(f, "{} {} does not fit in a u32", which, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShapeConvertError {}
+
+// every u32 is exact as an f64, so this never loses anything
+impl From<Rect> for Shape {
+    fn from(rect: Rect) -> Shape {
+        Shape::Rect(rect.len as f64, rect.breadth as f64)
+    }
+}
+
+fn whole_side(which: &'static str, value: f64) -> Result<u32, ShapeConvertError> {
+    if value < 0.0 {
+        Err(ShapeConvertError::Negative { which, value })
+    } else if value > u32::MAX as f64 {
+        Err(ShapeConvertError::TooLarge { which, value })
+    } else if value.fract() != 0.0 {
+        Err(ShapeConvertError::Fractional { which, value })
+    } else {
+        Ok(value as u32)
+    }
+}
+
+// only a bare Shape::Rect with whole, non-negative sides that fit in a u32
+impl TryFrom<&Shape> for Rect {
+    type Error = ShapeConvertError;
+
+    fn try_from(shape: &Shape) -> Result<Rect, ShapeConvertError> {
+        match shape {
+            Shape::Rect(a, b) => {
+                Ok(Rect::new(whole_side("length", *a)?, whole_side("breadth", *b)?))
+            }
+            other => Err(ShapeConvertError::WrongVariant(other.kind())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adding_covers_both_rects() {
+        assert_eq!(Rect::new(2, 5) + Rect::new(4, 3), Rect::new(4, 5));
+        assert_eq!(Rect::new(3, 3) + Rect::new(1, 1), Rect::new(3, 3));
+        // the default 1x1 only ever grows a 0-sided rect
+        assert_eq!(Rect::new(0, 7) + Rect::default(), Rect::new(1, 7));
+        let (a, b) = (Rect::new(6, 1), Rect::new(2, 9));
+        let union = a + b;
+        assert_eq!(union, b + a);
+        assert!(a <= union && b <= union);
+    }
+
+    #[test]
+    fn multiplying_scales_both_sides() {
+        assert_eq!(Rect::new(2, 3) * 4, Rect::new(8, 12));
+        assert_eq!(Rect::new(u32::MAX, 1) * 1, Rect::new(u32::MAX, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "breadth 2147483648 times 2 overflows a u32")]
+    fn scaling_past_a_u32_panics_with_the_side() {
+        let _ = Rect::new(3, 1 << 31) * 2;
+    }
+
+    #[test]
+    fn containment_gives_all_four_answers() {
+        let small = Rect::new(2, 3);
+        assert_eq!(small.partial_cmp(&Rect::new(2, 3)), Some(Ordering::Equal));
+        assert_eq!(small.partial_cmp(&Rect::new(4, 3)), Some(Ordering::Less));
+        assert_eq!(small.partial_cmp(&Rect::new(5, 9)), Some(Ordering::Less));
+        assert_eq!(Rect::new(2, 8).partial_cmp(&small), Some(Ordering::Greater));
+        // a 2x5 and a 5x2 don't fit in each other either way
+        assert_eq!(Rect::new(2, 5).partial_cmp(&Rect::new(5, 2)), None);
+        let (tall, wide) = (Rect::new(2, 5), Rect::new(5, 2));
+        assert_eq!([tall < wide, tall <= wide, tall > wide, tall >= wide], [false; 4]);
+    }
+
+    #[test]
+    fn a_rect_survives_the_round_trip_through_shape() {
+        for rect in [Rect::new(3, 4), Rect::new(0, 0), Rect::new(u32::MAX, 1), Rect::default()] {
+            let shape = Shape::from(rect);
+            assert_eq!(Rect::try_from(&shape), Ok(rect));
+        }
+        assert_eq!(Shape::from(Rect::new(3, 4)), Shape::Rect(3.0, 4.0));
+        // -0.0 is a whole number that isn't negative
+        assert_eq!(Rect::try_from(&Shape::Rect(-0.0, 2.0)), Ok(Rect::new(0, 2)));
+    }
+
+    #[test]
+    fn each_reason_a_shape_is_not_a_rect() {
+        let convert = |shape: Shape| Rect::try_from(&shape).unwrap_err();
+        assert_eq!(
+            convert(Shape::Circle(1.0)),
+            ShapeConvertError::WrongVariant(ShapeKind::Circle)
+        );
+        let scaled = convert(Shape::Scaled(2.0, Box::new(Shape::Rect(1.0, 1.0))));
+        assert!(matches!(scaled, ShapeConvertError::WrongVariant(_)));
+        assert_eq!(
+            convert(Shape::Rect(2.5, 1.0)),
+            ShapeConvertError::Fractional { which: "length", value: 2.5 }
+        );
+        assert_eq!(
+            convert(Shape::Rect(1.0, -3.0)),
+            ShapeConvertError::Negative { which: "breadth", value: -3.0 }
+        );
+        assert_eq!(
+            convert(Shape::Rect(1e10, 1.0)),
+            ShapeConvertError::TooLarge { which: "length", value: 1e10 }
+        );
+        assert!(matches!(
+            convert(Shape::Rect(f64::INFINITY, 1.0)),
+            ShapeConvertError::TooLarge { .. }
+        ));
+        // NaN compares false with everything, so it falls through to fractional
+        assert!(matches!(
+            convert(Shape::Rect(1.0, f64::NAN)),
+            ShapeConvertError::Fractional { which: "breadth", .. }
+        ));
+        assert_eq!(convert(Shape::Rect(-1.5, 1.0)).to_string(), "length -1.5 is negative");
+    }
+
+    #[test]
+    fn u32_max_is_the_largest_side_that_converts() {
+        let max = u32::MAX as f64;
+        assert_eq!(Rect::try_from(&Shape::Rect(max, max)), Ok(Rect::new(u32::MAX, u32::MAX)));
+        assert_eq!(
+            Rect::try_from(&Shape::Rect(max + 1.0, 1.0)),
+            Err(ShapeConvertError::TooLarge { which: "length", value: max + 1.0 })
+        );
+        assert!(matches!(
+            Rect::try_from(&Shape::Rect(max - 0.5, 1.0)),
+            Err(ShapeConvertError::Fractional { .. })
+        ));
+    }
+
+    #[test]
+    fn the_default_rect_is_one_by_one() {
+        let rect = Rect::default();
+        assert_eq!((rect.len, rect.breadth), (1, 1));
+        assert_eq!((rect.area(), rect.peri()), (1, 4));
+        // so it is the identity for scaling
+        assert_eq!(Rect::new(4, 7) * rect.len, Rect::new(4, 7));
+    }
+}
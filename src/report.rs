@@ -0,0 +1,273 @@
+// two ways of telling someone a run failed: a short friendly message for
+// the person at the terminal, or everything we know for whoever debugs it
+
+use std::error::Error;
+use std::io::{self, ErrorKind, Write};
+
+use crate::error::{report_error, AppError};
+use crate::line_reader::FileReadError;
+use crate::shape::ParseShapeError;
+use crate::user::UserError;
+
+// Display stays the technical message. a user message never has paths,
+// type names or os error codes in it, a path can say more than it should
+pub trait UserFacing {
+    fn user_message(&self) -> String;
+
+    // a line on what to do about it
+    fn hint(&self) -> Option<String> {
+        None
+    }
+}
+
+fn io_message(err: &io::Error) -> String {
+    match err.kind() {
+        ErrorKind::NotFound => String::from("a file we needed could not be found"),
+        ErrorKind::PermissionDenied => String::from("a file we needed is not readable"),
+        ErrorKind::InvalidData => String::from("a file we read is not valid text"),
+        _ => String::from("reading or writing a file failed"),
+    }
+}
+
+fn io_hint(err: &io::Error) -> Option<String> {
+    match err.kind() {
+        ErrorKind::NotFound => {
+            Some(String::from("check that the file exists and is spelled right"))
+        }
+        ErrorKind::PermissionDenied => Some(String::from("check the file's permissions")),
+        _ => None,
+    }
+}
+
+impl UserFacing for FileReadError {
+    fn user_message(&self) -> String {
+        match self {
+            FileReadError::Open { source, .. } => io_message(source),
+            FileReadError::Read { line, .. } => {
+                format!("the file could not be read past line {}", line.saturating_sub(1))
+            }
+        }
+    }
+
+    fn hint(&self) -> Option<String> {
+        match self {
+            FileReadError::Open { source, .. } | FileReadError::Read { source, .. } => {
+                io_hint(source)
+            }
+        }
+    }
+}
+
+// these messages are written for people already
+impl UserFacing for UserError {
+    fn user_message(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl UserFacing for ParseShapeError {
+    fn user_message(&self) -> String {
+        format!("that shape could not be read: {}", self)
+    }
+
+    fn hint(&self) -> Option<String> {
+        Some(String::from("shapes look like `rect 3 4`, `circle 1` or `scaled 2 circle 1`"))
+    }
+}
+
+// the first error in the chain that knows how to talk to people
+fn user_facing_cause<'a>(err: &'a (dyn Error + 'static)) -> Option<&'a dyn UserFacing> {
+    let mut cause = Some(err);
+    while let Some(err) = cause {
+        if let Some(e) = err.downcast_ref::<FileReadError>() {
+            return Some(e);
+        }
+        if let Some(e) = err.downcast_ref::<ParseShapeError>() {
+            return Some(e);
+        }
+        if let Some(e) = err.downcast_ref::<UserError>() {
+            return Some(e);
+        }
+        cause = err.source();
+    }
+    None
+}
+
+fn first_io_error<'a>(err: &'a (dyn Error + 'static)) -> Option<&'a io::Error> {
+    let mut cause = Some(err);
+    while let Some(err) = cause {
+        if let Some(io) = err.downcast_ref::<io::Error>() {
+            return Some(io);
+        }
+        cause = err.source();
+    }
+    None
+}
+
+impl UserFacing for AppError {
+    fn user_message(&self) -> String {
+        match self {
+            AppError::Usage(msg) => msg.clone(),
+            AppError::Config(_) => String::from("the config is not valid"),
+            AppError::Msg(_) => String::from("something went wrong inside the program"),
+            _ => match (user_facing_cause(self), first_io_error(self)) {
+                (Some(e), _) => e.user_message(),
+                (None, Some(io)) => io_message(io),
+                (None, None) => String::from("something went wrong inside the program"),
+            },
+        }
+    }
+
+    fn hint(&self) -> Option<String> {
+        let specific = match (user_facing_cause(self), first_io_error(self)) {
+            (Some(e), _) => e.hint(),
+            (None, Some(io)) => io_hint(io),
+            (None, None) => None,
+        };
+        specific.or_else(|| Some(String::from("run again with --verbose for the details")))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportMode {
+    // the friendly message and a hint
+    User,
+    // the whole cause chain and the error's Debug form
+    Debug,
+}
+
+impl ReportMode {
+    // --verbose or --quiet on the command line, the last one wins.
+    // without either, a non-empty RUST_TUT_DEBUG (other than "0") means Debug
+    pub fn from_args_and_env(args: &[String], debug_env: Option<&str>) -> ReportMode {
+        let flag = args.iter().rev().find_map(|arg| match arg.as_str() {
+            "--verbose" => Some(ReportMode::Debug),
+            "--quiet" => Some(ReportMode::User),
+            _ => None,
+        });
+        match (flag, debug_env) {
+            (Some(mode), _) => mode,
+            (None, Some(value)) if !value.is_empty() && value != "0" => ReportMode::Debug,
+            (None, _) => ReportMode::User,
+        }
+    }
+
+    pub fn from_env() -> ReportMode {
+        let args: Vec<String> = std::env::args().collect();
+        let debug_env = std::env::var("RUST_TUT_DEBUG").ok();
+        ReportMode::from_args_and_env(&args, debug_env.as_deref())
+    }
+}
+
+// the command line minus --verbose and --quiet, for bins that read positional args
+pub fn args_without_report_flags() -> Vec<String> {
+    std::env::args().skip(1).filter(|arg| arg != "--verbose" && arg != "--quiet").collect()
+}
+
+pub fn report_to(err: &AppError, mode: ReportMode, writer: &mut dyn Write) {
+    match mode {
+        ReportMode::User => {
+            let _ = writeln!(writer, "error: {}", err.user_message());
+            if let Some(hint) = err.hint() {
+                let _ = writeln!(writer, "hint: {}", hint);
+            }
+        }
+        ReportMode::Debug => {
+            report_error(err, writer);
+            let _ = writeln!(writer, "{:#?}", err);
+        }
+    }
+}
+
+pub fn report(err: &AppError, mode: ReportMode) {
+    report_to(err, mode, &mut io::stderr());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    use crate::error::Contextual;
+
+    const SECRET: &str = "/home/asha/private/clients.txt";
+
+    fn missing_file() -> AppError {
+        let source = io::Error::new(ErrorKind::NotFound, "No such file or directory");
+        AppError::from(FileReadError::Open { path: PathBuf::from(SECRET), source })
+    }
+
+    fn reported(err: &AppError, mode: ReportMode) -> String {
+        let mut sink: Vec<u8> = Vec::new();
+        report_to(err, mode, &mut sink);
+        String::from_utf8(sink).unwrap()
+    }
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn user_mode_never_shows_the_path() {
+        let text = reported(&missing_file(), ReportMode::User);
+        assert_eq!(
+            text,
+            "error: a file we needed could not be found\n\
+             hint: check that the file exists and is spelled right\n"
+        );
+        assert!(!text.contains(SECRET));
+
+        // a context on top doesn't let the path through either
+        let open: Result<(), FileReadError> = Err(FileReadError::Open {
+            path: PathBuf::from(SECRET),
+            source: io::Error::new(ErrorKind::PermissionDenied, "denied"),
+        });
+        let err = AppError::from(open.context(format!("opening {}", SECRET)).unwrap_err());
+        let text = reported(&err, ReportMode::User);
+        assert!(text.starts_with("error: a file we needed is not readable\n"), "{}", text);
+        assert!(!text.contains(SECRET));
+    }
+
+    #[test]
+    fn debug_mode_shows_the_whole_chain_with_the_path() {
+        let text = reported(&missing_file(), ReportMode::Debug);
+        assert!(text.starts_with(&format!("error: file: could not open {}\n", SECRET)), "{}", text);
+        assert!(text.contains("  caused by: No such file or directory\n"), "{}", text);
+        // and the Debug form after it
+        assert!(text.contains("FileRead("), "{}", text);
+        assert!(text.matches(SECRET).count() >= 2, "{}", text);
+    }
+
+    #[test]
+    fn other_errors_get_friendly_messages_and_the_verbose_hint() {
+        let usage = AppError::Usage("give a pattern and a file".to_string());
+        assert_eq!(
+            reported(&usage, ReportMode::User),
+            "error: give a pattern and a file\nhint: run again with --verbose for the details\n"
+        );
+        let shape = AppError::from(ParseShapeError::UnknownShape("hex".to_string()));
+        assert_eq!(shape.user_message(), "that shape could not be read: unknown shape `hex`");
+        assert!(shape.hint().unwrap().contains("rect 3 4"));
+        let msg = AppError::from("index 3 out of range in Vec::get");
+        assert_eq!(msg.user_message(), "something went wrong inside the program");
+        let read = FileReadError::Read {
+            path: PathBuf::from(SECRET),
+            line: 0,
+            source: io::Error::other("x"),
+        };
+        assert_eq!(read.user_message(), "the file could not be read past line 0");
+    }
+
+    #[test]
+    fn flags_win_over_the_env_var_and_the_last_flag_wins() {
+        let mode = ReportMode::from_args_and_env;
+        assert_eq!(mode(&args(&["bin"]), None), ReportMode::User);
+        assert_eq!(mode(&args(&["bin"]), Some("1")), ReportMode::Debug);
+        assert_eq!(mode(&args(&["bin"]), Some("0")), ReportMode::User);
+        assert_eq!(mode(&args(&["bin"]), Some("")), ReportMode::User);
+        assert_eq!(mode(&args(&["bin", "--verbose"]), None), ReportMode::Debug);
+        assert_eq!(mode(&args(&["bin", "--quiet"]), Some("1")), ReportMode::User);
+        assert_eq!(mode(&args(&["bin", "--quiet", "--verbose"]), Some("0")), ReportMode::Debug);
+        assert_eq!(mode(&args(&["bin", "--verbose", "--quiet"]), None), ReportMode::User);
+    }
+}
@@ -0,0 +1,235 @@
+// running a fallible operation again until it works, with a pause between tries
+
+use std::error::Error;
+use std::fmt;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Backoff {
+    // the same base_delay every time
+    Fixed,
+    // base_delay, then times `factor` after every failure, never above `cap`.
+    // a negative or non-finite factor is treated as 1, so the pause stays
+    // base_delay (still capped)
+    Exponential { factor: f64, cap: Duration },
+}
+
+pub struct RetryPolicy<E> {
+    // tries in total, the first one included. 0 still runs the operation once
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub backoff: Backoff,
+    // errors this says no to are returned right away
+    pub retry_if: fn(&E) -> bool,
+}
+
+impl<E> RetryPolicy<E> {
+    // the pause after failed attempt number `attempt` (1 for the first)
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        match self.backoff {
+            Backoff::Fixed => self.base_delay,
+            Backoff::Exponential { factor, cap } => {
+                let factor = if factor.is_finite() && factor >= 0.0 { factor } else { 1.0 };
+                let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+                let scaled = self.base_delay.as_secs_f64() * factor.powi(exponent);
+                // a huge or infinite product just means "the cap"
+                // (cap's f64 form can round up past it, so the conversion is checked too)
+                if scaled < cap.as_secs_f64() {
+                    Duration::try_from_secs_f64(scaled).unwrap_or(cap)
+                } else {
+                    cap
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RetryError<E> {
+    // what the last attempt failed with
+    pub error: E,
+    pub attempts: u32,
+    // time spent sleeping between attempts
+    pub total_delay: Duration,
+}
+
+impl<E: fmt::Display> fmt::Display for RetryError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let tries = if self.attempts == 1 { "try" } else { "tries" };
+        write!(f, "gave up after {} {}: {}", self.attempts, tries, self.error)
+    }
+}
+
+impl<E: Error + 'static> Error for RetryError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+pub fn retry<T, E, F: FnMut() -> Result<T, E>>(
+    policy: &RetryPolicy<E>,
+    op: F,
+) -> Result<T, RetryError<E>> {
+    retry_with_sleeper(policy, &mut thread::sleep, op)
+}
+
+// like retry, but every pause goes through `sleeper`, so a test can record
+// the delays instead of waiting for them
+pub fn retry_with_sleeper<T, E, F: FnMut() -> Result<T, E>>(
+    policy: &RetryPolicy<E>,
+    sleeper: &mut dyn FnMut(Duration),
+    mut op: F,
+) -> Result<T, RetryError<E>> {
+    let max_attempts = policy.max_attempts.max(1);
+    let mut total_delay = Duration::ZERO;
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let error = match op() {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+        if attempts >= max_attempts || !(policy.retry_if)(&error) {
+            return Err(RetryError { error, attempts, total_delay });
+        }
+        let delay = policy.delay_after(attempts);
+        sleeper(delay);
+        // a huge base_delay over many attempts would overflow a plain +=
+        total_delay = total_delay.saturating_add(delay);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[derive(Debug, PartialEq)]
+    enum Failure {
+        Flaky,
+        Fatal,
+    }
+
+    fn is_flaky(err: &Failure) -> bool {
+        *err == Failure::Flaky
+    }
+
+    fn policy(max_attempts: u32, backoff: Backoff) -> RetryPolicy<Failure> {
+        RetryPolicy { max_attempts, base_delay: ms(100), backoff, retry_if: is_flaky }
+    }
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    // runs op under policy and returns what it gave plus every recorded sleep
+    fn run<T>(
+        policy: &RetryPolicy<Failure>,
+        op: impl FnMut() -> Result<T, Failure>,
+    ) -> (Result<T, RetryError<Failure>>, Vec<Duration>) {
+        let mut sleeps = Vec::new();
+        let result = retry_with_sleeper(policy, &mut |d| sleeps.push(d), op);
+        (result, sleeps)
+    }
+
+    #[test]
+    fn succeeds_on_the_third_attempt() {
+        let calls = Cell::new(0);
+        let (result, sleeps) = run(&policy(5, Backoff::Fixed), || {
+            calls.set(calls.get() + 1);
+            if calls.get() < 3 { Err(Failure::Flaky) } else { Ok("done") }
+        });
+        assert_eq!(result.unwrap(), "done");
+        assert_eq!(calls.get(), 3);
+        assert_eq!(sleeps, [ms(100), ms(100)]);
+    }
+
+    #[test]
+    fn a_non_retryable_error_stops_right_away() {
+        let calls = Cell::new(0);
+        let (result, sleeps) = run(&policy(5, Backoff::Fixed), || -> Result<(), _> {
+            calls.set(calls.get() + 1);
+            Err(Failure::Fatal)
+        });
+        let err = result.unwrap_err();
+        assert_eq!((err.error, err.attempts, err.total_delay), (Failure::Fatal, 1, Duration::ZERO));
+        assert_eq!(calls.get(), 1);
+        assert!(sleeps.is_empty());
+    }
+
+    #[test]
+    fn exponential_delays_double_up_to_the_cap() {
+        let backoff = Backoff::Exponential { factor: 2.0, cap: ms(500) };
+        let (result, sleeps) = run(&policy(6, backoff), || -> Result<(), _> {
+            Err(Failure::Flaky)
+        });
+        assert_eq!(sleeps, [ms(100), ms(200), ms(400), ms(500), ms(500)]);
+        assert_eq!(result.unwrap_err().total_delay, ms(1700));
+    }
+
+    #[test]
+    fn exhausting_the_attempts_returns_the_last_error() {
+        let (result, sleeps) = run(&policy(3, Backoff::Fixed), || -> Result<(), _> {
+            Err(Failure::Flaky)
+        });
+        let err = result.unwrap_err();
+        assert_eq!((err.error, err.attempts, err.total_delay), (Failure::Flaky, 3, ms(200)));
+        assert_eq!(sleeps.len(), 2);
+    }
+
+    #[test]
+    fn zero_attempts_still_runs_once() {
+        let (result, sleeps) = run(&policy(0, Backoff::Fixed), || -> Result<(), _> {
+            Err(Failure::Flaky)
+        });
+        assert_eq!(result.unwrap_err().attempts, 1);
+        assert!(sleeps.is_empty());
+    }
+
+    #[test]
+    fn odd_factors_never_panic() {
+        for factor in [-2.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let p = policy(1, Backoff::Exponential { factor, cap: ms(300) });
+            for attempt in [0, 1, 2, 3, u32::MAX] {
+                let d = p.delay_after(attempt);
+                assert!(d <= ms(300), "factor {} attempt {} gave {:?}", factor, attempt, d);
+            }
+        }
+        // negative and non-finite factors fall back to the base delay
+        for factor in [-2.0, f64::NAN, f64::INFINITY] {
+            let p = policy(1, Backoff::Exponential { factor, cap: ms(300) });
+            assert_eq!(p.delay_after(3), ms(100));
+        }
+        // a factor below 1 shrinks the delay, and 0 means no pause after the first
+        let p = policy(1, Backoff::Exponential { factor: 0.5, cap: ms(300) });
+        assert_eq!(p.delay_after(2), ms(50));
+        let p = policy(1, Backoff::Exponential { factor: 0.0, cap: ms(300) });
+        assert_eq!(p.delay_after(2), Duration::ZERO);
+    }
+
+    #[test]
+    fn a_huge_total_delay_saturates() {
+        let huge = RetryPolicy {
+            max_attempts: 4,
+            base_delay: Duration::MAX / 2,
+            backoff: Backoff::Fixed,
+            retry_if: is_flaky,
+        };
+        let (result, sleeps) = run(&huge, || -> Result<(), _> { Err(Failure::Flaky) });
+        assert_eq!(sleeps, [Duration::MAX / 2; 3]);
+        assert_eq!(result.unwrap_err().total_delay, Duration::MAX);
+
+        // the cap is as big as a Duration gets
+        let capped = RetryPolicy {
+            base_delay: Duration::from_secs(u64::MAX / 4),
+            backoff: Backoff::Exponential { factor: 2.0, cap: Duration::MAX },
+            ..huge
+        };
+        for attempt in 1..6 {
+            assert!(capped.delay_after(attempt) >= Duration::from_secs(u64::MAX / 4));
+        }
+        let (result, _) = run(&capped, || -> Result<(), _> { Err(Failure::Flaky) });
+        assert_eq!(result.unwrap_err().total_delay, Duration::MAX);
+    }
+}
@@ -0,0 +1,202 @@
+// a fixed size buffer that keeps the newest `capacity` items: once it is
+// full, every push overwrites the oldest item
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RingBufferError {
+    ZeroCapacity,
+}
+
+impl fmt::Display for RingBufferError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RingBufferError::ZeroCapacity => {
+                write!(f, "a ring buffer needs room for at least one item")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RingBufferError {}
+
+#[derive(Debug, Clone)]
+pub struct RingBuffer<T> {
+    slots: Vec<Option<T>>,
+    // slot of the oldest item, the items follow it and wrap past the end
+    head: usize,
+    len: usize,
+}
+
+impl<T> RingBuffer<T> {
+    pub fn with_capacity(capacity: usize) -> Result<RingBuffer<T>, RingBufferError> {
+        if capacity == 0 {
+            return Err(RingBufferError::ZeroCapacity);
+        }
+        let slots = std::iter::repeat_with(|| None).take(capacity).collect();
+        Ok(RingBuffer { slots, head: 0, len: 0 })
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    // the slot `offset` items after the oldest one
+    fn slot(&self, offset: usize) -> usize {
+        (self.head + offset) % self.capacity()
+    }
+
+    // returns the oldest item when it had to be overwritten
+    pub fn push(&mut self, item: T) -> Option<T> {
+        if self.is_full() {
+            let oldest = self.slots[self.head].replace(item);
+            self.head = self.slot(1);
+            oldest
+        } else {
+            let tail = self.slot(self.len);
+            self.slots[tail] = Some(item);
+            self.len += 1;
+            None
+        }
+    }
+
+    pub fn pop_oldest(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let oldest = self.slots[self.head].take();
+        self.head = self.slot(1);
+        self.len -= 1;
+        oldest
+    }
+
+    pub fn latest(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.slots[self.slot(self.len - 1)].as_ref()
+    }
+
+    // oldest to newest
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { buffer: self, offset: 0 }
+    }
+}
+
+pub struct Iter<'a, T> {
+    buffer: &'a RingBuffer<T>,
+    offset: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.offset == self.buffer.len {
+            return None;
+        }
+        let item = self.buffer.slots[self.buffer.slot(self.offset)].as_ref();
+        self.offset += 1;
+        item
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(buffer: &RingBuffer<i32>) -> Vec<i32> {
+        buffer.iter().copied().collect()
+    }
+
+    #[test]
+    fn zero_capacity_is_rejected() {
+        assert_eq!(RingBuffer::<i32>::with_capacity(0).err(), Some(RingBufferError::ZeroCapacity));
+    }
+
+    #[test]
+    fn push_returns_what_it_overwrote() {
+        let mut buffer = RingBuffer::with_capacity(3).unwrap();
+        assert_eq!(buffer.push(1), None);
+        assert_eq!(buffer.push(2), None);
+        assert_eq!(buffer.push(3), None);
+        assert!(buffer.is_full());
+        assert_eq!(buffer.push(4), Some(1));
+        assert_eq!(buffer.push(5), Some(2));
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.latest(), Some(&5));
+    }
+
+    #[test]
+    fn wraps_around_several_times() {
+        let mut buffer = RingBuffer::with_capacity(4).unwrap();
+        for n in 0..23 {
+            let overwritten = buffer.push(n);
+            assert_eq!(overwritten, if n >= 4 { Some(n - 4) } else { None });
+        }
+        // five laps and a bit, the newest four are left
+        assert_eq!(items(&buffer), [19, 20, 21, 22]);
+        assert_eq!(buffer.capacity(), 4);
+    }
+
+    #[test]
+    fn iterates_oldest_to_newest_mid_wrap() {
+        let mut buffer = RingBuffer::with_capacity(5).unwrap();
+        for n in 1..=7 {
+            buffer.push(n);
+        }
+        // the head is on slot 2, the items cross the end of the Vec
+        assert_eq!(items(&buffer), [3, 4, 5, 6, 7]);
+        assert_eq!(buffer.pop_oldest(), Some(3));
+        assert_eq!(buffer.pop_oldest(), Some(4));
+        assert_eq!(items(&buffer), [5, 6, 7]);
+        assert!(!buffer.is_full());
+        buffer.push(8);
+        assert_eq!((&buffer).into_iter().copied().collect::<Vec<_>>(), [5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn pop_until_empty() {
+        let mut buffer = RingBuffer::with_capacity(2).unwrap();
+        buffer.push(1);
+        buffer.push(2);
+        buffer.push(3);
+        assert_eq!(buffer.pop_oldest(), Some(2));
+        assert_eq!(buffer.pop_oldest(), Some(3));
+        assert_eq!(buffer.pop_oldest(), None);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.latest(), None);
+        assert!(items(&buffer).is_empty());
+    }
+
+    #[test]
+    fn a_capacity_of_one_keeps_only_the_newest() {
+        let mut buffer = RingBuffer::with_capacity(1).unwrap();
+        assert_eq!(buffer.push(1), None);
+        assert_eq!(buffer.push(2), Some(1));
+        assert_eq!(buffer.push(3), Some(2));
+        assert_eq!(items(&buffer), [3]);
+        assert_eq!(buffer.latest(), Some(&3));
+    }
+}
@@ -0,0 +1,92 @@
+// a tiny seeded random number generator (splitmix64)
+// not for anything secret, just for examples that must print the same thing every run
+
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // a number in low..high, high is not included
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low < high, "empty range {}..{}", low, high);
+        // worked out in u64 so even i64::MIN..i64::MAX doesn't overflow
+        let span = (high as u64).wrapping_sub(low as u64);
+        low.wrapping_add((self.next_u64() % span) as i64)
+    }
+
+    // a float in 0.0..1.0
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() as i64) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_same_seed_gives_the_same_sequence() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+    }
+
+    #[test]
+    fn range_stays_inside_and_reaches_both_ends() {
+        let mut rng = Rng::new(1);
+        let values: Vec<i64> = (0..1000).map(|_| rng.range(-2, 3)).collect();
+        assert!(values.iter().all(|v| (-2..3).contains(v)));
+        assert!(values.contains(&-2) && values.contains(&2));
+        assert_eq!(rng.range(5, 6), 5);
+    }
+
+    #[test]
+    fn the_widest_range_does_not_overflow() {
+        let mut rng = Rng::new(3);
+        for _ in 0..100 {
+            let v = rng.range(i64::MIN, i64::MAX);
+            assert!(v < i64::MAX);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn an_empty_range_panics() {
+        Rng::new(0).range(4, 4);
+    }
+
+    #[test]
+    fn floats_are_in_the_unit_interval() {
+        let mut rng = Rng::new(9);
+        assert!((0..1000).map(|_| rng.next_f64()).all(|f| (0.0..1.0).contains(&f)));
+    }
+
+    #[test]
+    fn pick_returns_an_item_of_the_slice() {
+        let items = ["a", "b", "c"];
+        let mut rng = Rng::new(2);
+        for _ in 0..50 {
+            assert!(items.contains(rng.pick(&items)));
+        }
+    }
+}
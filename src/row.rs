@@ -0,0 +1,455 @@
+// ToRow and FromRow turn a value into a row of strings and back, so CSV
+// files and printed tables work the same way for every type that has them
+
+use std::fmt;
+use std::io::{self, Write};
+
+use chrono::{DateTime, Utc};
+
+use crate::datetime::events::Event;
+use crate::rect::Rect;
+use crate::str_ext::StrExt;
+use crate::user::{User, UserError};
+
+pub trait ToRow {
+    fn headers() -> Vec<&'static str>;
+    fn to_row(&self) -> Vec<String>;
+}
+
+pub trait FromRow: Sized {
+    fn from_row(row: &[&str]) -> Result<Self, RowError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowError {
+    // the row has more or fewer fields than there are headers
+    WrongLength { expected: usize, got: usize },
+    BadValue { column: &'static str, value: String, reason: String },
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RowError::WrongLength { expected, got } => {
+                write!(f, "expected {} fields, got {}", expected, got)
+            }
+            RowError::BadValue { column, value, reason } => {
+                write!(f, "column `{}` = `{}`: {}", column, value, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RowError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CsvError {
+    Empty,
+    // the first line isn't the header T::headers() gives
+    BadHeader { expected: Vec<&'static str>, got: Vec<String> },
+    Row { line: usize, source: RowError },
+    // a quoted field that is still open at the end of the text
+    UnclosedQuote { line: usize },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsvError::Empty => write!(f, "no header line"),
+            CsvError::BadHeader { expected, got } => {
+                write!(f, "expected header {}, got {}", expected.join(","), got.join(","))
+            }
+            CsvError::Row { line, .. } => write!(f, "bad row on line {}", line),
+            CsvError::UnclosedQuote { line } => {
+                write!(f, "quoted field starting on line {} is never closed", line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CsvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CsvError::Row { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+// checked before from_row looks at any field, so every impl can index freely
+fn check_len<T: ToRow>(row: &[&str]) -> Result<(), RowError> {
+    let expected = T::headers().len();
+    if row.len() == expected {
+        Ok(())
+    } else {
+        Err(RowError::WrongLength { expected, got: row.len() })
+    }
+}
+
+fn parse_field<V: std::str::FromStr>(column: &'static str, value: &str) -> Result<V, RowError>
+where
+    V::Err: fmt::Display,
+{
+    value.trim().parse().map_err(|err: V::Err| RowError::BadValue {
+        column,
+        value: value.to_string(),
+        reason: err.to_string(),
+    })
+}
+
+impl ToRow for User {
+    fn headers() -> Vec<&'static str> {
+        vec!["name", "username", "email", "user_id", "is_indian"]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.username.clone(),
+            self.email.clone(),
+            self.user_id.to_string(),
+            self.is_indian.to_string(),
+        ]
+    }
+}
+
+// goes through User::new, so a row can't make a user new would refuse
+impl FromRow for User {
+    fn from_row(row: &[&str]) -> Result<User, RowError> {
+        check_len::<User>(row)?;
+        let user_id = parse_field("user_id", row[3])?;
+        let is_indian = parse_field("is_indian", row[4])?;
+        User::new(row[0], row[1], row[2], user_id, is_indian).map_err(|err| {
+            let (column, value) = match &err {
+                UserError::EmptyName => ("name", row[0]),
+                UserError::BadUsername(_) => ("username", row[1]),
+                UserError::BadEmail(_) => ("email", row[2]),
+                UserError::BadId(_) => ("user_id", row[3]),
+            };
+            RowError::BadValue { column, value: value.to_string(), reason: err.to_string() }
+        })
+    }
+}
+
+impl ToRow for Rect {
+    fn headers() -> Vec<&'static str> {
+        vec!["len", "breadth"]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![self.len.to_string(), self.breadth.to_string()]
+    }
+}
+
+impl FromRow for Rect {
+    fn from_row(row: &[&str]) -> Result<Rect, RowError> {
+        check_len::<Rect>(row)?;
+        Ok(Rect::new(parse_field("len", row[0])?, parse_field("breadth", row[1])?))
+    }
+}
+
+// the times are written as RFC 3339 so they read back exactly
+impl ToRow for Event {
+    fn headers() -> Vec<&'static str> {
+        vec!["title", "start", "end"]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![self.title.clone(), self.start.to_rfc3339(), self.end.to_rfc3339()]
+    }
+}
+
+impl FromRow for Event {
+    fn from_row(row: &[&str]) -> Result<Event, RowError> {
+        check_len::<Event>(row)?;
+        let start: DateTime<Utc> = parse_field("start", row[1])?;
+        let end: DateTime<Utc> = parse_field("end", row[2])?;
+        Event::new(row[0], start, end).map_err(|err| RowError::BadValue {
+            column: "end",
+            value: row[2].to_string(),
+            reason: err.to_string(),
+        })
+    }
+}
+
+// a field with a comma, a quote or a line break goes in quotes, with
+// any quote inside written twice
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// one parsed record and the line it starts on, counting from 1
+struct Record {
+    line: usize,
+    fields: Vec<String>,
+}
+
+// the reverse of csv_field over the whole text: a line break inside quotes
+// is part of the field, outside quotes it ends the record. "\r\n" counts as
+// one break, and records that are only whitespace are skipped
+fn split_csv_records(text: &str) -> Result<Vec<Record>, CsvError> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut blank = true;
+    let mut line = 1;
+    let mut start = 1;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            line += 1;
+        }
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => {
+                quoted = !quoted;
+                blank = false;
+            }
+            (',', false) => {
+                fields.push(std::mem::take(&mut field));
+                blank = false;
+            }
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+            ('\n', false) | ('\r', false) => {
+                fields.push(std::mem::take(&mut field));
+                let fields = std::mem::take(&mut fields);
+                if !blank {
+                    records.push(Record { line: start, fields });
+                }
+                blank = true;
+                start = line;
+            }
+            _ => {
+                blank &= c.is_whitespace();
+                field.push(c);
+            }
+        }
+    }
+    if quoted {
+        return Err(CsvError::UnclosedQuote { line: start });
+    }
+    fields.push(field);
+    if !blank {
+        records.push(Record { line: start, fields });
+    }
+    Ok(records)
+}
+
+// a header line and then one line per item
+pub fn write_csv<T: ToRow>(items: &[T], w: &mut dyn Write) -> io::Result<()> {
+    writeln!(w, "{}", T::headers().join(","))?;
+    for item in items {
+        let fields: Vec<String> = item.to_row().iter().map(|f| csv_field(f)).collect();
+        writeln!(w, "{}", fields.join(","))?;
+    }
+    Ok(())
+}
+
+pub fn to_csv<T: ToRow>(items: &[T]) -> String {
+    let mut out = Vec::new();
+    write_csv(items, &mut out).expect("writing to a Vec can't fail");
+    String::from_utf8(out).expect("every field was a String")
+}
+
+// blank lines are skipped, line numbers in errors count from 1 like an editor
+// and point at the line a record starts on
+pub fn read_csv<T: ToRow + FromRow>(text: &str) -> Result<Vec<T>, CsvError> {
+    let mut records = split_csv_records(text)?.into_iter();
+    let got = records.next().ok_or(CsvError::Empty)?.fields;
+    let expected = T::headers();
+    // "User ID", "userId" and "user-id" all count as the user_id header
+    let matches = got.len() == expected.len()
+        && got.iter().zip(&expected).all(|(g, e)| g.to_snake_case() == *e);
+    if !matches {
+        return Err(CsvError::BadHeader { expected, got });
+    }
+    records
+        .map(|record| {
+            let row: Vec<&str> = record.fields.iter().map(String::as_str).collect();
+            T::from_row(&row).map_err(|source| CsvError::Row { line: record.line, source })
+        })
+        .collect()
+}
+
+// the headers and rows lined up in columns as wide as their widest cell.
+// a row whose length doesn't match the headers is an error, not a ragged table
+pub fn format_table<T: ToRow>(items: &[T]) -> Result<String, RowError> {
+    let headers = T::headers();
+    let rows: Vec<Vec<String>> = items.iter().map(ToRow::to_row).collect();
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in &rows {
+        if row.len() != headers.len() {
+            return Err(RowError::WrongLength { expected: headers.len(), got: row.len() });
+        }
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    let mut push_line = |cells: &[&str]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        out.push_str(padded.join("  ").trim_end());
+        out.push('\n');
+    };
+    push_line(&headers);
+    for row in &rows {
+        let cells: Vec<&str> = row.iter().map(String::as_str).collect();
+        push_line(&cells);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn user(name: &str, username: &str, id: i32) -> User {
+        User::new(name, username, &format!("{}@example.com", username), id, true).unwrap()
+    }
+
+    fn event(title: &str, start_hour: u32, end_hour: u32) -> Event {
+        let at = |h| Utc.with_ymd_and_hms(2024, 3, 1, h, 0, 0).unwrap();
+        Event::new(title, at(start_hour), at(end_hour)).unwrap()
+    }
+
+    fn round_trip_row<T: ToRow + FromRow>(item: &T) -> T {
+        let fields = item.to_row();
+        assert_eq!(fields.len(), T::headers().len());
+        let row: Vec<&str> = fields.iter().map(String::as_str).collect();
+        T::from_row(&row).unwrap()
+    }
+
+    #[test]
+    fn each_type_round_trips_through_a_row() {
+        let u = user("Pawan, \"PK\"", "pawan", 7);
+        assert_eq!(round_trip_row(&u), u);
+        let r = Rect::new(3, 4);
+        assert_eq!(round_trip_row(&r), r);
+        let e = event("standup", 9, 10);
+        assert_eq!(round_trip_row(&e), e);
+    }
+
+    #[test]
+    fn each_type_round_trips_through_csv() {
+        let users = vec![user("Asha", "asha", 1), user("Line\nBreak, \"quoted\"", "lb_2", 2)];
+        assert_eq!(read_csv::<User>(&to_csv(&users)).unwrap(), users);
+        let rects = vec![Rect::new(1, 2), Rect::new(30, 40)];
+        assert_eq!(read_csv::<Rect>(&to_csv(&rects)).unwrap(), rects);
+        let events = vec![event("two\r\nline title", 9, 10), event("a,b", 11, 13)];
+        assert_eq!(read_csv::<Event>(&to_csv(&events)).unwrap(), events);
+    }
+
+    #[test]
+    fn user_csv_is_the_plain_header_and_rows() {
+        let csv = to_csv(&[user("Asha", "asha", 1), user("Ravi, Jr", "ravi", 2)]);
+        assert_eq!(
+            csv,
+            "name,username,email,user_id,is_indian\n\
+             Asha,asha,asha@example.com,1,true\n\
+             \"Ravi, Jr\",ravi,ravi@example.com,2,true\n"
+        );
+    }
+
+    #[test]
+    fn wrong_length_rows_are_errors() {
+        assert_eq!(
+            Rect::from_row(&["1"]),
+            Err(RowError::WrongLength { expected: 2, got: 1 })
+        );
+        assert_eq!(
+            User::from_row(&["a", "b", "c", "1", "true", "extra"]),
+            Err(RowError::WrongLength { expected: 5, got: 6 })
+        );
+        let err = read_csv::<Rect>("len,breadth\n1,2\n3\n").unwrap_err();
+        assert_eq!(
+            err,
+            CsvError::Row { line: 3, source: RowError::WrongLength { expected: 2, got: 1 } }
+        );
+    }
+
+    #[test]
+    fn bad_values_name_the_column() {
+        match Rect::from_row(&["1", "wide"]) {
+            Err(RowError::BadValue { column, value, .. }) => {
+                assert_eq!((column, value.as_str()), ("breadth", "wide"));
+            }
+            other => panic!("expected a bad value, got {:?}", other),
+        }
+        match User::from_row(&["Asha", "asha", "not-an-email", "1", "true"]) {
+            Err(RowError::BadValue { column, .. }) => assert_eq!(column, "email"),
+            other => panic!("expected a bad value, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn headers_are_checked_loosely_but_must_match() {
+        assert_eq!(read_csv::<Rect>("Len,Breadth\n1,2\n").unwrap(), [Rect::new(1, 2)]);
+        assert!(matches!(read_csv::<Rect>("width,height\n1,2\n"), Err(CsvError::BadHeader { .. })));
+        assert_eq!(read_csv::<Rect>(""), Err(CsvError::Empty));
+        assert_eq!(read_csv::<Rect>("  \n\n"), Err(CsvError::Empty));
+    }
+
+    #[test]
+    fn line_numbers_point_at_the_start_of_the_record() {
+        // the second event spans lines 3 and 4, so the bad one starts on line 5
+        let text = "title,start,end\n\n\"a\nb\",2024-03-01T09:00:00Z,2024-03-01T10:00:00Z\n\
+                    late,2024-03-01T10:00:00Z,2024-03-01T09:00:00Z\n";
+        match read_csv::<Event>(text) {
+            Err(CsvError::Row { line, .. }) => assert_eq!(line, 5),
+            other => panic!("expected a row error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn an_unclosed_quote_is_an_error() {
+        assert_eq!(
+            read_csv::<Rect>("len,breadth\n\"1,2\n3,4\n"),
+            Err(CsvError::UnclosedQuote { line: 2 })
+        );
+    }
+
+    #[test]
+    fn crlf_files_read_the_same() {
+        assert_eq!(
+            read_csv::<Rect>("len,breadth\r\n1,2\r\n3,4\r\n").unwrap(),
+            [Rect::new(1, 2), Rect::new(3, 4)]
+        );
+    }
+
+    #[test]
+    fn table_columns_are_as_wide_as_their_widest_cell() {
+        let table = format_table(&[Rect::new(1, 2), Rect::new(1000, 3)]).unwrap();
+        assert_eq!(table, "len   breadth\n1     2\n1000  3\n");
+    }
+
+    struct Ragged;
+
+    impl ToRow for Ragged {
+        fn headers() -> Vec<&'static str> {
+            vec!["a", "b"]
+        }
+
+        fn to_row(&self) -> Vec<String> {
+            vec![String::from("only one")]
+        }
+    }
+
+    #[test]
+    fn table_rejects_rows_that_dont_match_the_headers() {
+        assert_eq!(format_table(&[Ragged]), Err(RowError::WrongLength { expected: 2, got: 1 }));
+    }
+}
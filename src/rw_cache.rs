@@ -0,0 +1,184 @@
+// a cache for data that is read far more often than it is written:
+// any number of readers at once, writers take turns
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheMetrics {
+    // every get and get_or_insert_with counts as exactly one of the two
+    pub fn total(&self) -> u64 {
+        self.hits + self.misses
+    }
+}
+
+pub struct RwCache<K: Eq + Hash + Clone, V: Clone> {
+    map: RwLock<HashMap<K, V>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> RwCache<K, V> {
+    pub fn new() -> RwCache<K, V> {
+        RwCache {
+            map: RwLock::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, hit: bool) {
+        let counter = if hit { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        let value = self.map.read().unwrap().get(key).cloned();
+        self.record(value.is_some());
+        value
+    }
+
+    // f runs while the write lock is held, and only after checking the key
+    // again under that lock, so threads racing on one key call f once between
+    // them (again only after an invalidate). the price is that readers wait
+    // while f runs
+    pub fn get_or_insert_with(&self, key: K, f: impl FnOnce() -> V) -> V {
+        if let Some(value) = self.map.read().unwrap().get(&key) {
+            self.record(true);
+            return value.clone();
+        }
+        let mut map = self.map.write().unwrap();
+        if let Some(value) = map.get(&key) {
+            self.record(true);
+            return value.clone();
+        }
+        self.record(false);
+        let value = f();
+        map.insert(key, value.clone());
+        value
+    }
+
+    // the old value, if there was one
+    pub fn invalidate(&self, key: &K) -> Option<V> {
+        self.map.write().unwrap().remove(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Default for RwCache<K, V> {
+    fn default() -> Self {
+        RwCache::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Barrier;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn racing_threads_compute_a_key_once() {
+        let cache: RwCache<&str, u64> = RwCache::new();
+        let calls = AtomicUsize::new(0);
+        let barrier = Barrier::new(16);
+        thread::scope(|s| {
+            for _ in 0..16 {
+                s.spawn(|| {
+                    barrier.wait();
+                    for _ in 0..50 {
+                        let value = cache.get_or_insert_with("answer", || {
+                            calls.fetch_add(1, Ordering::SeqCst);
+                            thread::sleep(Duration::from_millis(20));
+                            42
+                        });
+                        assert_eq!(value, 42);
+                    }
+                });
+            }
+        });
+        // the documented number: once, since the key is checked again under the write lock
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let metrics = cache.metrics();
+        assert_eq!(metrics.total(), 16 * 50);
+        assert_eq!(metrics, CacheMetrics { hits: 16 * 50 - 1, misses: 1 });
+    }
+
+    #[test]
+    fn metrics_count_every_get() {
+        let cache: RwCache<u32, String> = RwCache::new();
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get_or_insert_with(1, || String::from("one")), "one");
+        assert_eq!(cache.get(&1).as_deref(), Some("one"));
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get_or_insert_with(1, || unreachable!()), "one");
+        assert_eq!(cache.metrics(), CacheMetrics { hits: 2, misses: 3 });
+        assert_eq!(cache.metrics().total(), 5);
+    }
+
+    #[test]
+    fn invalidate_makes_the_next_call_recompute() {
+        let cache: RwCache<&str, u32> = RwCache::new();
+        let mut runs = 0;
+        cache.get_or_insert_with("k", || {
+            runs += 1;
+            1
+        });
+        assert_eq!(cache.invalidate(&"k"), Some(1));
+        assert_eq!(cache.invalidate(&"k"), None);
+        assert!(cache.is_empty());
+        let value = cache.get_or_insert_with("k", || {
+            runs += 1;
+            2
+        });
+        assert_eq!((value, runs), (2, 2));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn many_keys_from_many_threads() {
+        let cache: RwCache<u64, u64> = RwCache::new();
+        let calls = AtomicUsize::new(0);
+        thread::scope(|s| {
+            for t in 0..8 {
+                let (cache, calls) = (&cache, &calls);
+                s.spawn(move || {
+                    for n in 0..100 {
+                        let key = (n + t) % 20;
+                        let value = cache.get_or_insert_with(key, || {
+                            calls.fetch_add(1, Ordering::SeqCst);
+                            key * key
+                        });
+                        assert_eq!(value, key * key);
+                    }
+                });
+            }
+        });
+        assert_eq!(calls.load(Ordering::SeqCst), 20);
+        assert_eq!(cache.len(), 20);
+        assert_eq!(cache.metrics(), CacheMetrics { hits: 800 - 20, misses: 20 });
+    }
+}
@@ -0,0 +1,129 @@
+// deterministic example data, the same seed always gives the same values
+
+use crate::lazy::Lazy;
+use crate::point::Point;
+use crate::rng::Rng;
+use crate::shape::Shape;
+use crate::user::User;
+
+const WORDS: &str = include_str!("words.txt");
+
+const NAMES: [&str; 12] = [
+    "Pawan", "Asha", "Ravi", "Meera", "Arjun", "Sara", "Kabir", "Lena", "Omar", "Yuki", "Ines",
+    "Tom",
+];
+
+// split up the first time it is used, every later call gets the same list
+static WORD_LIST: Lazy<Vec<&'static str>> =
+    Lazy::new(|| WORDS.lines().map(str::trim).filter(|w| !w.is_empty()).collect());
+
+pub fn word_list() -> &'static [&'static str] {
+    WORD_LIST.get()
+}
+
+pub fn sample_numbers(seed: u64, n: usize) -> Vec<i32> {
+    let mut rng = Rng::new(seed);
+    (0..n).map(|_| rng.range(-1000, 1001) as i32).collect()
+}
+
+pub fn sample_words(seed: u64, n: usize) -> Vec<String> {
+    let words = word_list();
+    let mut rng = Rng::new(seed);
+    (0..n).map(|_| rng.pick(words).to_string()).collect()
+}
+
+// every user is built through User::new, so they are all valid
+pub fn sample_users(seed: u64, n: usize) -> Vec<User> {
+    let mut rng = Rng::new(seed);
+    (0..n)
+        .map(|i| {
+            let name = *rng.pick(&NAMES);
+            let username = format!("{}_{}", name.to_lowercase(), rng.range(10, 100));
+            let email = format!("{}@example.com", username);
+            let is_indian = rng.range(0, 2) == 1;
+            User::new(name, &username, &email, i as i32 + 1, is_indian)
+                .expect("sample users are always valid")
+        })
+        .collect()
+}
+
+pub fn sample_points(seed: u64, n: usize) -> Vec<Point> {
+    let mut rng = Rng::new(seed);
+    (0..n)
+        .map(|_| Point::new(rng.range(-100, 101) as i32, rng.range(-100, 101) as i32))
+        .collect()
+}
+
+// rects, circles and scaled shapes, about one in twenty has a negative
+// dimension so calc_area has something to reject
+pub fn sample_shapes(seed: u64, n: usize) -> Vec<Shape> {
+    let mut rng = Rng::new(seed);
+    (0..n)
+        .map(|_| {
+            let sign = if rng.range(0, 20) == 0 { -1.0 } else { 1.0 };
+            let size = sign * rng.range(1, 100) as f64 / 10.0;
+            match rng.range(0, 3) {
+                0 => Shape::Rect(size, rng.range(1, 100) as f64 / 10.0),
+                1 => Shape::Circle(size),
+                _ => Shape::Scaled(rng.range(1, 5) as f64, Box::new(Shape::Circle(size))),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_same_seed_gives_the_same_data() {
+        assert_eq!(sample_numbers(1, 50), sample_numbers(1, 50));
+        assert_eq!(sample_words(1, 50), sample_words(1, 50));
+        assert_eq!(sample_users(1, 50), sample_users(1, 50));
+        assert_eq!(sample_points(1, 50), sample_points(1, 50));
+        assert_eq!(sample_shapes(1, 50), sample_shapes(1, 50));
+    }
+
+    #[test]
+    fn different_seeds_give_different_data() {
+        assert_ne!(sample_numbers(1, 20), sample_numbers(2, 20));
+        assert_ne!(sample_words(1, 20), sample_words(2, 20));
+        assert_ne!(sample_users(1, 20), sample_users(2, 20));
+        assert_ne!(sample_points(1, 20), sample_points(2, 20));
+        assert_ne!(sample_shapes(1, 20), sample_shapes(2, 20));
+    }
+
+    #[test]
+    fn a_shorter_run_is_a_prefix_of_a_longer_one() {
+        assert_eq!(sample_numbers(5, 10)[..], sample_numbers(5, 30)[..10]);
+        assert!(sample_users(5, 0).is_empty());
+    }
+
+    #[test]
+    fn users_pass_validation_and_have_unique_ids() {
+        for user in sample_users(11, 200) {
+            let again = User::new(
+                &user.name,
+                &user.username,
+                &user.email,
+                user.user_id,
+                user.is_indian,
+            );
+            assert_eq!(again.as_ref(), Ok(&user));
+            assert!(user.validate().is_ok());
+        }
+        let ids: Vec<i32> = sample_users(11, 5).iter().map(|u| u.user_id).collect();
+        assert_eq!(ids, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn values_stay_in_their_ranges() {
+        assert!(sample_numbers(3, 500).iter().all(|n| (-1000..=1000).contains(n)));
+        for p in sample_points(3, 500) {
+            assert!((-100..=100).contains(&p.x) && (-100..=100).contains(&p.y));
+        }
+        let words = word_list();
+        assert!(!words.is_empty());
+        assert!(sample_words(3, 100).iter().all(|w| words.contains(&w.as_str())));
+    }
+}
@@ -0,0 +1,198 @@
+// a priority queue of tasks on top of BinaryHeap
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BinaryHeap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Task {
+    pub name: String,
+    pub priority: u8,
+    // when the task was made, a smaller number is older
+    pub created: u64,
+}
+
+// BinaryHeap pops the greatest element, so "greater" has to mean "runs
+// first": a higher priority, or on a tie an older (smaller) created.
+// that second comparison is the wrong way round on purpose
+impl Ord for Task {
+    fn cmp(&self, other: &Task) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then(other.created.cmp(&self.created))
+            .then_with(|| other.name.cmp(&self.name))
+    }
+}
+
+impl PartialOrd for Task {
+    fn partial_cmp(&self, other: &Task) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// what the heap holds: the task plus a number that add hands out in turn,
+// so two tasks with the same name and created can still be told apart
+#[derive(Debug, PartialEq, Eq)]
+struct Entry {
+    task: Task,
+    seq: u64,
+}
+
+// on a complete tie the task added first runs first
+impl Ord for Entry {
+    fn cmp(&self, other: &Entry) -> Ordering {
+        self.task.cmp(&other.task).then(other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Entry) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Default)]
+pub struct Scheduler {
+    heap: BinaryHeap<Entry>,
+    next_seq: u64,
+    // a heap can't remove from the middle, so cancelled tasks stay in
+    // it and are marked here by seq until they reach the top
+    cancelled: HashSet<u64>,
+}
+
+impl Scheduler {
+    pub fn new() -> Scheduler {
+        Scheduler::default()
+    }
+
+    pub fn add(&mut self, task: Task) {
+        self.heap.push(Entry { task, seq: self.next_seq });
+        self.next_seq += 1;
+    }
+
+    // drops cancelled tasks off the top of the heap
+    fn compact(&mut self) {
+        while let Some(top) = self.heap.peek() {
+            if !self.cancelled.remove(&top.seq) {
+                break;
+            }
+            self.heap.pop();
+        }
+    }
+
+    pub fn next_task(&mut self) -> Option<Task> {
+        self.compact();
+        self.heap.pop().map(|entry| entry.task)
+    }
+
+    // &mut because it may have to clear cancelled tasks off the top first
+    pub fn peek(&mut self) -> Option<&Task> {
+        self.compact();
+        self.heap.peek().map(|entry| &entry.task)
+    }
+
+    // cancels every waiting task with this name, false if there were none
+    pub fn cancel(&mut self, name: &str) -> bool {
+        let seqs: Vec<u64> = self
+            .heap
+            .iter()
+            .filter(|entry| entry.task.name == name && !self.cancelled.contains(&entry.seq))
+            .map(|entry| entry.seq)
+            .collect();
+        let found = !seqs.is_empty();
+        self.cancelled.extend(seqs);
+        found
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len() - self.cancelled.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // how many live tasks wait at each priority
+    pub fn pending_by_priority(&self) -> BTreeMap<u8, usize> {
+        let mut counts = BTreeMap::new();
+        for entry in self.heap.iter().filter(|entry| !self.cancelled.contains(&entry.seq)) {
+            *counts.entry(entry.task.priority).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(name: &str, priority: u8, created: u64) -> Task {
+        Task { name: name.to_string(), priority, created }
+    }
+
+    fn drain(scheduler: &mut Scheduler) -> Vec<String> {
+        std::iter::from_fn(|| scheduler.next_task()).map(|t| t.name).collect()
+    }
+
+    #[test]
+    fn higher_priority_first() {
+        let mut s = Scheduler::new();
+        s.add(task("low", 1, 0));
+        s.add(task("high", 9, 1));
+        s.add(task("mid", 5, 2));
+        assert_eq!(s.peek().map(|t| t.name.as_str()), Some("high"));
+        assert_eq!(drain(&mut s), ["high", "mid", "low"]);
+        assert_eq!(s.next_task(), None);
+    }
+
+    #[test]
+    fn fifo_within_a_priority() {
+        let mut s = Scheduler::new();
+        s.add(task("third", 3, 30));
+        s.add(task("first", 3, 10));
+        s.add(task("second", 3, 20));
+        assert_eq!(drain(&mut s), ["first", "second", "third"]);
+    }
+
+    #[test]
+    fn cancel_before_pop() {
+        let mut s = Scheduler::new();
+        s.add(task("keep", 1, 0));
+        s.add(task("drop", 9, 1));
+        assert!(s.cancel("drop"));
+        assert!(!s.cancel("drop"));
+        assert!(!s.cancel("missing"));
+        assert_eq!(s.len(), 1);
+        assert_eq!(s.peek().map(|t| t.name.as_str()), Some("keep"));
+        assert_eq!(s.pending_by_priority(), BTreeMap::from([(1, 1)]));
+        assert_eq!(drain(&mut s), ["keep"]);
+        assert!(s.is_empty());
+    }
+
+    // the same name and created twice must not share one tombstone
+    #[test]
+    fn duplicates_are_all_cancelled() {
+        let mut s = Scheduler::new();
+        s.add(task("backup", 5, 100));
+        s.add(task("backup", 5, 100));
+        s.add(task("other", 1, 0));
+        assert!(s.cancel("backup"));
+        assert_eq!(s.len(), 1);
+        assert_eq!(drain(&mut s), ["other"]);
+    }
+
+    #[test]
+    fn cancelled_tasks_never_come_out() {
+        let mut s = Scheduler::new();
+        for i in 0..50u64 {
+            let name = if i % 3 == 0 { "cancel me" } else { "run" };
+            s.add(task(name, (i % 7) as u8, i));
+        }
+        s.cancel("cancel me");
+        // a later task with the cancelled name is a new task and does run
+        s.add(task("cancel me", 0, 99));
+        let names = drain(&mut s);
+        assert_eq!(names.len(), 50 - 17 + 1);
+        assert_eq!(names.last().map(String::as_str), Some("cancel me"));
+        assert_eq!(names.iter().filter(|n| *n == "cancel me").count(), 1);
+    }
+}
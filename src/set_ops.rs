@@ -0,0 +1,97 @@
+// a few questions about sets that the std methods don't answer directly
+
+use std::collections::{BTreeSet, HashSet};
+use std::hash::Hash;
+
+// |a & b| / |a | b|, from 0.0 (nothing shared) to 1.0 (the same set).
+// two empty sets are taken to be identical, so that gives 1.0 too
+pub fn jaccard_similarity<T: Eq + Hash>(a: &HashSet<T>, b: &HashSet<T>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+// in one set but not both, in sorted order
+pub fn symmetric_diff_sorted<T: Ord + Clone>(a: &BTreeSet<T>, b: &BTreeSet<T>) -> Vec<T> {
+    a.symmetric_difference(b).cloned().collect()
+}
+
+// each set is a subset of the one after it, true for 0 or 1 sets
+pub fn is_subset_chain<T: Eq + Hash>(sets: &[HashSet<T>]) -> bool {
+    sets.windows(2).all(|w| w[0].is_subset(&w[1]))
+}
+
+// items in every set, empty when there are no sets. starts from the smallest
+// set, since nothing outside it can be in the answer
+pub fn multi_intersection<T: Eq + Hash + Clone>(sets: &[HashSet<T>]) -> HashSet<T> {
+    let Some(smallest) = sets.iter().min_by_key(|set| set.len()) else {
+        return HashSet::new();
+    };
+    smallest
+        .iter()
+        .filter(|item| sets.iter().all(|set| set.contains(*item)))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(items: &[u32]) -> HashSet<u32> {
+        items.iter().copied().collect()
+    }
+
+    fn tree(items: &[u32]) -> BTreeSet<u32> {
+        items.iter().copied().collect()
+    }
+
+    #[test]
+    fn empty_sets() {
+        assert_eq!(jaccard_similarity(&set(&[]), &set(&[])), 1.0);
+        assert_eq!(jaccard_similarity(&set(&[]), &set(&[1])), 0.0);
+        assert!(symmetric_diff_sorted(&tree(&[]), &tree(&[])).is_empty());
+        assert!(is_subset_chain::<u32>(&[]));
+        assert!(is_subset_chain(&[set(&[])]));
+        assert!(multi_intersection::<u32>(&[]).is_empty());
+        assert!(multi_intersection(&[set(&[1, 2]), set(&[])]).is_empty());
+    }
+
+    #[test]
+    fn disjoint_sets() {
+        let (a, b) = (set(&[1, 2]), set(&[3, 4, 5]));
+        assert_eq!(jaccard_similarity(&a, &b), 0.0);
+        assert_eq!(symmetric_diff_sorted(&tree(&[5, 1]), &tree(&[4, 2])), [1, 2, 4, 5]);
+        assert!(!is_subset_chain(&[a.clone(), b.clone()]));
+        assert!(multi_intersection(&[a, b]).is_empty());
+    }
+
+    #[test]
+    fn identical_sets() {
+        let a = set(&[1, 2, 3]);
+        assert_eq!(jaccard_similarity(&a, &a.clone()), 1.0);
+        assert!(symmetric_diff_sorted(&tree(&[1, 2]), &tree(&[2, 1])).is_empty());
+        assert!(is_subset_chain(&[a.clone(), a.clone(), a.clone()]));
+        assert_eq!(multi_intersection(&[a.clone(), a.clone()]), a);
+    }
+
+    #[test]
+    fn partly_overlapping_sets() {
+        let (a, b) = (set(&[1, 2, 3, 4]), set(&[3, 4, 5, 6]));
+        // 2 shared out of 6
+        assert!((jaccard_similarity(&a, &b) - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(symmetric_diff_sorted(&tree(&[1, 2, 3, 4]), &tree(&[3, 4, 5, 6])), [1, 2, 5, 6]);
+        let three = [set(&[1, 2, 3, 4, 5]), set(&[2, 3, 4, 9]), set(&[3, 4, 7, 8, 2, 0])];
+        assert_eq!(multi_intersection(&three), set(&[2, 3, 4]));
+    }
+
+    #[test]
+    fn subset_chains() {
+        assert!(is_subset_chain(&[set(&[1]), set(&[1, 2]), set(&[1, 2, 3])]));
+        // the links have to be in order
+        assert!(!is_subset_chain(&[set(&[1, 2]), set(&[1]), set(&[1, 2, 3])]));
+        assert!(!is_subset_chain(&[set(&[1]), set(&[1, 2]), set(&[2, 3])]));
+    }
+}
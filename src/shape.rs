@@ -0,0 +1,900 @@
+// the Shape enum from enum_pt_match, shared so other bins can use it too
+
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+use std::fmt;
+use std::sync::mpsc::channel;
+
+use crate::iter_ext::IterExt;
+use crate::pairs::MinMax;
+use crate::thread_pool::ThreadPool;
+use crate::validate::{ValidationIssue, Validator};
+
+// non_exhaustive: crates using this enum (every bin is one) must have a
+// `_` arm when they match on it, so adding a variant later won't break them.
+// worth it for a public enum that is expected to grow, not for a closed set
+// like Ordering where a new variant would be a breaking change anyway
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Shape {
+    Rect(f64, f64),
+    Circle(f64),
+    // a shape drawn `factor` times bigger, build it with Shape::scaled
+    Scaled(f64, Box<Shape>),
+    // several shapes together, the area is the sum of theirs (overlaps count twice)
+    Group(Vec<Shape>),
+}
+
+// which variant a shape is, without its data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ShapeKind {
+    Rect,
+    Circle,
+    Scaled,
+    Group,
+}
+
+// Shape::accept calls the method for the variant. a variant added later will
+// get a method with a default, so old visitors still compile
+pub trait ShapeVisitor: AsVisitor {
+    fn visit_rect(&mut self, length: f64, breadth: f64);
+    fn visit_circle(&mut self, radius: f64);
+    fn visit_scaled(&mut self, factor: f64, inner: &Shape);
+
+    // Group came later: by default it visits every child in turn,
+    // override it to skip a group or to wrap its children in something
+    fn visit_group(&mut self, children: &[Shape]) {
+        for child in children {
+            child.accept(self.as_visitor());
+        }
+    }
+
+    fn visit_unknown(&mut self, _shape: &Shape) {}
+}
+
+// a default method only knows Self, which might not be Sized, so it can't turn
+// itself into a &mut dyn ShapeVisitor. this blanket impl does it for every
+// visitor, so nobody has to write it
+pub trait AsVisitor {
+    fn as_visitor(&mut self) -> &mut dyn ShapeVisitor;
+}
+
+impl<T: ShapeVisitor> AsVisitor for T {
+    fn as_visitor(&mut self) -> &mut dyn ShapeVisitor {
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShapeError {
+    // zero, negative or NaN
+    BadScale(f64),
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShapeError::BadScale(factor) => {
+                write!(f, "scale factor must be a positive number, got {}", factor)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AreaError {
+    NegativeDimension { which: &'static str, value: f64 },
+    // a NaN or infinite dimension, or an area too big for f64
+    NonFinite,
+}
+
+impl fmt::Display for AreaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AreaError::NegativeDimension { which, value } => {
+                write!(f, "{} can't be negative, got {}", which, value)
+            }
+            AreaError::NonFinite => write!(f, "the area is not a finite number"),
+        }
+    }
+}
+
+impl std::error::Error for AreaError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseShapeError {
+    Empty,
+    UnknownShape(String),
+    WrongArgCount { shape: &'static str, expected: usize, got: usize },
+    BadNumber(String),
+    Scale(ShapeError),
+}
+
+impl fmt::Display for ParseShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseShapeError::Empty => write!(f, "no shape given"),
+            ParseShapeError::UnknownShape(name) => write!(f, "unknown shape `{}`", name),
+            ParseShapeError::WrongArgCount { shape, expected, got } => {
+                write!(f, "{} takes {} number(s), got {}", shape, expected, got)
+            }
+            ParseShapeError::BadNumber(text) => write!(f, "`{}` is not a number", text),
+            ParseShapeError::Scale(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ParseShapeError {}
+
+impl From<ShapeError> for ParseShapeError {
+    fn from(err: ShapeError) -> Self {
+        ParseShapeError::Scale(err)
+    }
+}
+
+impl Shape {
+    pub fn scaled(factor: f64, inner: Shape) -> Result<Shape, ShapeError> {
+        // is_finite rules out NaN and infinity
+        if !factor.is_finite() || factor <= 0.0 {
+            return Err(ShapeError::BadScale(factor));
+        }
+        Ok(Shape::Scaled(factor, Box::new(inner)))
+    }
+
+    pub fn kind(&self) -> ShapeKind {
+        match self {
+            Shape::Rect(..) => ShapeKind::Rect,
+            Shape::Circle(_) => ShapeKind::Circle,
+            Shape::Scaled(..) => ShapeKind::Scaled,
+            Shape::Group(_) => ShapeKind::Group,
+        }
+    }
+
+    pub fn accept(&self, v: &mut dyn ShapeVisitor) {
+        match self {
+            Shape::Rect(a, b) => v.visit_rect(*a, *b),
+            Shape::Circle(r) => v.visit_circle(*r),
+            Shape::Scaled(factor, inner) => v.visit_scaled(*factor, inner),
+            Shape::Group(children) => v.visit_group(children),
+        }
+    }
+
+    // the name the visitor first had, same as accept
+    pub fn visit(&self, v: &mut dyn ShapeVisitor) {
+        self.accept(v)
+    }
+}
+
+// peels off every Scaled layer with a loop, so deep nesting can't blow the stack
+fn unwrap_scales(shape: &Shape) -> (f64, &Shape) {
+    let mut factor = 1.0;
+    let mut current = shape;
+    while let Shape::Scaled(f, inner) = current {
+        factor *= f;
+        current = inner;
+    }
+    (factor, current)
+}
+
+fn check(which: &'static str, value: f64) -> Result<(), AreaError> {
+    if !value.is_finite() {
+        Err(AreaError::NonFinite)
+    } else if value < 0.0 {
+        Err(AreaError::NegativeDimension { which, value })
+    } else {
+        Ok(())
+    }
+}
+
+pub fn calc_area(shape: &Shape) -> Result<f64, AreaError> {
+    let mut current = shape;
+    while let Shape::Scaled(factor, inner) = current {
+        check("scale factor", *factor)?;
+        current = inner;
+    }
+    let area = match current {
+        Shape::Rect(a, b) => {
+            check("length", *a)?;
+            check("breadth", *b)?;
+            calc_area_unchecked(shape)
+        }
+        Shape::Circle(r) => {
+            check("radius", *r)?;
+            calc_area_unchecked(shape)
+        }
+        // each child is checked and summed once, not checked here and summed again
+        Shape::Group(children) => {
+            let mut total = 0.0;
+            for child in children {
+                total += calc_area(child)?;
+            }
+            let (factor, _) = unwrap_scales(shape);
+            total * factor * factor
+        }
+        Shape::Scaled(..) => unreachable!("the loop removed every Scaled"),
+    };
+    if area.is_finite() { Ok(area) } else { Err(AreaError::NonFinite) }
+}
+
+// the same checks as calc_area, but every bad number is reported, not just the first
+pub fn validate_shape(shape: &Shape) -> Result<(), Vec<ValidationIssue>> {
+    let mut validator = Validator::new(shape);
+    let mut current = shape;
+    while let Shape::Scaled(factor, inner) = current {
+        validator = validator.check(check("scale factor", *factor));
+        current = inner;
+    }
+    validator = match current {
+        Shape::Rect(a, b) => validator.check(check("length", *a)).check(check("breadth", *b)),
+        Shape::Circle(r) => validator.check(check("radius", *r)),
+        Shape::Group(children) => {
+            children.iter().fold(validator, |v, child| v.nested(validate_shape(child)))
+        }
+        Shape::Scaled(..) => unreachable!("the loop removed every Scaled"),
+    };
+    validator.finish()
+}
+
+// no checks, a negative radius still gives a positive area
+pub fn calc_area_unchecked(shape: &Shape) -> f64 {
+    let (factor, base) = unwrap_scales(shape);
+    let area = match base {
+        Shape::Rect(a, b) => a * b,
+        Shape::Circle(r) => PI * r * r,
+        Shape::Group(children) => children.iter().map(calc_area_unchecked).fold(0.0, |t, a| t + a),
+        Shape::Scaled(..) => unreachable!("unwrap_scales removed every Scaled"),
+    };
+    area * factor * factor
+}
+
+pub fn perimeter(shape: &Shape) -> f64 {
+    let (factor, base) = unwrap_scales(shape);
+    let perimeter = match base {
+        Shape::Rect(a, b) => 2.0 * (a + b),
+        Shape::Circle(r) => 2.0 * PI * r,
+        // the outlines added up, like the area
+        Shape::Group(children) => children.iter().map(perimeter).fold(0.0, |t, p| t + p),
+        Shape::Scaled(..) => unreachable!("unwrap_scales removed every Scaled"),
+    };
+    perimeter * factor
+}
+
+// folds all the Scaled layers into the dimensions of the shape inside
+pub fn normalize(shape: Shape) -> Shape {
+    let mut factor = 1.0;
+    let mut current = shape;
+    while let Shape::Scaled(f, inner) = current {
+        factor *= f;
+        current = *inner;
+    }
+    match current {
+        Shape::Rect(a, b) => Shape::Rect(a * factor, b * factor),
+        Shape::Circle(r) => Shape::Circle(r * factor),
+        // the scale is pushed down into every child
+        Shape::Group(children) => Shape::Group(
+            children
+                .into_iter()
+                .map(|child| normalize(Shape::Scaled(factor, Box::new(child))))
+                .collect(),
+        ),
+        Shape::Scaled(..) => unreachable!("the loop removed every Scaled"),
+    }
+}
+
+fn parse_number(text: &str) -> Result<f64, ParseShapeError> {
+    text.parse().map_err(|_| ParseShapeError::BadNumber(text.to_string()))
+}
+
+// "rect 2 4", "circle 1.5", and any of those after "scaled <factor>",
+// which can repeat: "scaled 2 scaled 3 circle 1"
+pub fn parse_shape(text: &str) -> Result<Shape, ParseShapeError> {
+    let mut words: Vec<&str> = text.split_whitespace().collect();
+    let mut factors = Vec::new();
+    while words.first() == Some(&"scaled") {
+        let factor = words.get(1).ok_or(ParseShapeError::WrongArgCount {
+            shape: "scaled",
+            expected: 1,
+            got: 0,
+        })?;
+        factors.push(parse_number(factor)?);
+        words.drain(..2);
+    }
+
+    let (name, numbers) = words.split_first().ok_or(ParseShapeError::Empty)?;
+    let numbers = numbers.iter().map(|n| parse_number(n)).collect::<Result<Vec<f64>, _>>()?;
+    let mut shape = match (*name, numbers.as_slice()) {
+        ("rect", [a, b]) => Shape::Rect(*a, *b),
+        ("circle", [r]) => Shape::Circle(*r),
+        ("rect", _) => {
+            let got = numbers.len();
+            return Err(ParseShapeError::WrongArgCount { shape: "rect", expected: 2, got });
+        }
+        ("circle", _) => {
+            let got = numbers.len();
+            return Err(ParseShapeError::WrongArgCount { shape: "circle", expected: 1, got });
+        }
+        (other, _) => return Err(ParseShapeError::UnknownShape(other.to_string())),
+    };
+    // the innermost "scaled" is the last one written
+    for factor in factors.into_iter().rev() {
+        shape = Shape::scaled(factor, shape)?;
+    }
+    Ok(shape)
+}
+
+// a missing shape and a shape with a bad area both fall back to `default`
+pub fn area_or_default(maybe: Option<&Shape>, default: f64) -> f64 {
+    match maybe.map(calc_area) {
+        Some(Ok(area)) => area,
+        Some(Err(_)) | None => default,
+    }
+}
+
+// only a bare Circle counts, not one inside Scaled
+pub fn first_circle(shapes: &[Shape]) -> Option<&Shape> {
+    shapes.iter().find(|shape| matches!(shape, Shape::Circle(_)))
+}
+
+// the (length, breadth) of the bare Rect with the biggest area
+// max_by keeps the last of equal elements, so the last one wins a tie
+pub fn largest_rect_dims(shapes: &[Shape]) -> Option<(f64, f64)> {
+    shapes
+        .iter()
+        .filter_map(|shape| match shape {
+            Shape::Rect(a, b) => Some((*a, *b)),
+            _ => None,
+        })
+        .max_by(|(a1, b1), (a2, b2)| (a1 * b1).total_cmp(&(a2 * b2)))
+}
+
+// None unless both shapes are there and both have a valid area
+pub fn pair_areas(a: Option<Shape>, b: Option<Shape>) -> Option<(f64, f64)> {
+    a.zip(b).and_then(|(a, b)| {
+        let first = calc_area(&a).ok()?;
+        let second = calc_area(&b).ok()?;
+        Some((first, second))
+    })
+}
+
+// shapes ordered by area. this is a wrapper and not PartialOrd on Shape itself:
+// PartialOrd has to agree with ==, and Shape's == compares the variant and
+// its numbers, so Rect(2, 3) and Rect(3, 2) are different shapes. by area they
+// are equal, and a < b, a == b and a > b would all be false for them.
+//
+// it is a real partial order, not just floats being awkward: an invalid shape
+// has no area at all, so it can't be compared with anything, not even itself.
+// that is why there is no Ord here and `sort` can't be used on it
+#[derive(Debug, Clone, Copy)]
+pub struct ByArea<'a>(pub &'a Shape);
+
+impl PartialEq for ByArea<'_> {
+    fn eq(&self, other: &ByArea) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+// None when either side fails calc_area, a bad dimension or a NaN
+impl PartialOrd for ByArea<'_> {
+    fn partial_cmp(&self, other: &ByArea) -> Option<Ordering> {
+        let a = calc_area(self.0).ok()?;
+        let b = calc_area(other.0).ok()?;
+        a.partial_cmp(&b)
+    }
+}
+
+// smallest area first, every shape without an area is moved to the end in
+// the order it was in. returns how many were moved there
+pub fn sort_shapes_lenient(shapes: &mut Vec<Shape>) -> usize {
+    // each area is worked out once, not once per comparison
+    let mut keyed: Vec<(Option<f64>, Shape)> =
+        shapes.drain(..).map(|shape| (calc_area(&shape).ok(), shape)).collect();
+    // a stable sort, so equal areas keep their order too
+    keyed.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) => a.total_cmp(b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+    let incomparable = keyed.iter().filter(|(area, _)| area.is_none()).count();
+    shapes.extend(keyed.into_iter().map(|(_, shape)| shape));
+    incomparable
+}
+
+// every field comes from one pass, nothing is collected
+// min, max and mean are None when there were no shapes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AreaStats {
+    pub count: usize,
+    pub total: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+}
+
+// uses calc_area_unchecked, check the shapes first if they might be invalid
+pub fn area_stats(shapes: impl Iterator<Item = Shape>) -> AreaStats {
+    let mut stats = AreaStats { count: 0, total: 0.0, min: None, max: None, mean: None };
+    let mut range = MinMax::new();
+    for shape in shapes {
+        let area = calc_area_unchecked(&shape);
+        stats.count += 1;
+        stats.total += area;
+        range.observe(area);
+    }
+    if let Some((&min, &max)) = range.get() {
+        stats.min = Some(min);
+        stats.max = Some(max);
+    }
+    if stats.count > 0 {
+        stats.mean = Some(stats.total / stats.count as f64);
+    }
+    stats
+}
+
+// yields the mean area of the shapes seen so far, one value per shape
+pub struct RunningMean<I> {
+    inner: I,
+    count: usize,
+    total: f64,
+}
+
+impl<I: Iterator<Item = Shape>> Iterator for RunningMean<I> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        let shape = self.inner.next()?;
+        self.count += 1;
+        self.total += calc_area_unchecked(&shape);
+        Some(self.total / self.count as f64)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+pub fn running_mean<I: Iterator<Item = Shape>>(shapes: I) -> RunningMean<I> {
+    RunningMean { inner: shapes, count: 0, total: 0.0 }
+}
+
+// shapes per pool job, one job per shape would mostly measure the channel
+const AREA_BATCH: usize = 64;
+
+// every shape comes back exactly once with its index, invalid ones as an Err,
+// sorted by index no matter which job finished first
+pub fn compute_areas_parallel(
+    shapes: Vec<Shape>,
+    pool: &ThreadPool,
+) -> Vec<(usize, Result<f64, AreaError>)> {
+    let total = shapes.len();
+    let (sender, receiver) = channel();
+    for (n, batch) in shapes.into_iter().batched(AREA_BATCH).enumerate() {
+        let sender = sender.clone();
+        pool.execute(move || {
+            let start = n * AREA_BATCH;
+            let areas: Vec<_> =
+                batch.iter().enumerate().map(|(i, shape)| (start + i, calc_area(shape))).collect();
+            let _ = sender.send(areas);
+        });
+    }
+    // the jobs own the remaining senders, so the loop below ends with the last job
+    drop(sender);
+
+    let mut results = Vec::with_capacity(total);
+    for areas in receiver {
+        results.extend(areas);
+    }
+    results.sort_by_key(|(index, _)| *index);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.0)
+    }
+
+    #[test]
+    fn scale_of_2_then_3_is_a_scale_of_6() {
+        let rect = Shape::Rect(2.0, 4.0);
+        let nested = Shape::scaled(3.0, Shape::scaled(2.0, rect.clone()).unwrap()).unwrap();
+        let single = Shape::scaled(6.0, rect).unwrap();
+        assert!(close(calc_area(&nested).unwrap(), calc_area(&single).unwrap()));
+        assert!(close(calc_area(&nested).unwrap(), 8.0 * 36.0));
+        // the perimeter grows by the factor, not its square
+        assert!(close(perimeter(&nested), perimeter(&single)));
+        assert!(close(perimeter(&nested), 12.0 * 6.0));
+        assert_eq!(normalize(nested), normalize(single));
+    }
+
+    #[test]
+    fn normalize_removes_every_wrapper_and_keeps_the_area() {
+        let shapes = [
+            Shape::scaled(2.0, Shape::scaled(0.5, Shape::Circle(3.0)).unwrap()).unwrap(),
+            Shape::scaled(1.5, Shape::Rect(2.0, 3.0)).unwrap(),
+            Shape::scaled(
+                2.0,
+                Shape::Group(vec![
+                    Shape::Rect(1.0, 1.0),
+                    Shape::scaled(3.0, Shape::Circle(1.0)).unwrap(),
+                ]),
+            )
+            .unwrap(),
+        ];
+        for shape in shapes {
+            let area = calc_area(&shape).unwrap();
+            let flat = normalize(shape);
+            assert!(!has_scaled(&flat), "{:?} still has a Scaled", flat);
+            assert!(close(calc_area(&flat).unwrap(), area));
+        }
+        assert_eq!(normalize(Shape::Circle(2.0)), Shape::Circle(2.0));
+    }
+
+    fn has_scaled(shape: &Shape) -> bool {
+        match shape {
+            Shape::Scaled(..) => true,
+            Shape::Group(children) => children.iter().any(has_scaled),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn five_hundred_levels_of_nesting_normalize() {
+        let mut shape = Shape::Rect(1.0, 2.0);
+        for _ in 0..500 {
+            shape = Shape::scaled(1.01, shape).unwrap();
+        }
+        let factor = 1.01f64.powi(500);
+        assert!(close(calc_area(&shape).unwrap(), 2.0 * factor * factor));
+        assert!(close(perimeter(&shape), 6.0 * factor));
+        match normalize(shape) {
+            Shape::Rect(a, b) => {
+                assert!(close(a, factor));
+                assert!(close(b, 2.0 * factor));
+            }
+            other => panic!("expected a Rect, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn scaled_rejects_zero_negative_and_non_finite_factors() {
+        for factor in [0.0, -0.0, -1.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let err = Shape::scaled(factor, Shape::Circle(1.0)).unwrap_err();
+            assert!(matches!(err, ShapeError::BadScale(f) if f.to_bits() == factor.to_bits()));
+        }
+        assert!(Shape::scaled(f64::MIN_POSITIVE, Shape::Circle(1.0)).is_ok());
+        assert_eq!(
+            ShapeError::BadScale(-1.0).to_string(),
+            "scale factor must be a positive number, got -1"
+        );
+    }
+
+    // the same ? flow as enum_pt_match's total_area
+    fn total_area(shapes: &[Shape]) -> Result<f64, AreaError> {
+        let mut total = 0.0;
+        for shape in shapes {
+            total += calc_area(shape)?;
+        }
+        Ok(total)
+    }
+
+    #[test]
+    fn each_negative_dimension_is_named() {
+        let cases = [
+            (Shape::Rect(-2.0, 3.0), "length", -2.0),
+            (Shape::Rect(2.0, -3.0), "breadth", -3.0),
+            (Shape::Circle(-1.5), "radius", -1.5),
+            (Shape::Scaled(-2.0, Box::new(Shape::Circle(1.0))), "scale factor", -2.0),
+        ];
+        for (shape, which, value) in cases {
+            assert_eq!(calc_area(&shape), Err(AreaError::NegativeDimension { which, value }));
+        }
+        // the length is checked first
+        assert_eq!(
+            calc_area(&Shape::Rect(-1.0, -2.0)),
+            Err(AreaError::NegativeDimension { which: "length", value: -1.0 })
+        );
+    }
+
+    #[test]
+    fn nan_infinite_and_overflowing_areas_are_non_finite() {
+        assert_eq!(calc_area(&Shape::Circle(f64::NAN)), Err(AreaError::NonFinite));
+        assert_eq!(calc_area(&Shape::Rect(f64::INFINITY, 1.0)), Err(AreaError::NonFinite));
+        // both sides are fine, their product isn't
+        assert_eq!(calc_area(&Shape::Rect(1e200, 1e200)), Err(AreaError::NonFinite));
+        assert_eq!(calc_area(&Shape::Rect(0.0, 5.0)), Ok(0.0));
+    }
+
+    #[test]
+    fn question_mark_stops_at_the_first_bad_shape() {
+        let good = [Shape::Rect(2.0, 4.0), Shape::Circle(1.0)];
+        assert!(close(total_area(&good).unwrap(), 8.0 + PI));
+        assert_eq!(total_area(&[]), Ok(0.0));
+        let bad = [Shape::Rect(2.0, 4.0), Shape::Circle(-2.0), Shape::Rect(-1.0, 1.0)];
+        assert_eq!(
+            total_area(&bad),
+            Err(AreaError::NegativeDimension { which: "radius", value: -2.0 })
+        );
+    }
+
+    #[test]
+    fn area_error_display_names_the_dimension() {
+        let err = calc_area(&Shape::Rect(1.0, -3.5)).unwrap_err();
+        assert_eq!(err.to_string(), "breadth can't be negative, got -3.5");
+        let err = calc_area(&Shape::Circle(-2.0)).unwrap_err();
+        assert_eq!(err.to_string(), "radius can't be negative, got -2");
+        assert_eq!(AreaError::NonFinite.to_string(), "the area is not a finite number");
+    }
+
+    #[test]
+    fn unchecked_keeps_the_old_behaviour() {
+        assert!(close(calc_area_unchecked(&Shape::Circle(-2.0)), 4.0 * PI));
+        assert_eq!(calc_area_unchecked(&Shape::Rect(-2.0, 3.0)), -6.0);
+    }
+
+    #[test]
+    fn area_or_default_falls_back_for_none_and_bad_shapes() {
+        let rect = Shape::Rect(2.0, 3.0);
+        assert_eq!(area_or_default(Some(&rect), 0.0), 6.0);
+        assert_eq!(area_or_default(None, 1.5), 1.5);
+        assert_eq!(area_or_default(Some(&Shape::Circle(-1.0)), -1.0), -1.0);
+        let shapes: [Shape; 0] = [];
+        assert_eq!(area_or_default(shapes.first(), 7.0), 7.0);
+    }
+
+    #[test]
+    fn first_circle_skips_wrapped_circles() {
+        let wrapped = Shape::scaled(2.0, Shape::Circle(1.0)).unwrap();
+        let shapes =
+            [Shape::Rect(1.0, 1.0), wrapped.clone(), Shape::Circle(3.0), Shape::Circle(4.0)];
+        assert_eq!(first_circle(&shapes), Some(&Shape::Circle(3.0)));
+        assert_eq!(first_circle(&[]), None);
+        assert_eq!(first_circle(&[wrapped, Shape::Rect(1.0, 2.0)]), None);
+    }
+
+    #[test]
+    fn largest_rect_dims_prefers_the_last_of_a_tie() {
+        let shapes = [
+            Shape::Rect(1.0, 6.0),
+            Shape::Circle(10.0),
+            Shape::Rect(2.0, 3.0),
+            Shape::Rect(1.0, 1.0),
+        ];
+        assert_eq!(largest_rect_dims(&shapes), Some((2.0, 3.0)));
+        let shapes = [Shape::Rect(1.0, 1.0), Shape::Rect(4.0, 2.0), Shape::Rect(3.0, 1.0)];
+        assert_eq!(largest_rect_dims(&shapes), Some((4.0, 2.0)));
+        assert_eq!(largest_rect_dims(&[]), None);
+        assert_eq!(largest_rect_dims(&[Shape::Circle(1.0)]), None);
+    }
+
+    #[test]
+    fn pair_areas_needs_both_shapes_and_both_areas() {
+        let rect = || Some(Shape::Rect(1.0, 2.0));
+        assert_eq!(pair_areas(rect(), Some(Shape::Rect(3.0, 3.0))), Some((2.0, 9.0)));
+        assert_eq!(pair_areas(rect(), None), None);
+        assert_eq!(pair_areas(None, rect()), None);
+        assert_eq!(pair_areas(None, None), None);
+        assert_eq!(pair_areas(rect(), Some(Shape::Circle(-1.0))), None);
+        assert_eq!(pair_areas(Some(Shape::Circle(f64::NAN)), rect()), None);
+    }
+
+    // writes down every call, so the order of dispatch can be checked
+    #[derive(Default)]
+    struct Recorder {
+        calls: Vec<String>,
+    }
+
+    impl ShapeVisitor for Recorder {
+        fn visit_rect(&mut self, length: f64, breadth: f64) {
+            self.calls.push(format!("rect {} {}", length, breadth));
+        }
+
+        fn visit_circle(&mut self, radius: f64) {
+            self.calls.push(format!("circle {}", radius));
+        }
+
+        fn visit_scaled(&mut self, factor: f64, inner: &Shape) {
+            self.calls.push(format!("scaled {}", factor));
+            inner.accept(self);
+        }
+    }
+
+    fn record(shape: &Shape) -> Vec<String> {
+        let mut recorder = Recorder::default();
+        shape.accept(&mut recorder);
+        recorder.calls
+    }
+
+    #[test]
+    fn accept_calls_the_method_for_each_variant() {
+        assert_eq!(record(&Shape::Rect(2.0, 3.0)), ["rect 2 3"]);
+        assert_eq!(record(&Shape::Circle(1.5)), ["circle 1.5"]);
+        let scaled = Shape::scaled(2.0, Shape::Circle(1.0)).unwrap();
+        assert_eq!(record(&scaled), ["scaled 2", "circle 1"]);
+        // Recorder has no visit_group, the default one walks the children in order
+        let group = Shape::Group(vec![Shape::Rect(1.0, 1.0), scaled, Shape::Group(vec![])]);
+        assert_eq!(record(&group), ["rect 1 1", "scaled 2", "circle 1"]);
+    }
+
+    #[test]
+    fn visit_is_the_same_as_accept() {
+        let group = Shape::Group(vec![Shape::Circle(1.0), Shape::Rect(2.0, 2.0)]);
+        let mut recorder = Recorder::default();
+        group.visit(&mut recorder);
+        assert_eq!(recorder.calls, record(&group));
+    }
+
+    #[test]
+    fn kind_maps_each_variant() {
+        assert_eq!(Shape::Rect(1.0, 2.0).kind(), ShapeKind::Rect);
+        assert_eq!(Shape::Circle(1.0).kind(), ShapeKind::Circle);
+        // only the outer layer counts
+        let scaled = Shape::scaled(2.0, Shape::Rect(1.0, 1.0)).unwrap();
+        assert_eq!(scaled.kind(), ShapeKind::Scaled);
+        assert_eq!(Shape::Group(vec![scaled]).kind(), ShapeKind::Group);
+        // kind doesn't look at the numbers, an invalid shape still has one
+        assert_eq!(Shape::Circle(-1.0).kind(), ShapeKind::Circle);
+    }
+
+    fn squares(sides: &[f64]) -> impl Iterator<Item = Shape> + '_ {
+        sides.iter().map(|&side| Shape::Rect(side, side))
+    }
+
+    #[test]
+    fn area_stats_match_hand_computed_values() {
+        // areas 1, 16, 4, 9
+        let stats = area_stats(squares(&[1.0, 4.0, 2.0, 3.0]));
+        assert_eq!(
+            stats,
+            AreaStats { count: 4, total: 30.0, min: Some(1.0), max: Some(16.0), mean: Some(7.5) }
+        );
+    }
+
+    #[test]
+    fn area_stats_of_nothing_has_no_min_max_or_mean() {
+        let stats = area_stats(std::iter::empty());
+        assert_eq!(stats, AreaStats { count: 0, total: 0.0, min: None, max: None, mean: None });
+    }
+
+    #[test]
+    fn area_stats_of_one_shape() {
+        let stats = area_stats(std::iter::once(Shape::Rect(2.0, 5.0)));
+        assert_eq!(
+            stats,
+            AreaStats { count: 1, total: 10.0, min: Some(10.0), max: Some(10.0), mean: Some(10.0) }
+        );
+    }
+
+    #[test]
+    fn running_mean_yields_each_prefix_mean() {
+        let means: Vec<f64> = running_mean(squares(&[1.0, 2.0, 3.0, 4.0])).collect();
+        let expected = [1.0, 2.5, 14.0 / 3.0, 7.5];
+        assert_eq!(means.len(), expected.len());
+        for (got, want) in means.iter().zip(expected) {
+            assert!(close(*got, want), "{} != {}", got, want);
+        }
+        assert_eq!(running_mean(std::iter::empty()).next(), None);
+        assert_eq!(running_mean(squares(&[1.0, 2.0])).size_hint(), (2, Some(2)));
+    }
+
+    #[test]
+    fn parallel_areas_match_the_sequential_ones() {
+        let shapes = crate::sample_data::sample_shapes(177, 5000);
+        let expected: Vec<Result<f64, AreaError>> = shapes.iter().map(calc_area).collect();
+        // about one in twenty has a negative dimension
+        let invalid = expected.iter().filter(|area| area.is_err()).count();
+        assert!(invalid > 100, "only {} invalid shapes", invalid);
+        for workers in [1, 4] {
+            let pool = ThreadPool::new(workers).unwrap();
+            let results = compute_areas_parallel(shapes.clone(), &pool);
+            // every index exactly once, in order
+            let indexes: Vec<usize> = results.iter().map(|(i, _)| *i).collect();
+            assert_eq!(indexes, (0..5000).collect::<Vec<_>>());
+            let areas: Vec<Result<f64, AreaError>> =
+                results.into_iter().map(|(_, area)| area).collect();
+            assert_eq!(areas, expected, "{} workers", workers);
+        }
+    }
+
+    #[test]
+    fn parallel_areas_of_nothing_and_of_one_bad_shape() {
+        let pool = ThreadPool::new(2).unwrap();
+        assert!(compute_areas_parallel(Vec::new(), &pool).is_empty());
+        let results = compute_areas_parallel(vec![Shape::Circle(-1.0)], &pool);
+        let err = AreaError::NegativeDimension { which: "radius", value: -1.0 };
+        assert_eq!(results, [(0, Err(err))]);
+    }
+
+    #[test]
+    fn validate_shape_reports_every_bad_number() {
+        assert_eq!(validate_shape(&Shape::Rect(2.0, 3.0)), Ok(()));
+        let shape = Shape::Scaled(
+            -2.0,
+            Box::new(Shape::Group(vec![
+                Shape::Rect(-1.0, 2.0),
+                Shape::Circle(f64::NAN),
+                Shape::Rect(1.0, -3.0),
+            ])),
+        );
+        let got: Vec<String> =
+            validate_shape(&shape).unwrap_err().into_iter().map(|i| i.message).collect();
+        assert_eq!(
+            got,
+            [
+                "scale factor can't be negative, got -2",
+                "length can't be negative, got -1",
+                "the area is not a finite number",
+                "breadth can't be negative, got -3",
+            ]
+        );
+    }
+
+    #[test]
+    fn by_area_orders_ordinary_shapes() {
+        let (small, big) = (Shape::Rect(1.0, 2.0), Shape::Circle(2.0));
+        assert_eq!(ByArea(&small).partial_cmp(&ByArea(&big)), Some(Ordering::Less));
+        assert_eq!(ByArea(&big).partial_cmp(&ByArea(&small)), Some(Ordering::Greater));
+        assert!(ByArea(&small) < ByArea(&big));
+        let scaled = Shape::Scaled(3.0, Box::new(Shape::Rect(1.0, 1.0)));
+        assert!(ByArea(&scaled) > ByArea(&small));
+    }
+
+    #[test]
+    fn different_shapes_with_the_same_area_are_equal_by_area() {
+        let (wide, tall) = (Shape::Rect(2.0, 3.0), Shape::Rect(3.0, 2.0));
+        assert_ne!(wide, tall);
+        assert!(ByArea(&wide) == ByArea(&tall));
+        let group = Shape::Group(vec![Shape::Rect(1.0, 4.0), Shape::Rect(1.0, 2.0)]);
+        let scaled = Shape::Scaled(2.0, Box::new(Shape::Rect(0.5, 3.0)));
+        assert_eq!(ByArea(&wide).partial_cmp(&ByArea(&group)), Some(Ordering::Equal));
+        assert_eq!(ByArea(&group).partial_cmp(&ByArea(&scaled)), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn a_shape_without_an_area_compares_with_nothing() {
+        let good = Shape::Rect(1.0, 1.0);
+        for bad in [Shape::Circle(f64::NAN), Shape::Rect(-1.0, 2.0), Shape::Circle(f64::MAX)] {
+            assert_eq!(ByArea(&bad).partial_cmp(&ByArea(&good)), None, "{:?}", bad);
+            assert_eq!(ByArea(&good).partial_cmp(&ByArea(&bad)), None, "{:?}", bad);
+            // not even equal to itself
+            assert!(ByArea(&bad) != ByArea(&bad));
+            let order = [ByArea(&bad) < ByArea(&good), ByArea(&bad) >= ByArea(&good)];
+            assert_eq!(order, [false, false]);
+        }
+    }
+
+    #[test]
+    fn sort_shapes_lenient_moves_the_bad_ones_to_the_end() {
+        let mut shapes = vec![
+            Shape::Circle(f64::NAN),
+            Shape::Rect(3.0, 3.0),
+            Shape::Rect(-1.0, 1.0),
+            Shape::Rect(1.0, 2.0),
+            Shape::Rect(2.0, 1.0),
+            Shape::Circle(0.5),
+        ];
+        assert_eq!(sort_shapes_lenient(&mut shapes), 2);
+        assert_eq!(shapes.len(), 6);
+        // equal areas keep their order, and so do the bad shapes
+        let sorted = [
+            Shape::Circle(0.5),
+            Shape::Rect(1.0, 2.0),
+            Shape::Rect(2.0, 1.0),
+            Shape::Rect(3.0, 3.0),
+        ];
+        assert_eq!(shapes[..4], sorted);
+        assert!(matches!(shapes[4], Shape::Circle(r) if r.is_nan()));
+        assert_eq!(shapes[5], Shape::Rect(-1.0, 1.0));
+
+        let mut none: Vec<Shape> = Vec::new();
+        assert_eq!(sort_shapes_lenient(&mut none), 0);
+        let mut all_bad = vec![Shape::Rect(-1.0, 1.0), Shape::Circle(-2.0)];
+        assert_eq!(sort_shapes_lenient(&mut all_bad), 2);
+        assert_eq!(all_bad, [Shape::Rect(-1.0, 1.0), Shape::Circle(-2.0)]);
+    }
+}
@@ -0,0 +1,256 @@
+// two ShapeVisitors: one adds up areas, the other draws the shapes as SVG
+
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+use crate::shape::{Shape, ShapeVisitor};
+
+// the same total as summing calc_area_unchecked, worked out by walking the shapes.
+// the scale of every Scaled around the current shape is kept as one factor
+pub struct AreaAccumulator {
+    pub total: f64,
+    scale: f64,
+}
+
+impl AreaAccumulator {
+    pub fn new() -> AreaAccumulator {
+        AreaAccumulator { total: 0.0, scale: 1.0 }
+    }
+}
+
+impl Default for AreaAccumulator {
+    fn default() -> AreaAccumulator {
+        AreaAccumulator::new()
+    }
+}
+
+impl ShapeVisitor for AreaAccumulator {
+    fn visit_rect(&mut self, length: f64, breadth: f64) {
+        self.total += length * breadth * self.scale * self.scale;
+    }
+
+    fn visit_circle(&mut self, radius: f64) {
+        self.total += PI * radius * radius * self.scale * self.scale;
+    }
+
+    // the old scale is put back afterwards rather than divided out again
+    fn visit_scaled(&mut self, factor: f64, inner: &Shape) {
+        let outer = self.scale;
+        self.scale *= factor;
+        inner.accept(self);
+        self.scale = outer;
+    }
+}
+
+pub fn total_area_visited(shapes: &[Shape]) -> f64 {
+    let mut acc = AreaAccumulator::new();
+    for shape in shapes {
+        shape.accept(&mut acc);
+    }
+    acc.total
+}
+
+// every shape is drawn from the top left corner, a Scaled becomes a
+// <g transform="scale(..)"> and a Group a plain <g> around its children
+pub struct SvgEmitter {
+    out: String,
+    depth: usize,
+    // the <rect> and <circle> elements written so far, the <g>s don't count
+    pub elements: usize,
+}
+
+impl SvgEmitter {
+    pub fn new() -> SvgEmitter {
+        SvgEmitter { out: String::new(), depth: 1, elements: 0 }
+    }
+
+    fn line(&mut self, text: &str) {
+        // writing to a String can't fail
+        let _ = writeln!(self.out, "{:indent$}{}", "", text, indent = self.depth * 2);
+    }
+
+    // the whole document, width and height are for the viewer only
+    pub fn finish(self, width: u32, height: u32) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n{}</svg>\n",
+            width, height, self.out
+        )
+    }
+}
+
+impl Default for SvgEmitter {
+    fn default() -> SvgEmitter {
+        SvgEmitter::new()
+    }
+}
+
+impl ShapeVisitor for SvgEmitter {
+    fn visit_rect(&mut self, length: f64, breadth: f64) {
+        self.line(&format!("<rect x=\"0\" y=\"0\" width=\"{}\" height=\"{}\" />", length, breadth));
+        self.elements += 1;
+    }
+
+    fn visit_circle(&mut self, radius: f64) {
+        self.line(&format!("<circle cx=\"{0}\" cy=\"{0}\" r=\"{0}\" />", radius));
+        self.elements += 1;
+    }
+
+    fn visit_scaled(&mut self, factor: f64, inner: &Shape) {
+        self.line(&format!("<g transform=\"scale({})\">", factor));
+        self.depth += 1;
+        inner.accept(self);
+        self.depth -= 1;
+        self.line("</g>");
+    }
+
+    // overrides the default walk so the children end up inside one <g>
+    fn visit_group(&mut self, children: &[Shape]) {
+        self.line("<g>");
+        self.depth += 1;
+        for child in children {
+            child.accept(self);
+        }
+        self.depth -= 1;
+        self.line("</g>");
+    }
+}
+
+pub fn to_svg(shapes: &[Shape], width: u32, height: u32) -> String {
+    let mut emitter = SvgEmitter::new();
+    for shape in shapes {
+        shape.accept(&mut emitter);
+    }
+    emitter.finish(width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::calc_area;
+
+    fn nested() -> Vec<Shape> {
+        let inner = Shape::Group(vec![Shape::Rect(1.0, 2.0), Shape::Circle(0.5)]);
+        vec![
+            Shape::Rect(3.0, 4.0),
+            Shape::scaled(2.0, Shape::Group(vec![inner.clone(), Shape::Circle(1.0)])).unwrap(),
+            Shape::Group(vec![Shape::scaled(3.0, inner).unwrap(), Shape::Group(vec![])]),
+        ]
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn accumulator_matches_calc_area_across_nested_groups() {
+        let shapes = nested();
+        let expected = shapes.iter().map(|s| calc_area(s).unwrap()).fold(0.0, |t, a| t + a);
+        let visited = total_area_visited(&shapes);
+        assert!(close(visited, expected), "{} != {}", visited, expected);
+        for shape in &shapes {
+            let mut acc = AreaAccumulator::new();
+            shape.accept(&mut acc);
+            assert!(close(acc.total, calc_area(shape).unwrap()));
+        }
+    }
+
+    #[test]
+    fn accumulator_of_nothing_is_zero() {
+        assert_eq!(total_area_visited(&[]), 0.0);
+        assert_eq!(total_area_visited(&[Shape::Group(vec![])]), 0.0);
+    }
+
+    #[test]
+    fn svg_has_one_element_per_rect_and_circle() {
+        let mut emitter = SvgEmitter::new();
+        for shape in nested() {
+            shape.accept(&mut emitter);
+        }
+        // rect, (rect, circle, circle), (rect, circle)
+        assert_eq!(emitter.elements, 6);
+        let svg = emitter.finish(100, 100);
+        assert_eq!(svg.matches("<rect ").count() + svg.matches("<circle ").count(), 6);
+        assert_eq!(svg.matches("<g").count(), svg.matches("</g>").count());
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\""));
+    }
+
+    #[test]
+    fn svg_nests_groups_and_scales() {
+        let shape = Shape::scaled(2.0, Shape::Group(vec![Shape::Circle(1.0)])).unwrap();
+        assert_eq!(
+            to_svg(&[shape], 10, 20),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"10\" height=\"20\">\n\
+             \x20 <g transform=\"scale(2)\">\n\
+             \x20   <g>\n\
+             \x20     <circle cx=\"1\" cy=\"1\" r=\"1\" />\n\
+             \x20   </g>\n\
+             \x20 </g>\n\
+             </svg>\n"
+        );
+    }
+
+    // only counts circles, and relies on the default visit_group to find them
+    #[derive(Default)]
+    struct CircleCounter {
+        circles: usize,
+    }
+
+    impl ShapeVisitor for CircleCounter {
+        fn visit_rect(&mut self, _length: f64, _breadth: f64) {}
+
+        fn visit_circle(&mut self, _radius: f64) {
+            self.circles += 1;
+        }
+
+        fn visit_scaled(&mut self, _factor: f64, inner: &Shape) {
+            inner.accept(self);
+        }
+    }
+
+    #[test]
+    fn a_custom_visitor_counts_circles_through_the_default_group_walk() {
+        let mut counter = CircleCounter::default();
+        for shape in nested() {
+            shape.accept(&mut counter);
+        }
+        assert_eq!(counter.circles, 3);
+    }
+
+    // overrides visit_group to skip the groups entirely
+    struct TopLevelOnly(CircleCounter);
+
+    impl ShapeVisitor for TopLevelOnly {
+        fn visit_rect(&mut self, _length: f64, _breadth: f64) {}
+
+        fn visit_circle(&mut self, radius: f64) {
+            self.0.visit_circle(radius);
+        }
+
+        fn visit_scaled(&mut self, _factor: f64, inner: &Shape) {
+            inner.accept(self);
+        }
+
+        fn visit_group(&mut self, _children: &[Shape]) {}
+    }
+
+    #[test]
+    fn visit_group_can_be_overridden() {
+        let mut visitor = TopLevelOnly(CircleCounter::default());
+        let shapes = [Shape::Circle(1.0), Shape::Group(vec![Shape::Circle(2.0)])];
+        for shape in &shapes {
+            shape.accept(&mut visitor);
+        }
+        assert_eq!(visitor.0.circles, 1);
+    }
+
+    #[test]
+    fn visit_is_the_same_as_accept() {
+        let mut a = CircleCounter::default();
+        let mut b = CircleCounter::default();
+        for shape in nested() {
+            shape.accept(&mut a);
+            shape.visit(&mut b);
+        }
+        assert_eq!(a.circles, b.circles);
+    }
+}
@@ -0,0 +1,307 @@
+// the classic sorting algorithms, written out for reading rather than speed.
+// every one of them counts its comparisons and swaps through a Counter,
+// the plain functions just throw the counts away
+
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SortStats {
+    pub comparisons: u64,
+    pub swaps: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Bubble,
+    Insertion,
+    Merge,
+    Quick,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 4] =
+        [Algorithm::Bubble, Algorithm::Insertion, Algorithm::Merge, Algorithm::Quick];
+}
+
+#[derive(Default)]
+struct Counter {
+    stats: SortStats,
+}
+
+impl Counter {
+    fn cmp<T: Ord>(&mut self, a: &T, b: &T) -> Ordering {
+        self.stats.comparisons += 1;
+        a.cmp(b)
+    }
+
+    fn swap<T>(&mut self, v: &mut [T], i: usize, j: usize) {
+        self.stats.swaps += 1;
+        v.swap(i, j);
+    }
+}
+
+pub fn is_sorted<T: Ord>(v: &[T]) -> bool {
+    v.windows(2).all(|w| w[0] <= w[1])
+}
+
+// sorts `v` with the chosen algorithm and reports how much work it took
+pub fn sort_stats<T: Ord>(algorithm: Algorithm, v: &mut [T]) -> SortStats {
+    let mut counter = Counter::default();
+    match algorithm {
+        Algorithm::Bubble => bubble(v, &mut counter),
+        Algorithm::Insertion => insertion(v, &mut counter),
+        Algorithm::Merge => merge(v, &mut counter),
+        Algorithm::Quick => quick(v, &mut counter),
+    }
+    counter.stats
+}
+
+pub fn bubble_sort<T: Ord>(v: &mut [T]) {
+    bubble(v, &mut Counter::default());
+}
+
+pub fn insertion_sort<T: Ord>(v: &mut [T]) {
+    insertion(v, &mut Counter::default());
+}
+
+// stable: equal elements keep their order
+pub fn merge_sort<T: Ord>(v: &mut [T]) {
+    merge(v, &mut Counter::default());
+}
+
+// not stable
+pub fn quick_sort<T: Ord>(v: &mut [T]) {
+    quick(v, &mut Counter::default());
+}
+
+// stops early once a pass swaps nothing
+fn bubble<T: Ord>(v: &mut [T], counter: &mut Counter) {
+    for end in (1..v.len()).rev() {
+        let mut swapped = false;
+        for i in 0..end {
+            if counter.cmp(&v[i], &v[i + 1]) == Ordering::Greater {
+                counter.swap(v, i, i + 1);
+                swapped = true;
+            }
+        }
+        if !swapped {
+            break;
+        }
+    }
+}
+
+fn insertion<T: Ord>(v: &mut [T], counter: &mut Counter) {
+    for i in 1..v.len() {
+        let mut j = i;
+        while j > 0 && counter.cmp(&v[j - 1], &v[j]) == Ordering::Greater {
+            counter.swap(v, j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+// T is only Ord, not Clone, so the merging is done on indexes: `order`
+// ends up saying which element belongs at each position, and then the
+// elements are moved there with swaps
+fn merge<T: Ord>(v: &mut [T], counter: &mut Counter) {
+    let n = v.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    let mut buffer = Vec::with_capacity(n);
+    let mut width = 1;
+    while width < n {
+        for start in (0..n).step_by(2 * width) {
+            let mid = (start + width).min(n);
+            let end = (start + 2 * width).min(n);
+            let (mut left, mut right) = (start, mid);
+            buffer.clear();
+            while left < mid && right < end {
+                // taking from the left on a tie is what keeps it stable
+                if counter.cmp(&v[order[right]], &v[order[left]]) == Ordering::Less {
+                    buffer.push(order[right]);
+                    right += 1;
+                } else {
+                    buffer.push(order[left]);
+                    left += 1;
+                }
+            }
+            buffer.extend_from_slice(&order[left..mid]);
+            buffer.extend_from_slice(&order[right..end]);
+            order[start..end].copy_from_slice(&buffer);
+        }
+        width *= 2;
+    }
+
+    // position i needs the element that started at order[i]. follow each
+    // cycle of that permutation, swapping the right element into place
+    let mut placed = vec![false; n];
+    for start in 0..n {
+        let mut i = start;
+        while !placed[i] {
+            placed[i] = true;
+            let from = order[i];
+            if placed[from] {
+                break;
+            }
+            counter.swap(v, i, from);
+            i = from;
+        }
+    }
+}
+
+// three-way partition around the median of the first, middle and last
+// element, so runs of equal values are handled in one go. the smaller side
+// is sorted by recursion and the bigger one by the loop, which keeps the
+// recursion depth at O(log n)
+fn quick<T: Ord>(mut v: &mut [T], counter: &mut Counter) {
+    while v.len() > 1 {
+        let last = v.len() - 1;
+        let mid = v.len() / 2;
+        if counter.cmp(&v[mid], &v[0]) == Ordering::Less {
+            counter.swap(v, mid, 0);
+        }
+        if counter.cmp(&v[last], &v[0]) == Ordering::Less {
+            counter.swap(v, last, 0);
+        }
+        if counter.cmp(&v[last], &v[mid]) == Ordering::Less {
+            counter.swap(v, last, mid);
+        }
+        // the median is now in the middle, move it to the front as the pivot
+        counter.swap(v, 0, mid);
+
+        // v[..lt] < pivot, v[lt..i] == pivot, v[gt..] > pivot, so v[lt] is
+        // always a copy of the pivot to compare against
+        let (mut lt, mut i, mut gt) = (0, 1, v.len());
+        while i < gt {
+            match counter.cmp(&v[i], &v[lt]) {
+                Ordering::Less => {
+                    counter.swap(v, lt, i);
+                    lt += 1;
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    gt -= 1;
+                    counter.swap(v, i, gt);
+                }
+                Ordering::Equal => i += 1,
+            }
+        }
+
+        let (left, rest) = v.split_at_mut(lt);
+        let right = &mut rest[gt - lt..];
+        if left.len() < right.len() {
+            quick(left, counter);
+            v = right;
+        } else {
+            quick(right, counter);
+            v = left;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    type Sorter = fn(&mut [i64]);
+
+    fn sorters() -> [(&'static str, Sorter); 4] {
+        [
+            ("bubble", bubble_sort),
+            ("insertion", insertion_sort),
+            ("merge", merge_sort),
+            ("quick", quick_sort),
+        ]
+    }
+
+    fn check_all(input: &[i64]) {
+        let mut expected = input.to_vec();
+        expected.sort();
+        for (name, sort) in sorters() {
+            let mut v = input.to_vec();
+            sort(&mut v);
+            assert_eq!(v, expected, "{} sort of {:?}", name, input);
+            assert!(is_sorted(&v));
+        }
+    }
+
+    #[test]
+    fn seeded_random_data_matches_std() {
+        let mut rng = Rng::new(157);
+        for len in [1, 2, 3, 10, 57, 300] {
+            let v: Vec<i64> = (0..len).map(|_| rng.range(-50, 50)).collect();
+            check_all(&v);
+        }
+    }
+
+    #[test]
+    fn sorted_reversed_equal_and_empty_inputs() {
+        let sorted: Vec<i64> = (0..200).collect();
+        check_all(&sorted);
+        let reversed: Vec<i64> = (0..200).rev().collect();
+        check_all(&reversed);
+        check_all(&[4; 100]);
+        check_all(&[]);
+        check_all(&[1, 2, 1, 2, 1, 2]);
+    }
+
+    // ordered by key only, the index shows where an element started
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Tagged {
+        key: u8,
+        index: usize,
+    }
+
+    impl PartialOrd for Tagged {
+        fn partial_cmp(&self, other: &Tagged) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Tagged {
+        fn cmp(&self, other: &Tagged) -> Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+
+    #[test]
+    fn merge_sort_is_stable() {
+        let mut rng = Rng::new(9);
+        let mut v: Vec<Tagged> =
+            (0..500).map(|index| Tagged { key: rng.range(0, 10) as u8, index }).collect();
+        merge_sort(&mut v);
+        assert!(is_sorted(&v));
+        for pair in v.windows(2) {
+            if pair[0].key == pair[1].key {
+                assert!(pair[0].index < pair[1].index, "{:?} before {:?}", pair[0], pair[1]);
+            }
+        }
+    }
+
+    #[test]
+    fn sort_stats_counts_the_work() {
+        let mut sorted: Vec<i64> = (0..10).collect();
+        // one pass with nothing to swap, then bubble sort stops
+        let stats = sort_stats(Algorithm::Bubble, &mut sorted);
+        assert_eq!(stats, SortStats { comparisons: 9, swaps: 0 });
+        let mut reversed = [3, 2, 1];
+        let stats = sort_stats(Algorithm::Insertion, &mut reversed);
+        assert_eq!(stats, SortStats { comparisons: 3, swaps: 3 });
+        assert_eq!(reversed, [1, 2, 3]);
+        for algorithm in Algorithm::ALL {
+            let mut v = [5, 1, 4, 2, 3];
+            let stats = sort_stats(algorithm, &mut v);
+            assert_eq!(v, [1, 2, 3, 4, 5], "{:?}", algorithm);
+            assert!(stats.comparisons > 0);
+            assert_eq!(sort_stats(algorithm, &mut [] as &mut [i64]), SortStats::default());
+        }
+    }
+
+    #[test]
+    fn is_sorted_edges() {
+        assert!(is_sorted::<i64>(&[]));
+        assert!(is_sorted(&[1]));
+        assert!(is_sorted(&[1, 1, 2]));
+        assert!(!is_sorted(&[2, 1]));
+    }
+}
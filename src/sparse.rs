@@ -0,0 +1,203 @@
+// a vector that only stores its non-zero entries, index -> value
+
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseVec {
+    len: usize,
+    // never holds a 0.0, set() removes entries instead
+    data: HashMap<usize, f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DimError {
+    OutOfBounds { index: usize, len: usize },
+    LengthMismatch { left: usize, right: usize },
+}
+
+impl fmt::Display for DimError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DimError::OutOfBounds { index, len } => {
+                write!(f, "index {} is out of bounds for length {}", index, len)
+            }
+            DimError::LengthMismatch { left, right } => {
+                write!(f, "vectors of length {} and {} don't match", left, right)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DimError {}
+
+impl SparseVec {
+    // all zeros
+    pub fn new(len: usize) -> SparseVec {
+        SparseVec { len, data: HashMap::new() }
+    }
+
+    pub fn from_dense(values: &[f64]) -> SparseVec {
+        let data = values.iter().copied().enumerate().filter(|(_, v)| *v != 0.0).collect();
+        SparseVec { len: values.len(), data }
+    }
+
+    pub fn to_dense(&self) -> Vec<f64> {
+        let mut out = vec![0.0; self.len];
+        for (&i, &v) in &self.data {
+            out[i] = v;
+        }
+        out
+    }
+
+    fn check(&self, index: usize) -> Result<(), DimError> {
+        if index < self.len {
+            Ok(())
+        } else {
+            Err(DimError::OutOfBounds { index, len: self.len })
+        }
+    }
+
+    // setting an entry to 0.0 removes it
+    pub fn set(&mut self, index: usize, value: f64) -> Result<(), DimError> {
+        self.check(index)?;
+        if value == 0.0 {
+            self.data.remove(&index);
+        } else {
+            self.data.insert(index, value);
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, index: usize) -> Result<f64, DimError> {
+        self.check(index)?;
+        Ok(self.data.get(&index).copied().unwrap_or(0.0))
+    }
+
+    fn same_len(&self, other: &SparseVec) -> Result<(), DimError> {
+        if self.len == other.len {
+            Ok(())
+        } else {
+            Err(DimError::LengthMismatch { left: self.len, right: other.len })
+        }
+    }
+
+    // walks the vector with fewer entries and looks each one up in the other
+    pub fn dot(&self, other: &SparseVec) -> Result<f64, DimError> {
+        self.same_len(other)?;
+        let (small, big) = if self.nnz() <= other.nnz() { (self, other) } else { (other, self) };
+        // no index in common is a dot product of 0.0 (see area::total_area on the fold)
+        Ok(small
+            .data
+            .iter()
+            .filter_map(|(i, a)| big.data.get(i).map(|b| a * b))
+            .fold(0.0, |total, x| total + x))
+    }
+
+    pub fn add(&self, other: &SparseVec) -> Result<SparseVec, DimError> {
+        self.same_len(other)?;
+        let mut out = self.clone();
+        for (&i, &v) in &other.data {
+            // entries that cancel out are removed by set
+            out.set(i, out.data.get(&i).copied().unwrap_or(0.0) + v)?;
+        }
+        Ok(out)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // number of non-zero entries
+    pub fn nnz(&self) -> usize {
+        self.data.len()
+    }
+
+    // the share of entries that are non-zero, 0.0 for an empty vector
+    pub fn density(&self) -> f64 {
+        if self.len == 0 { 0.0 } else { self.nnz() as f64 / self.len as f64 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disjoint_support_has_a_zero_dot_product() {
+        let a = SparseVec::from_dense(&[1.0, 0.0, 2.0, 0.0]);
+        let b = SparseVec::from_dense(&[0.0, 3.0, 0.0, 4.0]);
+        let dot = a.dot(&b).unwrap();
+        assert_eq!(dot, 0.0);
+        assert!(dot.is_sign_positive());
+    }
+
+    #[test]
+    fn overlapping_support() {
+        let a = SparseVec::from_dense(&[1.0, 2.0, 0.0, 3.0, 0.0]);
+        let b = SparseVec::from_dense(&[4.0, 0.0, 5.0, 6.0, 0.0]);
+        assert_eq!(a.dot(&b), Ok(4.0 + 18.0));
+        assert_eq!(b.dot(&a), Ok(22.0));
+        assert_eq!(a.dot(&a), Ok(14.0));
+        let sum = a.add(&b).unwrap();
+        assert_eq!(sum.to_dense(), [5.0, 2.0, 5.0, 9.0, 0.0]);
+        assert_eq!(sum.nnz(), 4);
+    }
+
+    #[test]
+    fn lengths_must_match() {
+        let a = SparseVec::new(3);
+        let b = SparseVec::new(4);
+        let err = DimError::LengthMismatch { left: 3, right: 4 };
+        assert_eq!(a.dot(&b), Err(err));
+        assert_eq!(a.add(&b), Err(err));
+        assert_eq!(err.to_string(), "vectors of length 3 and 4 don't match");
+    }
+
+    #[test]
+    fn out_of_bounds_errors_instead_of_growing() {
+        let mut v = SparseVec::new(3);
+        assert_eq!(v.set(3, 1.0), Err(DimError::OutOfBounds { index: 3, len: 3 }));
+        assert_eq!(v.get(10), Err(DimError::OutOfBounds { index: 10, len: 3 }));
+        assert_eq!(v.len(), 3);
+        assert_eq!(v.nnz(), 0);
+        assert_eq!(v.get(2), Ok(0.0));
+    }
+
+    #[test]
+    fn setting_zero_removes_the_entry() {
+        let mut v = SparseVec::new(4);
+        v.set(1, 2.5).unwrap();
+        v.set(3, -1.0).unwrap();
+        assert_eq!(v.nnz(), 2);
+        assert_eq!(v.density(), 0.5);
+        v.set(1, 0.0).unwrap();
+        assert_eq!(v.nnz(), 1);
+        assert_eq!(v.get(1), Ok(0.0));
+        // a sum that cancels out is removed too
+        let minus = SparseVec::from_dense(&[0.0, 0.0, 0.0, 1.0]);
+        let sum = v.add(&minus).unwrap();
+        assert_eq!(sum.nnz(), 0);
+        assert_eq!(sum, SparseVec::new(4));
+    }
+
+    #[test]
+    fn dense_round_trips() {
+        let values = [0.0, 1.5, 0.0, 0.0, -2.0];
+        let v = SparseVec::from_dense(&values);
+        assert_eq!(v.nnz(), 2);
+        assert_eq!(v.to_dense(), values);
+        let zeros = SparseVec::from_dense(&[0.0; 6]);
+        assert_eq!(zeros.nnz(), 0);
+        assert_eq!(zeros.density(), 0.0);
+        assert_eq!(zeros.to_dense(), [0.0; 6]);
+        let empty = SparseVec::from_dense(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.density(), 0.0);
+        assert!(empty.to_dense().is_empty());
+    }
+}
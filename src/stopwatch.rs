@@ -0,0 +1,204 @@
+// a stopwatch with laps and pause/resume
+// it asks a Clock for the time, so a fake clock can drive it without sleeping
+
+use std::time::{Duration, Instant};
+
+use crate::ring_buffer::{self, RingBuffer};
+
+// how many laps recent_laps() remembers
+pub const RECENT_LAPS: usize = 5;
+
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+// lets a stopwatch borrow a clock that the caller keeps controlling
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+}
+
+pub struct Stopwatch<C: Clock = SystemClock> {
+    clock: C,
+    // when the current running stretch began, None while paused
+    running_since: Option<Instant>,
+    // running time from the stretches before the current one
+    banked: Duration,
+    // elapsed() at the moment of the last lap
+    last_lap_at: Duration,
+    laps: Vec<Duration>,
+    recent: RingBuffer<Duration>,
+}
+
+impl Stopwatch<SystemClock> {
+    pub fn start() -> Stopwatch<SystemClock> {
+        Stopwatch::start_with_clock(SystemClock)
+    }
+}
+
+impl<C: Clock> Stopwatch<C> {
+    pub fn start_with_clock(clock: C) -> Stopwatch<C> {
+        let now = clock.now();
+        Stopwatch {
+            clock,
+            running_since: Some(now),
+            banked: Duration::ZERO,
+            last_lap_at: Duration::ZERO,
+            laps: Vec::new(),
+            recent: RingBuffer::with_capacity(RECENT_LAPS).unwrap(),
+        }
+    }
+
+    // total running time, paused stretches don't count
+    pub fn elapsed(&self) -> Duration {
+        match self.running_since {
+            Some(since) => self.banked + (self.clock.now() - since),
+            None => self.banked,
+        }
+    }
+
+    // records the running time since the previous lap (or the start)
+    // while paused that is whatever ran before the pause
+    pub fn lap(&mut self) -> Duration {
+        let now = self.elapsed();
+        let lap = now - self.last_lap_at;
+        self.last_lap_at = now;
+        self.laps.push(lap);
+        self.recent.push(lap);
+        lap
+    }
+
+    pub fn pause(&mut self) {
+        if let Some(since) = self.running_since.take() {
+            self.banked += self.clock.now() - since;
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if self.running_since.is_none() {
+            self.running_since = Some(self.clock.now());
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running_since.is_some()
+    }
+
+    pub fn laps(&self) -> &[Duration] {
+        &self.laps
+    }
+
+    // the last RECENT_LAPS laps, oldest first
+    pub fn recent_laps(&self) -> ring_buffer::Iter<'_, Duration> {
+        self.recent.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    // only moves when a test tells it to
+    struct FakeClock(Cell<Instant>);
+
+    impl FakeClock {
+        fn new() -> FakeClock {
+            FakeClock(Cell::new(Instant::now()))
+        }
+
+        fn advance(&self, ms: u64) {
+            self.0.set(self.0.get() + Duration::from_millis(ms));
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> Instant {
+            self.0.get()
+        }
+    }
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn laps_are_the_time_between_them() {
+        let clock = FakeClock::new();
+        let mut watch = Stopwatch::start_with_clock(&clock);
+        clock.advance(100);
+        assert_eq!(watch.lap(), ms(100));
+        clock.advance(250);
+        assert_eq!(watch.lap(), ms(250));
+        clock.advance(50);
+        assert_eq!(watch.lap(), ms(50));
+        assert_eq!(watch.laps(), [ms(100), ms(250), ms(50)]);
+        assert_eq!(watch.elapsed(), ms(400));
+    }
+
+    #[test]
+    fn paused_time_is_not_counted() {
+        let clock = FakeClock::new();
+        let mut watch = Stopwatch::start_with_clock(&clock);
+        clock.advance(100);
+        watch.pause();
+        assert!(!watch.is_running());
+        clock.advance(1000);
+        assert_eq!(watch.elapsed(), ms(100));
+        watch.resume();
+        clock.advance(20);
+        assert_eq!(watch.elapsed(), ms(120));
+    }
+
+    #[test]
+    fn pausing_or_resuming_twice_changes_nothing() {
+        let clock = FakeClock::new();
+        let mut watch = Stopwatch::start_with_clock(&clock);
+        clock.advance(10);
+        watch.resume();
+        clock.advance(10);
+        watch.pause();
+        watch.pause();
+        clock.advance(500);
+        assert_eq!(watch.elapsed(), ms(20));
+    }
+
+    #[test]
+    fn a_lap_while_paused_is_the_time_since_the_last_lap() {
+        let clock = FakeClock::new();
+        let mut watch = Stopwatch::start_with_clock(&clock);
+        clock.advance(100);
+        watch.lap();
+        clock.advance(30);
+        watch.pause();
+        clock.advance(900);
+        assert_eq!(watch.lap(), ms(30));
+        // nothing ran since, so another lap is empty
+        assert_eq!(watch.lap(), Duration::ZERO);
+        watch.resume();
+        clock.advance(5);
+        assert_eq!(watch.lap(), ms(5));
+    }
+
+    #[test]
+    fn recent_laps_keep_the_last_few() {
+        let clock = FakeClock::new();
+        let mut watch = Stopwatch::start_with_clock(&clock);
+        for n in 1..=7 {
+            clock.advance(n);
+            watch.lap();
+        }
+        let recent: Vec<Duration> = watch.recent_laps().copied().collect();
+        assert_eq!(recent, [ms(3), ms(4), ms(5), ms(6), ms(7)]);
+        assert_eq!(watch.laps().len(), 7);
+    }
+}
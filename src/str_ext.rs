@@ -0,0 +1,143 @@
+// a few string helpers, available on every &str once StrExt is in scope
+
+use std::str::FromStr;
+
+pub trait StrExt {
+    fn parse_or<T: FromStr>(&self, default: T) -> T;
+    fn truncate_chars(&self, n: usize) -> &str;
+    fn indent(&self, spaces: usize) -> String;
+    fn to_snake_case(&self) -> String;
+    fn split_once_trimmed(&self, sep: char) -> Option<(&str, &str)>;
+}
+
+impl StrExt for str {
+    // surrounding whitespace is ignored, anything else that doesn't parse gives `default`
+    fn parse_or<T: FromStr>(&self, default: T) -> T {
+        self.trim().parse().unwrap_or(default)
+    }
+
+    // the first n chars, not bytes, so a multibyte char is never cut in half
+    fn truncate_chars(&self, n: usize) -> &str {
+        match self.char_indices().nth(n) {
+            Some((end, _)) => &self[..end],
+            None => self,
+        }
+    }
+
+    // every line gets `spaces` in front, blank lines stay blank so there is
+    // no trailing whitespace, and a final newline is kept as it was
+    fn indent(&self, spaces: usize) -> String {
+        let pad = " ".repeat(spaces);
+        let mut out = String::with_capacity(self.len());
+        for line in self.split_inclusive('\n') {
+            if !line.trim().is_empty() {
+                out.push_str(&pad);
+            }
+            out.push_str(line);
+        }
+        out
+    }
+
+    // camelCase, PascalCase, kebab-case and "some words" all become snake_case.
+    // a run of capitals is one word, so HTTPServer is http_server
+    fn to_snake_case(&self) -> String {
+        let chars: Vec<char> = self.trim().chars().collect();
+        let mut out = String::with_capacity(self.len() + 4);
+        for (i, &c) in chars.iter().enumerate() {
+            if c == '-' || c == '_' || c.is_whitespace() {
+                if !out.is_empty() && !out.ends_with('_') {
+                    out.push('_');
+                }
+                continue;
+            }
+            if c.is_uppercase() && i > 0 && !out.ends_with('_') {
+                let prev = chars[i - 1];
+                let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+                let word_ends = prev.is_lowercase() || prev.is_ascii_digit();
+                if word_ends || (prev.is_uppercase() && next_is_lower) {
+                    out.push('_');
+                }
+            }
+            out.extend(c.to_lowercase());
+        }
+        // "trailing-" shouldn't leave an underscore behind
+        if out.ends_with('_') {
+            out.pop();
+        }
+        out
+    }
+
+    // like split_once, with both halves trimmed
+    fn split_once_trimmed(&self, sep: char) -> Option<(&str, &str)> {
+        self.split_once(sep).map(|(a, b)| (a.trim(), b.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_chars_counts_chars_not_bytes() {
+        assert_eq!("Здравствуйте".truncate_chars(3), "Здр");
+        assert_eq!("Здр".truncate_chars(3), "Здр");
+        assert_eq!("日本語のテキスト".truncate_chars(2), "日本");
+        assert_eq!("a🦀b".truncate_chars(2), "a🦀");
+        // é written as e plus a combining accent is two chars
+        assert_eq!("e\u{301}t".truncate_chars(1), "e");
+        assert_eq!("short".truncate_chars(10), "short");
+        assert_eq!("abc".truncate_chars(0), "");
+        assert_eq!("".truncate_chars(3), "");
+    }
+
+    #[test]
+    fn snake_case_from_the_usual_styles() {
+        let cases = [
+            ("camelCase", "camel_case"),
+            ("PascalCase", "pascal_case"),
+            ("kebab-case-name", "kebab_case_name"),
+            ("already_snake", "already_snake"),
+            ("Two words", "two_words"),
+            ("HTTPServer", "http_server"),
+            ("parseHTTPResponse", "parse_http_response"),
+            ("version2Beta", "version2_beta"),
+            ("  -Leading and trailing- ", "leading_and_trailing"),
+            ("a--b__c  d", "a_b_c_d"),
+            ("Ünïcode Wörds", "ünïcode_wörds"),
+            ("", ""),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.to_snake_case(), expected, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn indent_pads_every_line_and_keeps_the_ending() {
+        assert_eq!("one\ntwo".indent(2), "  one\n  two");
+        assert_eq!("one\ntwo\n".indent(4), "    one\n    two\n");
+        // a blank line in the middle gets no trailing spaces
+        assert_eq!("a\n\nb\n".indent(2), "  a\n\n  b\n");
+        assert_eq!("a\r\nb".indent(1), " a\r\n b");
+        assert_eq!("x".indent(0), "x");
+        assert_eq!("".indent(3), "");
+    }
+
+    #[test]
+    fn parse_or_falls_back_on_anything_that_doesnt_parse() {
+        assert_eq!(" 42 ".parse_or(0), 42);
+        assert_eq!("forty".parse_or(7), 7);
+        assert_eq!("".parse_or(-1), -1);
+        assert_eq!("300".parse_or(0u8), 0);
+        assert_eq!("2.5".parse_or(0.0), 2.5);
+        assert!(!"yes".parse_or(false));
+        assert!("true".parse_or(false));
+    }
+
+    #[test]
+    fn split_once_trimmed_splits_at_the_first_separator() {
+        assert_eq!("file = a.txt".split_once_trimmed('='), Some(("file", "a.txt")));
+        assert_eq!("k=a=b".split_once_trimmed('='), Some(("k", "a=b")));
+        assert_eq!(" k = ".split_once_trimmed('='), Some(("k", "")));
+        assert_eq!("no separator".split_once_trimmed('='), None);
+    }
+}
@@ -0,0 +1,179 @@
+// ANSI colors for terminal output
+// the mode is global so every bin can set it once at startup
+
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::lazy::Lazy;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorMode {
+    // color only when stdout is a terminal
+    Auto,
+    Always,
+    Never,
+}
+
+static MODE: AtomicU8 = AtomicU8::new(0);
+
+// stdout doesn't stop being a terminal halfway through, so Auto asks once
+static STDOUT_IS_TERMINAL: Lazy<bool> = Lazy::new(|| std::io::stdout().is_terminal());
+
+impl ColorMode {
+    fn to_u8(self) -> u8 {
+        match self {
+            ColorMode::Auto => 0,
+            ColorMode::Always => 1,
+            ColorMode::Never => 2,
+        }
+    }
+
+    fn from_u8(value: u8) -> ColorMode {
+        match value {
+            1 => ColorMode::Always,
+            2 => ColorMode::Never,
+            _ => ColorMode::Auto,
+        }
+    }
+
+    // picks the mode from --color=never|always|auto, falling back to NO_COLOR
+    // an explicit --color flag wins, NO_COLOR only replaces Auto
+    pub fn from_args_and_env(args: &[String], no_color: Option<&str>) -> ColorMode {
+        let flag = args.iter().rev().find_map(|arg| arg.strip_prefix("--color="));
+        match flag {
+            Some("always") => ColorMode::Always,
+            Some("never") => ColorMode::Never,
+            _ => match no_color {
+                Some(value) if !value.is_empty() => ColorMode::Never,
+                _ => ColorMode::Auto,
+            },
+        }
+    }
+}
+
+pub fn set_color_mode(mode: ColorMode) {
+    MODE.store(mode.to_u8(), Ordering::Relaxed);
+}
+
+pub fn color_mode() -> ColorMode {
+    ColorMode::from_u8(MODE.load(Ordering::Relaxed))
+}
+
+// reads the command line and NO_COLOR and sets the global mode
+pub fn init_from_env() {
+    let args: Vec<String> = std::env::args().collect();
+    let no_color = std::env::var("NO_COLOR").ok();
+    set_color_mode(ColorMode::from_args_and_env(&args, no_color.as_deref()));
+}
+
+fn enabled() -> bool {
+    match color_mode() {
+        ColorMode::Always => true,
+        ColorMode::Never => false,
+        ColorMode::Auto => *STDOUT_IS_TERMINAL.get(),
+    }
+}
+
+fn paint(code: &str, text: &str) -> String {
+    if enabled() {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+pub fn bold(text: &str) -> String {
+    paint("1", text)
+}
+
+pub fn green(text: &str) -> String {
+    paint("32", text)
+}
+
+pub fn red(text: &str) -> String {
+    paint("31", text)
+}
+
+pub fn dim(text: &str) -> String {
+    paint("2", text)
+}
+
+// bold and underlined, for the title of a block of output
+pub fn heading(text: &str) -> String {
+    paint("1;4", text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // the mode is global, so tests that change it take turns
+    static MODE_LOCK: Mutex<()> = Mutex::new(());
+
+    fn with_mode<T>(mode: ColorMode, f: impl FnOnce() -> T) -> T {
+        let _guard = MODE_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let old = color_mode();
+        set_color_mode(mode);
+        let value = f();
+        set_color_mode(old);
+        value
+    }
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn always_wraps_text_in_exact_escape_codes() {
+        with_mode(ColorMode::Always, || {
+            assert_eq!(bold("hi").as_bytes(), b"\x1b[1mhi\x1b[0m");
+            assert_eq!(green("ok"), "\x1b[32mok\x1b[0m");
+            assert_eq!(red("no"), "\x1b[31mno\x1b[0m");
+            assert_eq!(dim("meh"), "\x1b[2mmeh\x1b[0m");
+            assert_eq!(heading("Title"), "\x1b[1;4mTitle\x1b[0m");
+        });
+    }
+
+    #[test]
+    fn never_passes_text_through_without_escapes() {
+        with_mode(ColorMode::Never, || {
+            for styled in [bold("hi"), green("ok"), red("no"), dim("meh"), heading("Title")] {
+                assert!(!styled.contains('\x1b'), "{:?}", styled);
+            }
+            assert_eq!(heading("Title"), "Title");
+            assert_eq!(red(""), "");
+        });
+    }
+
+    #[test]
+    fn the_color_flag_picks_the_mode() {
+        assert_eq!(ColorMode::from_args_and_env(&args(&["bin"]), None), ColorMode::Auto);
+        let always = args(&["bin", "--color=always"]);
+        assert_eq!(ColorMode::from_args_and_env(&always, None), ColorMode::Always);
+        let never = args(&["bin", "--color=never"]);
+        assert_eq!(ColorMode::from_args_and_env(&never, None), ColorMode::Never);
+        // the last flag wins
+        let both = args(&["bin", "--color=never", "--color=always"]);
+        assert_eq!(ColorMode::from_args_and_env(&both, None), ColorMode::Always);
+    }
+
+    #[test]
+    fn no_color_forces_never_unless_the_flag_says_always() {
+        let plain = args(&["bin"]);
+        assert_eq!(ColorMode::from_args_and_env(&plain, Some("1")), ColorMode::Never);
+        // NO_COLOR only counts when it is set to something
+        assert_eq!(ColorMode::from_args_and_env(&plain, Some("")), ColorMode::Auto);
+        let auto = args(&["bin", "--color=auto"]);
+        assert_eq!(ColorMode::from_args_and_env(&auto, Some("1")), ColorMode::Never);
+        let always = args(&["bin", "--color=always"]);
+        assert_eq!(ColorMode::from_args_and_env(&always, Some("1")), ColorMode::Always);
+    }
+
+    #[test]
+    fn the_mode_round_trips_through_the_atomic() {
+        for mode in [ColorMode::Auto, ColorMode::Always, ColorMode::Never] {
+            assert_eq!(with_mode(mode, color_mode), mode);
+        }
+    }
+}
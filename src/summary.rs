@@ -0,0 +1,271 @@
+// the Summarize trait from trait_bounds.rs, with default methods and impls
+// for the shared types
+
+use std::io::{self, Write};
+
+use crate::point::Point;
+use crate::rect::Rect;
+use crate::shape::{calc_area, Shape};
+use crate::str_ext::StrExt;
+use crate::user::User;
+
+const SHORT_LEN: usize = 40;
+
+// only summary has to be written, the rest come for free
+pub trait Summarize {
+    fn summary(&self) -> String;
+
+    // at most 40 chars, counted as chars so a multibyte one is never cut in half
+    fn summary_short(&self) -> String {
+        let summary = self.summary();
+        if summary.chars().count() <= SHORT_LEN {
+            return summary;
+        }
+        format!("{}…", summary.truncate_chars(SHORT_LEN - 1))
+    }
+
+    fn summarize_to(&self, w: &mut dyn Write) -> io::Result<()> {
+        writeln!(w, "{}", self.summary())
+    }
+}
+
+// every method is passed on, so an overridden summary_short still wins
+// when the value is behind a reference or a box
+impl<T: Summarize + ?Sized> Summarize for &T {
+    fn summary(&self) -> String {
+        (**self).summary()
+    }
+
+    fn summary_short(&self) -> String {
+        (**self).summary_short()
+    }
+
+    fn summarize_to(&self, w: &mut dyn Write) -> io::Result<()> {
+        (**self).summarize_to(w)
+    }
+}
+
+// ?Sized lets Box<dyn Summarize> in as well
+impl<T: Summarize + ?Sized> Summarize for Box<T> {
+    fn summary(&self) -> String {
+        (**self).summary()
+    }
+
+    fn summary_short(&self) -> String {
+        (**self).summary_short()
+    }
+
+    fn summarize_to(&self, w: &mut dyn Write) -> io::Result<()> {
+        (**self).summarize_to(w)
+    }
+}
+
+pub fn print_summary<T: Summarize + ?Sized>(item: &T) {
+    println!("{}", item.summary_short());
+}
+
+// any mix of Summarize types in one list. Box<dyn Summarize> works because
+// the trait is object safe: no generic methods, and summarize_to takes a
+// &mut dyn Write rather than a generic W: Write
+#[derive(Default)]
+pub struct SummaryReport {
+    items: Vec<Box<dyn Summarize>>,
+}
+
+impl SummaryReport {
+    pub fn new() -> SummaryReport {
+        SummaryReport { items: Vec::new() }
+    }
+
+    pub fn add(&mut self, item: Box<dyn Summarize>) {
+        self.items.push(item);
+    }
+
+    // boxes it for you, 'static because the report keeps it
+    pub fn add_item<T: Summarize + 'static>(&mut self, item: T) {
+        self.add(Box::new(item));
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    // "1. ..." per line in the order they were added, an empty report is ""
+    pub fn render(&self) -> String {
+        numbered(self.items.iter().map(|item| item.summary()).collect())
+    }
+
+    // the same, sorted by the summary text
+    pub fn render_sorted(&self) -> String {
+        let mut summaries: Vec<String> = self.items.iter().map(|item| item.summary()).collect();
+        summaries.sort();
+        numbered(summaries)
+    }
+}
+
+fn numbered(lines: Vec<String>) -> String {
+    lines.iter().enumerate().map(|(i, line)| format!("{}. {}\n", i + 1, line)).collect()
+}
+
+impl Summarize for User {
+    fn summary(&self) -> String {
+        format!("{} (@{}, #{}) <{}>", self.name, self.username, self.user_id, self.email)
+    }
+}
+
+impl Summarize for Shape {
+    fn summary(&self) -> String {
+        match calc_area(self) {
+            Ok(area) => format!("{:?} with area {:.2}", self, area),
+            Err(err) => format!("{:?} ({})", self, err),
+        }
+    }
+}
+
+impl Summarize for Rect {
+    fn summary(&self) -> String {
+        // in u64, a big rect's u32 area would overflow
+        let (len, breadth) = (self.len as u64, self.breadth as u64);
+        let (area, peri) = (len * breadth, 2 * (len + breadth));
+        format!("{}x{} rect, area {}, perimeter {}", self.len, self.breadth, area, peri)
+    }
+}
+
+// a point is already short, so it says it differently instead of truncating
+impl Summarize for Point {
+    fn summary(&self) -> String {
+        format!("point at x = {}, y = {}", self.x, self.y)
+    }
+
+    fn summary_short(&self) -> String {
+        format!("({}, {})", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Text(String);
+
+    impl Summarize for Text {
+        fn summary(&self) -> String {
+            self.0.clone()
+        }
+    }
+
+    fn text(s: &str) -> Text {
+        Text(s.to_string())
+    }
+
+    #[test]
+    fn short_summaries_are_left_alone() {
+        assert_eq!(text("").summary_short(), "");
+        let forty = "a".repeat(40);
+        assert_eq!(text(&forty).summary_short(), forty);
+    }
+
+    #[test]
+    fn long_summaries_are_cut_to_forty_chars_with_an_ellipsis() {
+        let long = "b".repeat(41);
+        let short = text(&long).summary_short();
+        assert_eq!(short, format!("{}…", "b".repeat(39)));
+        assert_eq!(short.chars().count(), 40);
+    }
+
+    #[test]
+    fn truncation_never_splits_a_multibyte_char() {
+        // 36 ascii chars, so the cut falls among the multibyte ones
+        let long = format!("{}naïve café ☕ über", "x".repeat(36));
+        let short = text(&long).summary_short();
+        assert_eq!(short.chars().count(), 40);
+        assert_eq!(short, format!("{}naï…", "x".repeat(36)));
+        // nothing but 4-byte chars
+        let emoji = text(&"🦀".repeat(50)).summary_short();
+        assert_eq!(emoji, format!("{}…", "🦀".repeat(39)));
+    }
+
+    #[test]
+    fn summarize_to_writes_the_full_summary() {
+        let mut out: Vec<u8> = Vec::new();
+        text(&"c".repeat(60)).summarize_to(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), format!("{}\n", "c".repeat(60)));
+    }
+
+    #[test]
+    fn references_and_boxes_pass_the_override_through() {
+        let point = Point::new(3, -4);
+        assert_eq!(point.summary(), "point at x = 3, y = -4");
+        assert_eq!(point.summary_short(), "(3, -4)");
+        assert_eq!((&&point).summary_short(), "(3, -4)");
+        print_summary(&&point);
+
+        let boxed: Box<dyn Summarize> = Box::new(point);
+        assert_eq!(boxed.summary_short(), "(3, -4)");
+        assert_eq!(Box::new(&point).summary(), "point at x = 3, y = -4");
+        let mut out: Vec<u8> = Vec::new();
+        boxed.summarize_to(&mut out).unwrap();
+        assert_eq!(out, b"point at x = 3, y = -4\n");
+    }
+
+    #[test]
+    fn the_shared_types_use_the_defaults() {
+        let rect = Rect::new(3, 4);
+        assert_eq!(rect.summary(), "3x4 rect, area 12, perimeter 14");
+        let big = Rect::new(u32::MAX, u32::MAX);
+        assert!(big.summary().contains("perimeter 17179869180"), "{}", big.summary());
+        assert_eq!(big.summary_short().chars().count(), 40);
+
+        assert_eq!(Shape::Circle(1.0).summary(), "Circle(1.0) with area 3.14");
+        assert_eq!(
+            Shape::Circle(-1.0).summary(),
+            "Circle(-1.0) (radius can't be negative, got -1)"
+        );
+        let user = User::new("Asha", "asha_1", "asha@example.com", 7, true).unwrap();
+        assert_eq!(user.summary(), "Asha (@asha_1, #7) <asha@example.com>");
+    }
+
+    #[test]
+    fn reports_number_items_in_order_or_sorted() {
+        let mut report = SummaryReport::new();
+        assert!(report.is_empty());
+        assert_eq!(report.render(), "");
+        report.add_item(text("zebra"));
+        report.add_item(Point::new(1, 2));
+        report.add(Box::new(text("apple")));
+        assert_eq!(report.len(), 3);
+        assert_eq!(report.render(), "1. zebra\n2. point at x = 1, y = 2\n3. apple\n");
+        assert_eq!(report.render_sorted(), "1. apple\n2. point at x = 1, y = 2\n3. zebra\n");
+    }
+
+    #[test]
+    fn a_mixed_report_of_users_points_and_shapes() {
+        let mut report = SummaryReport::default();
+        report.add_item(User::new("Ravi", "ravi", "ravi@example.com", 2, true).unwrap());
+        report.add_item(Point::new(0, 1));
+        report.add(Box::new(Shape::Rect(2.0, 3.0)));
+        report.add_item(Rect::new(1, 2));
+        assert_eq!(report.len(), 4);
+        assert_eq!(
+            report.render(),
+            "1. Ravi (@ravi, #2) <ravi@example.com>\n\
+             2. point at x = 0, y = 1\n\
+             3. Rect(2.0, 3.0) with area 6.00\n\
+             4. 1x2 rect, area 2, perimeter 6\n"
+        );
+        // sorted by the text, so capitals and digits come before lowercase
+        assert_eq!(
+            report.render_sorted(),
+            "1. 1x2 rect, area 2, perimeter 6\n\
+             2. Ravi (@ravi, #2) <ravi@example.com>\n\
+             3. Rect(2.0, 3.0) with area 6.00\n\
+             4. point at x = 0, y = 1\n"
+        );
+        // render uses the full summary, never the short one
+        assert!(!report.render().contains("(0, 1)"));
+    }
+}
@@ -0,0 +1,230 @@
+// word counting for text files
+
+use std::collections::HashMap;
+use std::thread;
+
+use crate::counter::Counter;
+use crate::parallel::thread_count;
+
+// lowercased words made of letters and digits in any script, an apostrophe
+// inside a word stays ("don't") but quotes around a word are dropped
+pub fn word_frequencies(text: &str) -> Counter<String> {
+    let lowered = text.to_lowercase().replace('\u{2019}', "'");
+    lowered
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|token| token.trim_matches('\''))
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// most frequent first, equal counts in alphabetical order
+pub fn top_n(freqs: &Counter<String>, n: usize) -> Vec<(String, usize)> {
+    // a Counter only keeps counts above zero
+    freqs.most_common(n).into_iter().map(|(word, count)| (word, count as usize)).collect()
+}
+
+// cuts the text into about `parts` pieces, each cut is moved forward to the
+// next whitespace char so no word is split and no char is cut in half
+fn split_at_whitespace(text: &str, parts: usize) -> Vec<&str> {
+    let target = text.len().div_ceil(parts.max(1)).max(1);
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = (start + target).min(text.len());
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+        end = match text[end..].find(char::is_whitespace) {
+            Some(offset) => end + offset,
+            None => text.len(),
+        };
+        pieces.push(&text[start..end]);
+        start = end;
+    }
+    pieces
+}
+
+// word_frequencies on one scoped thread per piece, merged at the end.
+// 0 threads means one per core
+pub fn parallel_word_count(text: &str, threads: usize) -> HashMap<String, usize> {
+    let pieces = split_at_whitespace(text, thread_count(threads));
+    let mut merged = HashMap::new();
+    thread::scope(|s| {
+        let handles: Vec<_> = pieces
+            .into_iter()
+            .map(|piece| s.spawn(move || word_frequencies(piece)))
+            .collect();
+        for handle in handles {
+            for (word, count) in handle.join().unwrap().iter() {
+                *merged.entry(word.clone()).or_insert(0) += count as usize;
+            }
+        }
+    });
+    merged
+}
+
+// same letters the same number of times, ignoring case and whitespace
+pub fn is_anagram(a: &str, b: &str) -> bool {
+    let letters = |s: &str| -> Counter<char> {
+        s.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect()
+    };
+    letters(a) == letters(b)
+}
+
+// the biggest count gets `width` blocks, the rest are scaled to it
+fn histogram_lines(pairs: &[(String, usize)], width: usize) -> Vec<String> {
+    let max = pairs.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    let label_width = pairs.iter().map(|(word, _)| word.chars().count()).max().unwrap_or(0);
+    pairs
+        .iter()
+        .map(|(word, count)| {
+            // every word that shows up gets at least one block
+            let len = (count * width).div_ceil(max.max(1));
+            format!("{:<label_width$} {} {}", word, "\u{2588}".repeat(len), count)
+        })
+        .collect()
+}
+
+pub fn print_histogram(pairs: &[(String, usize)], width: usize) {
+    for line in histogram_lines(pairs, width) {
+        println!("{}", line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(items: &[(&str, usize)]) -> Vec<(String, usize)> {
+        items.iter().map(|(word, count)| (word.to_string(), *count)).collect()
+    }
+
+    #[test]
+    fn counts_a_fixed_text() {
+        let freqs = word_frequencies("the cat and the hat and THE bat");
+        assert_eq!(freqs.count(&"the".to_string()), 3);
+        assert_eq!(freqs.count(&"and".to_string()), 2);
+        assert_eq!(freqs.count(&"cat".to_string()), 1);
+        assert_eq!(freqs.count(&"dog".to_string()), 0);
+        assert_eq!(freqs.len(), 5);
+        assert_eq!(freqs.total(), 8);
+        assert_eq!(top_n(&freqs, 2), pairs(&[("the", 3), ("and", 2)]));
+    }
+
+    #[test]
+    fn ties_are_alphabetical() {
+        let freqs = word_frequencies("pear apple fig pear fig apple kiwi");
+        assert_eq!(top_n(&freqs, 3), pairs(&[("apple", 2), ("fig", 2), ("pear", 2)]));
+        // n bigger than the number of words gives them all
+        assert_eq!(top_n(&freqs, 10).len(), 4);
+        assert!(top_n(&freqs, 0).is_empty());
+        // even the largest n just gives every word, it isn't an allocation size
+        let all = pairs(&[("apple", 2), ("fig", 2), ("pear", 2), ("kiwi", 1)]);
+        assert_eq!(top_n(&freqs, usize::MAX), all);
+        assert_eq!(top_n(&freqs, 1 << 40), all);
+    }
+
+    #[test]
+    fn empty_and_blank_text_have_no_words() {
+        assert!(word_frequencies("").is_empty());
+        assert!(word_frequencies(" \n\t ...!? --").is_empty());
+        assert!(top_n(&word_frequencies(""), 5).is_empty());
+    }
+
+    #[test]
+    fn punctuation_is_stripped_but_inner_apostrophes_stay() {
+        let freqs = word_frequencies("\"Don't!\" she said; 'don't,' he said... (don\u{2019}t)");
+        assert_eq!(freqs.count(&"don't".to_string()), 3);
+        assert_eq!(freqs.count(&"said".to_string()), 2);
+        assert_eq!(freqs.count(&"she".to_string()), 1);
+        assert_eq!(freqs.len(), 4);
+        // letters and digits in any script are words too
+        let freqs = word_frequencies("Über straße, 42 über-STRASSE");
+        assert_eq!(freqs.count(&"über".to_string()), 2);
+        assert_eq!(freqs.count(&"42".to_string()), 1);
+    }
+
+    #[test]
+    fn histogram_bars_are_scaled_to_the_biggest() {
+        let lines = histogram_lines(&pairs(&[("the", 10), ("a", 5), ("cat", 1)]), 4);
+        let expected = [
+            "the \u{2588}\u{2588}\u{2588}\u{2588} 10",
+            "a   \u{2588}\u{2588} 5",
+            "cat \u{2588} 1",
+        ];
+        assert_eq!(lines, expected);
+        assert!(histogram_lines(&[], 10).is_empty());
+    }
+
+    #[test]
+    fn anagrams_ignore_case_and_whitespace() {
+        assert!(is_anagram("listen", "silent"));
+        assert!(is_anagram("Dormitory", "dirty  room"));
+        assert!(is_anagram("a\tb\nc", "CBA"));
+        assert!(!is_anagram("aab", "abb"));
+        assert!(!is_anagram("abc", "abcd"));
+        assert!(is_anagram("", "   "));
+    }
+
+    #[test]
+    fn anagrams_in_unicode() {
+        assert!(is_anagram("été", "éét"));
+        assert!(!is_anagram("été", "ete"));
+        assert!(is_anagram("ÉTÉ", "été"));
+        assert!(is_anagram("日本語", "語 本 日"));
+    }
+
+    fn as_map(freqs: &Counter<String>) -> HashMap<String, usize> {
+        freqs.iter().map(|(word, count)| (word.clone(), count as usize)).collect()
+    }
+
+    fn corpus(seed: u64, words: usize) -> String {
+        let vocabulary = ["the", "Rust", "naïve", "émigré", "日本語", "don't", "ok!", "ß"];
+        let gaps = [" ", "  ", "\n", "\t", "\u{3000}", " \u{00a0}"];
+        let mut rng = crate::rng::Rng::new(seed);
+        let mut text = String::new();
+        for _ in 0..words {
+            let (word, gap) = (rng.pick(&vocabulary), rng.pick(&gaps));
+            text.push_str(word);
+            text.push_str(gap);
+        }
+        text
+    }
+
+    #[test]
+    fn parallel_count_matches_the_sequential_one() {
+        let text = corpus(169, 20_000);
+        let expected = as_map(&word_frequencies(&text));
+        for threads in [0, 1, 2, 3, 8, 33] {
+            assert_eq!(parallel_word_count(&text, threads), expected, "{} threads", threads);
+        }
+    }
+
+    #[test]
+    fn cuts_never_split_a_multi_byte_char_or_a_word() {
+        // every word is several bytes long, so most cut points land inside one
+        for seed in 0..20 {
+            let text = corpus(seed, 50);
+            for parts in 1..12 {
+                let pieces = split_at_whitespace(&text, parts);
+                assert_eq!(pieces.concat(), text);
+                for piece in &pieces[1..] {
+                    assert!(piece.starts_with(char::is_whitespace), "{:?}", piece);
+                }
+                assert_eq!(parallel_word_count(&text, parts), as_map(&word_frequencies(&text)));
+            }
+        }
+    }
+
+    #[test]
+    fn more_threads_than_words_and_empty_text() {
+        let text = "one two one";
+        let counts = parallel_word_count(text, 64);
+        assert_eq!(counts.len(), 2);
+        assert_eq!((counts["one"], counts["two"]), (2, 1));
+        assert!(parallel_word_count("", 4).is_empty());
+        assert!(parallel_word_count("   \n  ", 4).is_empty());
+        assert!(split_at_whitespace("", 4).is_empty());
+    }
+}
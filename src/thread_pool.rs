@@ -0,0 +1,235 @@
+// a fixed set of named worker threads running boxed closures from one queue.
+// a job that panics is logged and the worker moves on to the next one
+
+use std::fmt;
+use std::io;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use crate::isolate::panic_message;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+enum Message {
+    Job(Job),
+    Shutdown,
+}
+
+#[derive(Debug)]
+pub enum PoolError {
+    ZeroSize,
+    Spawn(io::Error),
+}
+
+impl fmt::Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PoolError::ZeroSize => write!(f, "a thread pool needs at least one worker"),
+            PoolError::Spawn(_) => write!(f, "could not start a worker thread"),
+        }
+    }
+}
+
+impl std::error::Error for PoolError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PoolError::ZeroSize => None,
+            PoolError::Spawn(err) => Some(err),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    active: AtomicUsize,
+    completed: AtomicUsize,
+}
+
+pub struct ThreadPool {
+    sender: Sender<Message>,
+    workers: Vec<JoinHandle<()>>,
+    counters: Arc<Counters>,
+}
+
+impl ThreadPool {
+    // workers are called worker-0, worker-1, ... which shows up in panic messages
+    pub fn new(size: usize) -> Result<ThreadPool, PoolError> {
+        if size == 0 {
+            return Err(PoolError::ZeroSize);
+        }
+        let (sender, receiver) = channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let counters = Arc::new(Counters::default());
+
+        let mut workers = Vec::with_capacity(size);
+        for i in 0..size {
+            let receiver = Arc::clone(&receiver);
+            let counters = Arc::clone(&counters);
+            let worker = thread::Builder::new()
+                .name(format!("worker-{}", i))
+                .spawn(move || worker_loop(receiver, counters))
+                .map_err(PoolError::Spawn)?;
+            workers.push(worker);
+        }
+        Ok(ThreadPool { sender, workers, counters })
+    }
+
+    pub fn execute(&self, f: impl FnOnce() + Send + 'static) {
+        // the workers only go away in drop, so the receiver is still there
+        self.sender.send(Message::Job(Box::new(f))).unwrap();
+    }
+
+    // jobs running right now
+    pub fn active_jobs(&self) -> usize {
+        self.counters.active.load(Ordering::SeqCst)
+    }
+
+    // jobs that have finished, panicked ones included
+    pub fn completed_jobs(&self) -> usize {
+        self.counters.completed.load(Ordering::SeqCst)
+    }
+}
+
+// the shutdown messages queue up behind the jobs already sent,
+// so every pending job still runs before the workers stop
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        for _ in &self.workers {
+            let _ = self.sender.send(Message::Shutdown);
+        }
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn worker_loop(receiver: Arc<Mutex<Receiver<Message>>>, counters: Arc<Counters>) {
+    loop {
+        // the lock is released at the end of this statement, before running the job
+        let message = receiver.lock().unwrap().recv();
+        let job = match message {
+            Ok(Message::Job(job)) => job,
+            Ok(Message::Shutdown) | Err(_) => break,
+        };
+        counters.active.fetch_add(1, Ordering::SeqCst);
+        if let Err(payload) = catch_unwind(AssertUnwindSafe(job)) {
+            let name = thread::current().name().unwrap_or("worker").to_string();
+            eprintln!("{}: a job panicked ({}), carrying on", name, panic_message(&*payload));
+        }
+        counters.active.fetch_sub(1, Ordering::SeqCst);
+        counters.completed.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    #[test]
+    fn a_pool_of_zero_is_rejected() {
+        assert!(matches!(ThreadPool::new(0), Err(PoolError::ZeroSize)));
+        assert_eq!(PoolError::ZeroSize.to_string(), "a thread pool needs at least one worker");
+    }
+
+    #[test]
+    fn a_hundred_jobs_all_run() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let pool = ThreadPool::new(4).unwrap();
+        for _ in 0..100 {
+            let count = Arc::clone(&count);
+            pool.execute(move || {
+                count.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        drop(pool);
+        assert_eq!(count.load(Ordering::SeqCst), 100);
+    }
+
+    #[test]
+    fn a_worker_carries_on_after_a_panic() {
+        // one worker, so the job after the panic runs on the same thread
+        let pool = ThreadPool::new(1).unwrap();
+        let (sender, receiver) = channel();
+        pool.execute(|| panic!("job went wrong"));
+        pool.execute(move || sender.send("still working").unwrap());
+        let reply = receiver.recv_timeout(Duration::from_secs(10));
+        assert_eq!(reply, Ok("still working"));
+        drop(pool);
+    }
+
+    #[test]
+    fn drop_runs_the_pending_jobs_first() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let pool = ThreadPool::new(2).unwrap();
+        for _ in 0..20 {
+            let count = Arc::clone(&count);
+            pool.execute(move || {
+                thread::sleep(Duration::from_millis(2));
+                count.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        // most of the jobs are still queued here
+        drop(pool);
+        assert_eq!(count.load(Ordering::SeqCst), 20);
+    }
+
+    #[test]
+    fn completed_counts_panics_and_active_goes_back_to_zero() {
+        let pool = ThreadPool::new(2).unwrap();
+        let (sender, receiver) = channel();
+        for n in 0..10 {
+            let sender = sender.clone();
+            pool.execute(move || {
+                let _guard = Guard(sender);
+                if n % 3 == 0 {
+                    panic!("job {} panics", n);
+                }
+            });
+        }
+        for _ in 0..10 {
+            receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+        }
+        // the counters are bumped right after a job ends, give the workers a moment
+        for _ in 0..1000 {
+            if pool.completed_jobs() == 10 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(pool.completed_jobs(), 10);
+        assert_eq!(pool.active_jobs(), 0);
+    }
+
+    // sends when dropped, so a panicking job still reports that it ended
+    struct Guard(Sender<()>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            let _ = self.0.send(());
+        }
+    }
+
+    #[test]
+    fn workers_are_named() {
+        let pool = ThreadPool::new(3).unwrap();
+        let (sender, receiver) = channel();
+        for _ in 0..30 {
+            let sender = sender.clone();
+            pool.execute(move || {
+                sender.send(thread::current().name().map(String::from)).unwrap();
+                thread::sleep(Duration::from_millis(1));
+            });
+        }
+        drop(pool);
+        drop(sender);
+        let names: HashSet<String> = receiver.iter().map(Option::unwrap).collect();
+        assert!(!names.is_empty());
+        let allowed = ["worker-0", "worker-1", "worker-2"].map(String::from);
+        assert!(names.iter().all(|name| allowed.contains(name)), "{:?}", names);
+    }
+}
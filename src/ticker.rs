@@ -0,0 +1,130 @@
+// a background thread that calls a function every `interval`
+// stopping wakes the thread right away instead of waiting out the interval
+
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::cancel::CancellationToken;
+
+pub struct Ticker {
+    token: CancellationToken,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Ticker {
+    // on_tick gets 1, 2, 3, ... one call per interval
+    pub fn start(interval: Duration, on_tick: impl FnMut(u64) + Send + 'static) -> Ticker {
+        Ticker::start_with_token(interval, &CancellationToken::new(), on_tick)
+    }
+
+    // also stops when `token` is cancelled. the ticker listens on a child
+    // token, so stopping the ticker leaves the caller's token alone
+    pub fn start_with_token(
+        interval: Duration,
+        token: &CancellationToken,
+        mut on_tick: impl FnMut(u64) + Send + 'static,
+    ) -> Ticker {
+        let token = token.child();
+        let thread_token = token.clone();
+        let handle = thread::spawn(move || {
+            let mut tick = 0;
+            while !thread_token.cancelled_or_sleep(interval) {
+                tick += 1;
+                on_tick(tick);
+            }
+        });
+        Ticker { token, handle: Some(handle) }
+    }
+
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            self.token.cancel();
+            let _ = handle.join();
+        }
+    }
+}
+
+// a ticker that goes out of scope stops too
+impl Drop for Ticker {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    // waits, up to a generous deadline, for `ticks` to reach `count`
+    fn wait_for(ticks: &Mutex<Vec<u64>>, count: usize) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while ticks.lock().unwrap().len() < count && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn collecting(ticks: &Arc<Mutex<Vec<u64>>>) -> impl FnMut(u64) + Send + 'static {
+        let ticks = Arc::clone(ticks);
+        move |tick| ticks.lock().unwrap().push(tick)
+    }
+
+    #[test]
+    fn ticks_arrive_counting_up_from_one() {
+        let ticks = Arc::new(Mutex::new(Vec::new()));
+        let ticker = Ticker::start(Duration::from_millis(2), collecting(&ticks));
+        wait_for(&ticks, 5);
+        ticker.stop();
+        let ticks = ticks.lock().unwrap();
+        assert!(ticks.len() >= 5, "only {} ticks", ticks.len());
+        assert_eq!(ticks[0], 1);
+        assert!(ticks.windows(2).all(|w| w[1] == w[0] + 1));
+    }
+
+    #[test]
+    fn stop_does_not_wait_out_a_long_interval() {
+        let ticks = Arc::new(Mutex::new(Vec::new()));
+        let ticker = Ticker::start(Duration::from_secs(10), collecting(&ticks));
+        thread::sleep(Duration::from_millis(20));
+        let started = Instant::now();
+        ticker.stop();
+        assert!(started.elapsed() < Duration::from_secs(2), "stop took {:?}", started.elapsed());
+        assert!(ticks.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn dropping_stops_the_thread() {
+        let ticks = Arc::new(Mutex::new(Vec::new()));
+        {
+            let _ticker = Ticker::start(Duration::from_millis(2), collecting(&ticks));
+            wait_for(&ticks, 2);
+        }
+        let after_drop = ticks.lock().unwrap().len();
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(ticks.lock().unwrap().len(), after_drop);
+    }
+
+    #[test]
+    fn cancelling_the_parent_token_stops_it_but_stopping_leaves_the_parent() {
+        let parent = CancellationToken::new();
+        let ticker = Ticker::start_with_token(Duration::from_secs(10), &parent, |_| {});
+        ticker.stop();
+        assert!(!parent.is_cancelled());
+
+        let ticks = Arc::new(Mutex::new(Vec::new()));
+        let ticker =
+            Ticker::start_with_token(Duration::from_millis(2), &parent, collecting(&ticks));
+        wait_for(&ticks, 1);
+        parent.cancel();
+        let at_cancel = ticks.lock().unwrap().len();
+        thread::sleep(Duration::from_millis(20));
+        // at most the tick that was already running when the token flipped
+        assert!(ticks.lock().unwrap().len() <= at_cancel + 1);
+        ticker.stop();
+    }
+}
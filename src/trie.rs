@@ -0,0 +1,200 @@
+// a prefix tree over chars, so a lookup costs the length of the word,
+// not the number of words. chars rather than bytes, so "café" is 4 steps
+
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+struct Node {
+    children: HashMap<char, Node>,
+    // a word ends here, not just passes through
+    is_word: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Trie {
+    root: Node,
+    len: usize,
+}
+
+impl Trie {
+    pub fn new() -> Trie {
+        Trie::default()
+    }
+
+    // number of words
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // the empty string is not a word, inserting it does nothing.
+    // false when the word was empty or already there
+    pub fn insert(&mut self, word: &str) -> bool {
+        if word.is_empty() {
+            return false;
+        }
+        let mut node = &mut self.root;
+        for c in word.chars() {
+            node = node.children.entry(c).or_default();
+        }
+        if node.is_word {
+            return false;
+        }
+        node.is_word = true;
+        self.len += 1;
+        true
+    }
+
+    fn find(&self, prefix: &str) -> Option<&Node> {
+        let mut node = &self.root;
+        for c in prefix.chars() {
+            node = node.children.get(&c)?;
+        }
+        Some(node)
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.find(word).is_some_and(|node| node.is_word)
+    }
+
+    // true for "" as long as there is at least one word
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        match self.find(prefix) {
+            Some(node) => node.is_word || !node.children.is_empty(),
+            None => false,
+        }
+    }
+
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let Some(start) = self.find(prefix) else {
+            return Vec::new();
+        };
+        let mut words = Vec::new();
+        let mut pending = vec![(start, prefix.to_string())];
+        while let Some((node, word)) = pending.pop() {
+            if node.is_word {
+                words.push(word.clone());
+            }
+            for (c, child) in &node.children {
+                let mut longer = word.clone();
+                longer.push(*c);
+                pending.push((child, longer));
+            }
+        }
+        words.sort();
+        words
+    }
+
+    // nodes that no longer lead to any word are cut off on the way back up,
+    // so removing "carton" leaves "cart" and "car" whole
+    pub fn remove(&mut self, word: &str) -> bool {
+        let chars: Vec<char> = word.chars().collect();
+        let removed = remove_from(&mut self.root, &chars);
+        if removed {
+            self.len -= 1;
+        }
+        removed
+    }
+}
+
+// true if the word was found and unmarked
+fn remove_from(node: &mut Node, rest: &[char]) -> bool {
+    let Some((first, tail)) = rest.split_first() else {
+        let was_word = node.is_word;
+        node.is_word = false;
+        return was_word;
+    };
+    let Some(child) = node.children.get_mut(first) else {
+        return false;
+    };
+    let removed = remove_from(child, tail);
+    if removed && !child.is_word && child.children.is_empty() {
+        node.children.remove(first);
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trie(words: &[&str]) -> Trie {
+        let mut trie = Trie::new();
+        for word in words {
+            trie.insert(word);
+        }
+        trie
+    }
+
+    // every node below the root
+    fn node_count(node: &Node) -> usize {
+        node.children.values().map(|child| 1 + node_count(child)).sum()
+    }
+
+    #[test]
+    fn overlapping_prefixes() {
+        let t = trie(&["carton", "car", "cart", "cat"]);
+        assert_eq!(t.len(), 4);
+        assert!(t.contains("car") && t.contains("cart") && t.contains("carton"));
+        assert!(!t.contains("ca") && !t.contains("carto") && !t.contains("cartons"));
+        assert!(t.starts_with("carto"));
+        assert!(!t.starts_with("cb"));
+        assert_eq!(t.words_with_prefix("car"), ["car", "cart", "carton"]);
+        assert_eq!(t.words_with_prefix("cart"), ["cart", "carton"]);
+        assert_eq!(t.words_with_prefix("ca"), ["car", "cart", "carton", "cat"]);
+        assert!(t.words_with_prefix("dog").is_empty());
+        // inserting again changes nothing
+        let mut t = t;
+        assert!(!t.insert("cart"));
+        assert_eq!(t.len(), 4);
+    }
+
+    #[test]
+    fn removal_keeps_the_other_words() {
+        let mut t = trie(&["car", "cart", "carton"]);
+        assert!(t.remove("cart"));
+        assert!(!t.contains("cart"));
+        assert!(t.contains("car") && t.contains("carton"));
+        // the t of "cart" still leads to "carton"
+        assert_eq!(node_count(&t.root), 6);
+        assert!(t.remove("carton"));
+        assert_eq!(t.words_with_prefix(""), ["car"]);
+        // "ton" and the "t" are pruned, "car" is left
+        assert_eq!(node_count(&t.root), 3);
+        assert!(!t.remove("carton"));
+        assert!(!t.remove("ca"));
+        assert!(!t.remove("dog"));
+        assert!(t.remove("car"));
+        assert_eq!(node_count(&t.root), 0);
+        assert!(t.is_empty());
+    }
+
+    #[test]
+    fn the_empty_string_is_not_a_word() {
+        let mut t = Trie::new();
+        assert!(!t.insert(""));
+        assert!(t.is_empty());
+        assert!(!t.contains(""));
+        assert!(!t.starts_with(""));
+        t.insert("a");
+        assert!(t.starts_with(""));
+        assert!(!t.contains(""));
+        assert!(!t.remove(""));
+        assert_eq!(t.len(), 1);
+    }
+
+    #[test]
+    fn unicode_words_are_walked_by_char() {
+        let t = trie(&["café", "cafés", "caffè", "日本", "日本語", "ñandú"]);
+        assert!(t.contains("café"));
+        assert!(!t.contains("cafe"));
+        assert_eq!(t.words_with_prefix("caf"), ["caffè", "café", "cafés"]);
+        assert_eq!(t.words_with_prefix("日"), ["日本", "日本語"]);
+        assert!(t.starts_with("ña"));
+        // one node per char, not per byte
+        assert_eq!(node_count(&trie(&["日本語"]).root), 3);
+    }
+}
@@ -0,0 +1,191 @@
+// the User from struct.rs, with a constructor that checks its fields
+
+use std::fmt;
+
+use crate::collections_ext::unique_by;
+use crate::validate::{ValidationIssue, Validator};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct User {
+    pub name: String,
+    pub username: String,
+    pub email: String,
+    pub user_id: i32,
+    pub is_indian: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserError {
+    EmptyName,
+    BadUsername(String),
+    BadEmail(String),
+    BadId(i32),
+}
+
+impl fmt::Display for UserError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UserError::EmptyName => write!(f, "name can't be empty"),
+            UserError::BadUsername(u) => {
+                write!(f, "username `{}` must be 3-20 lowercase letters, digits or _", u)
+            }
+            UserError::BadEmail(e) => write!(f, "`{}` is not a valid email", e),
+            UserError::BadId(id) => write!(f, "user id must be positive, got {}", id),
+        }
+    }
+}
+
+impl std::error::Error for UserError {}
+
+impl User {
+    pub fn new(
+        name: &str,
+        username: &str,
+        email: &str,
+        user_id: i32,
+        is_indian: bool,
+    ) -> Result<User, UserError> {
+        if name.trim().is_empty() {
+            return Err(UserError::EmptyName);
+        }
+        if !is_valid_username(username) {
+            return Err(UserError::BadUsername(username.to_string()));
+        }
+        if !is_valid_email(email) {
+            return Err(UserError::BadEmail(email.to_string()));
+        }
+        if user_id <= 0 {
+            return Err(UserError::BadId(user_id));
+        }
+        Ok(User {
+            name: name.to_string(),
+            username: username.to_string(),
+            email: email.to_string(),
+            user_id,
+            is_indian,
+        })
+    }
+
+    // the fields are public, so a user can be changed into a bad one after new.
+    // this lists everything wrong with it, with the same messages as UserError
+    pub fn validate(&self) -> Result<(), Vec<ValidationIssue>> {
+        Validator::new(self)
+            .ensure(!self.name.trim().is_empty(), &UserError::EmptyName.to_string())
+            .ensure_with(
+                |u| is_valid_username(&u.username),
+                || UserError::BadUsername(self.username.clone()).to_string(),
+            )
+            .ensure_with(
+                |u| is_valid_email(&u.email),
+                || UserError::BadEmail(self.email.clone()).to_string(),
+            )
+            .ensure_with(|u| u.user_id > 0, || UserError::BadId(self.user_id).to_string())
+            .finish()
+    }
+}
+
+// a placeholder for struct update syntax, not a real user:
+// User { name: "Asha".into(), ..Default::default() }
+// only the name is filled in, so validate() fails until the rest is set
+impl Default for User {
+    fn default() -> User {
+        User {
+            name: String::from("(placeholder)"),
+            username: String::new(),
+            email: String::new(),
+            user_id: 0,
+            is_indian: false,
+        }
+    }
+}
+
+// one user per email address, the first one wins. emails are compared
+// without case, so Pawan@Example.com and pawan@example.com are the same
+pub fn dedup_by_email(users: Vec<User>) -> Vec<User> {
+    unique_by(users, |user| user.email.to_lowercase())
+}
+
+fn is_valid_username(username: &str) -> bool {
+    (3..=20).contains(&username.len())
+        && username
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+// only the basic shape: something@something.something
+fn is_valid_email(email: &str) -> bool {
+    match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.split('.').count() >= 2
+                && domain.split('.').all(|part| !part.is_empty())
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(name: &str, email: &str) -> User {
+        User { name: name.to_string(), email: email.to_string(), ..User::default() }
+    }
+
+    #[test]
+    fn dedup_by_email_ignores_case_and_keeps_the_first() {
+        let users = vec![
+            user("Pawan", "Pawan@Example.com"),
+            user("Ravi", "ravi@example.com"),
+            user("Pawan again", "pawan@example.com"),
+        ];
+        let names: Vec<String> = dedup_by_email(users).into_iter().map(|u| u.name).collect();
+        assert_eq!(names, ["Pawan", "Ravi"]);
+    }
+
+    #[test]
+    fn validate_lists_every_problem_with_the_user_error_messages() {
+        let good = User::new("Asha", "asha_1", "asha@example.com", 7, true).unwrap();
+        assert_eq!(good.validate(), Ok(()));
+
+        let bad = User {
+            name: " ".to_string(),
+            username: "A".to_string(),
+            email: "nope".to_string(),
+            user_id: 0,
+            ..good.clone()
+        };
+        let got: Vec<String> = bad.validate().unwrap_err().iter().map(|i| i.to_string()).collect();
+        let expected = [
+            UserError::EmptyName,
+            UserError::BadUsername("A".to_string()),
+            UserError::BadEmail("nope".to_string()),
+            UserError::BadId(0),
+        ];
+        assert_eq!(got, expected.map(|e| e.to_string()));
+    }
+
+    #[test]
+    fn the_default_user_is_a_placeholder_that_fails_validation() {
+        let user = User::default();
+        assert_eq!(user.name, "(placeholder)");
+        assert_eq!((user.username.as_str(), user.email.as_str()), ("", ""));
+        assert_eq!((user.user_id, user.is_indian), (0, false));
+        // the name is set, so only the other three fields are reported
+        let issues = user.validate().unwrap_err();
+        assert_eq!(issues.len(), 3);
+
+        // struct update syntax fills the rest in from the default
+        let asha = User { name: "Asha".into(), ..Default::default() };
+        assert_eq!(asha.name, "Asha");
+        assert!(asha.validate().is_err());
+        let complete = User {
+            username: "asha".into(),
+            email: "asha@example.com".into(),
+            user_id: 1,
+            ..asha
+        };
+        assert_eq!(complete.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,183 @@
+// checks that report every problem at once instead of stopping at the first
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    pub message: String,
+}
+
+impl ValidationIssue {
+    pub fn new(message: impl Into<String>) -> ValidationIssue {
+        ValidationIssue { message: message.into() }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ValidationIssue {}
+
+// Validator::new(&user)
+//     .ensure(!user.name.is_empty(), "name can't be empty")
+//     .ensure_with(|u| u.user_id > 0, || format!("bad id {}", user.user_id))
+//     .finish()
+pub struct Validator<'a, T> {
+    value: &'a T,
+    issues: Vec<ValidationIssue>,
+}
+
+impl<'a, T> Validator<'a, T> {
+    pub fn new(value: &'a T) -> Validator<'a, T> {
+        Validator { value, issues: Vec::new() }
+    }
+
+    pub fn ensure(mut self, cond: bool, message: &str) -> Self {
+        if !cond {
+            self.issues.push(ValidationIssue::new(message));
+        }
+        self
+    }
+
+    // the message is only built when the check fails
+    pub fn ensure_with(
+        mut self,
+        check: impl FnOnce(&T) -> bool,
+        message: impl FnOnce() -> String,
+    ) -> Self {
+        if !check(self.value) {
+            self.issues.push(ValidationIssue::new(message()));
+        }
+        self
+    }
+
+    // for checks that already return a Result, the Err's message becomes the issue
+    pub fn check<E: fmt::Display>(mut self, result: Result<(), E>) -> Self {
+        if let Err(err) = result {
+            self.issues.push(ValidationIssue::new(err.to_string()));
+        }
+        self
+    }
+
+    // the issues of a part that was validated on its own are added as they are
+    pub fn nested(mut self, result: Result<(), Vec<ValidationIssue>>) -> Self {
+        if let Err(issues) = result {
+            self.issues.extend(issues);
+        }
+        self
+    }
+
+    // Ok when nothing failed, also when nothing was checked at all
+    pub fn finish(self) -> Result<(), Vec<ValidationIssue>> {
+        if self.issues.is_empty() { Ok(()) } else { Err(self.issues) }
+    }
+}
+
+pub type Check<T> = fn(&T) -> Result<(), ValidationIssue>;
+
+// every check runs, the issues of all the failing ones come back
+pub fn all_of<T>(value: &T, checks: &[Check<T>]) -> Result<(), Vec<ValidationIssue>> {
+    let issues: Vec<_> = checks.iter().filter_map(|check| check(value).err()).collect();
+    if issues.is_empty() { Ok(()) } else { Err(issues) }
+}
+
+// Ok as soon as one check passes, otherwise the issues of all of them.
+// no checks at all means nothing could pass
+pub fn any_of<T>(value: &T, checks: &[Check<T>]) -> Result<(), Vec<ValidationIssue>> {
+    let mut issues = Vec::new();
+    for check in checks {
+        match check(value) {
+            Ok(()) => return Ok(()),
+            Err(issue) => issues.push(issue),
+        }
+    }
+    if issues.is_empty() {
+        issues.push(ValidationIssue::new("no check to pass"));
+    }
+    Err(issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn messages(result: Result<(), Vec<ValidationIssue>>) -> Vec<String> {
+        result.unwrap_err().into_iter().map(|issue| issue.message).collect()
+    }
+
+    fn positive(n: &i32) -> Result<(), ValidationIssue> {
+        if *n > 0 { Ok(()) } else { Err(ValidationIssue::new("not positive")) }
+    }
+
+    fn even(n: &i32) -> Result<(), ValidationIssue> {
+        if n % 2 == 0 { Ok(()) } else { Err(ValidationIssue::new("not even")) }
+    }
+
+    fn small(n: &i32) -> Result<(), ValidationIssue> {
+        if n.abs() < 10 { Ok(()) } else { Err(ValidationIssue::new("not small")) }
+    }
+
+    #[test]
+    fn every_failing_check_is_reported_in_order() {
+        let n = -15;
+        let result = Validator::new(&n)
+            .ensure(n > 0, "must be positive")
+            .ensure(n != 0, "must not be zero")
+            .ensure_with(|v| v % 2 == 0, || format!("{} is odd", n))
+            .check("x".parse::<i32>().map(|_| ()))
+            .nested(Err(vec![ValidationIssue::new("inner")]))
+            .finish();
+        assert_eq!(
+            messages(result),
+            ["must be positive", "-15 is odd", "invalid digit found in string", "inner"]
+        );
+    }
+
+    #[test]
+    fn no_checks_and_all_passing_checks_succeed() {
+        assert_eq!(Validator::new(&1).finish(), Ok(()));
+        let result = Validator::new(&4)
+            .ensure(true, "unused")
+            .ensure_with(|v| *v == 4, String::new);
+        assert_eq!(result.finish(), Ok(()));
+    }
+
+    #[test]
+    fn closure_messages_are_only_built_on_failure() {
+        let built = Cell::new(0);
+        let message = || {
+            built.set(built.get() + 1);
+            "odd".to_string()
+        };
+        let passing = Validator::new(&2).ensure_with(|v| v % 2 == 0, message).finish();
+        assert!(passing.is_ok());
+        assert_eq!(built.get(), 0);
+        let failing = Validator::new(&3).ensure_with(|v| v % 2 == 0, message).finish();
+        assert_eq!(messages(failing), ["odd"]);
+        assert_eq!(built.get(), 1);
+    }
+
+    #[test]
+    fn all_of_collects_every_failure() {
+        let checks: [Check<i32>; 3] = [positive, even, small];
+        assert_eq!(all_of(&4, &checks), Ok(()));
+        assert_eq!(messages(all_of(&-11, &checks)), ["not positive", "not even", "not small"]);
+        assert_eq!(messages(all_of(&12, &checks)), ["not small"]);
+        assert_eq!(all_of(&0, &[]), Ok(()));
+    }
+
+    #[test]
+    fn any_of_passes_when_one_branch_passes() {
+        let checks: [Check<i32>; 3] = [positive, even, small];
+        // only small passes
+        assert_eq!(any_of(&-3, &checks), Ok(()));
+        // only even passes, and it's the middle one
+        assert_eq!(any_of(&-20, &checks), Ok(()));
+        assert_eq!(messages(any_of(&-11, &checks)), ["not positive", "not even", "not small"]);
+        assert_eq!(messages(any_of(&5, &[])), ["no check to pass"]);
+    }
+}